@@ -29,6 +29,7 @@ pub trait AnyEncodedString<'a> {
 pub enum EncodedString<'a> {
     Json(super::json::JsonEncodedString<'a>),
     Raw(super::raw::RawString<'a>),
+    SdParam(super::sdparam::SdParamString<'a>),
 }
 
 impl<'a> EncodedString<'a> {
@@ -42,11 +43,17 @@ impl<'a> EncodedString<'a> {
         EncodedString::Raw(super::raw::RawString::new(value))
     }
 
+    #[inline(always)]
+    pub fn sd_param(value: &'a str) -> Self {
+        EncodedString::SdParam(super::sdparam::SdParamString::new(value))
+    }
+
     #[inline(always)]
     pub fn source(&self) -> &'a str {
         match self {
             EncodedString::Json(string) => string.source(),
             EncodedString::Raw(string) => string.source(),
+            EncodedString::SdParam(string) => string.source(),
         }
     }
 
@@ -55,6 +62,7 @@ impl<'a> EncodedString<'a> {
         match self {
             EncodedString::Json(string) => string.is_empty(),
             EncodedString::Raw(string) => string.is_empty(),
+            EncodedString::SdParam(string) => string.is_empty(),
         }
     }
 }
@@ -67,6 +75,7 @@ impl<'a> AnyEncodedString<'a> for EncodedString<'a> {
         match self {
             EncodedString::Json(string) => string.decode(handler),
             EncodedString::Raw(string) => string.decode(handler),
+            EncodedString::SdParam(string) => string.decode(handler),
         }
     }
 
@@ -75,6 +84,7 @@ impl<'a> AnyEncodedString<'a> for EncodedString<'a> {
         match self {
             EncodedString::Json(string) => Self::Tokens::Json(string.tokens()),
             EncodedString::Raw(string) => Self::Tokens::Raw(string.tokens()),
+            EncodedString::SdParam(string) => Self::Tokens::SdParam(string.tokens()),
         }
     }
 
@@ -88,6 +98,7 @@ impl<'a> AnyEncodedString<'a> for EncodedString<'a> {
         match self {
             EncodedString::Json(string) => string.is_empty(),
             EncodedString::Raw(string) => string.is_empty(),
+            EncodedString::SdParam(string) => string.is_empty(),
         }
     }
 }
@@ -95,6 +106,7 @@ impl<'a> AnyEncodedString<'a> for EncodedString<'a> {
 pub enum EncodedStringTokens<'a> {
     Json(super::json::Tokens<'a>),
     Raw(super::raw::Tokens<'a>),
+    SdParam(super::sdparam::Tokens<'a>),
 }
 
 impl<'a> Iterator for EncodedStringTokens<'a> {
@@ -105,6 +117,7 @@ impl<'a> Iterator for EncodedStringTokens<'a> {
         match self {
             EncodedStringTokens::Json(tokens) => tokens.next(),
             EncodedStringTokens::Raw(tokens) => tokens.next(),
+            EncodedStringTokens::SdParam(tokens) => tokens.next(),
         }
     }
 }
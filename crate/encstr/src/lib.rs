@@ -1,6 +1,7 @@
 pub mod error;
 pub mod json;
 pub mod raw;
+pub mod sdparam;
 
 mod encstr;
 
@@ -0,0 +1,90 @@
+// local imports
+use super::*;
+
+// ---
+
+/// Value of a structured data parameter of an RFC 5424 syslog message, without the surrounding quotes.
+///
+/// Only `\"`, `\\` and `\]` are escape sequences, a backslash followed by any other character is taken literally.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct SdParamString<'a>(&'a str);
+
+impl<'a> SdParamString<'a> {
+    #[inline(always)]
+    pub fn new(value: &'a str) -> Self {
+        Self(value)
+    }
+
+    #[inline(always)]
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl<'a> AnyEncodedString<'a> for SdParamString<'a> {
+    type Tokens = Tokens<'a>;
+
+    #[inline]
+    fn decode<H: Handler>(&self, mut handler: H) -> Result<()> {
+        for token in self.tokens() {
+            handler.handle(token?);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn tokens(&self) -> Self::Tokens {
+        Tokens(self.0)
+    }
+
+    #[inline(always)]
+    fn source(&self) -> &'a str {
+        self.0
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> From<&'a str> for SdParamString<'a> {
+    #[inline(always)]
+    fn from(value: &'a str) -> Self {
+        Self::new(value)
+    }
+}
+
+// ---
+
+pub struct Tokens<'a>(&'a str);
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.0;
+        if s.is_empty() {
+            return None;
+        }
+
+        let bytes = s.as_bytes();
+        if let [b'\\', ch @ (b'"' | b'\\' | b']'), ..] = bytes {
+            self.0 = &s[2..];
+            return Some(Ok(Token::Char(*ch as char)));
+        }
+
+        // the sequence runs up to the next escape, a leading backslash that does not start one is literal
+        let end = (1..bytes.len())
+            .find(|&i| bytes[i] == b'\\' && matches!(bytes.get(i + 1), Some(b'"' | b'\\' | b']')))
+            .unwrap_or(bytes.len());
+        self.0 = &s[end..];
+        Some(Ok(Token::Sequence(&s[..end])))
+    }
+}
+
+// ---
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use super::*;
+
+fn decoded(value: &str) -> String {
+    let mut result = Builder::new();
+    SdParamString::new(value).decode(&mut result).unwrap();
+    result.into_string()
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(decoded(""), "");
+    assert_eq!(decoded("plain"), "plain");
+    assert_eq!(decoded(r#"a \"b\""#), r#"a "b""#);
+    assert_eq!(decoded(r"c\]"), "c]");
+    assert_eq!(decoded(r"x\\y"), r"x\y");
+}
+
+#[test]
+fn test_decode_literal_backslash() {
+    assert_eq!(decoded(r"C:\temp"), r"C:\temp");
+    assert_eq!(decoded(r"line\nbreak"), r"line\nbreak");
+    assert_eq!(decoded(r"\q"), r"\q");
+    assert_eq!(decoded(r#"C:\temp \"x\"\"#), r#"C:\temp "x"\"#);
+}
+
+#[test]
+fn test_tokens() {
+    let tokens = SdParamString::new(r#"a\"b"#)
+        .tokens()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        tokens,
+        vec![Token::Sequence("a"), Token::Char('"'), Token::Sequence("b")]
+    );
+}
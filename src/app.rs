@@ -1,15 +1,18 @@
 // std imports
 use std::{
     cmp::{Reverse, max},
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     convert::{TryFrom, TryInto},
     fs,
-    io::{BufWriter, Write},
+    io::{BufWriter, Read, Write},
     num::NonZeroUsize,
     ops::Range,
     path::PathBuf,
     rc::Rc,
-    sync::Arc,
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
     time::{Duration, Instant},
 };
 
@@ -18,6 +21,7 @@ use std::{
 use std::os::unix::fs::MetadataExt;
 
 // third-party imports
+use chrono::{DateTime, Utc};
 use closure::closure;
 use crossbeam_channel::{self as channel, Receiver, RecvTimeoutError, Sender};
 use crossbeam_utils::thread;
@@ -25,25 +29,51 @@ use enumset::{EnumSet, enum_set};
 use enumset_ext::EnumSetExt;
 use itertools::{Itertools, izip};
 use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use wildflower::Pattern;
 
 // local imports
 use crate::{
     IncludeExcludeKeyFilter,
     appdirs::AppDirs,
+    bookmark::{Bookmark, Position},
+    context::ContextTracker,
     datefmt::{DateTimeFormat, DateTimeFormatter},
+    dedup::Deduplicator,
     error::*,
-    fmtx::aligned_left,
-    formatting::{DynRecordWithSourceFormatter, RawRecordFormatter, RecordFormatterBuilder, RecordWithSourceFormatter},
+    fmtx::aligned_left_by_width,
+    formatting::{
+        DynRecordWithSourceFormatter, FieldQuote, NoOpRecordWithSourceFormatter, RawRecordFormatter,
+        RecordFormatterBuilder, RecordWithSourceFormatter, StyleRule,
+        columns::ColumnsRecordFormatter,
+        highlight::Highlighter,
+        html::{self, HtmlRecordFormatter},
+        json::JsonRecordFormatter,
+        logfmt::LogfmtRecordFormatter,
+        value::ValueRecordFormatter,
+        wrap::Wrapper,
+    },
     fsmon::{self, EventKind},
-    index::{Indexer, IndexerSettings, Timestamp},
-    input::{BlockLine, Input, InputHolder, InputReference},
-    model::{Filter, Parser, ParserSettings, RawRecord, Record, RecordFilter, RecordWithSourceConstructor},
+    gelf,
+    grouping::{self, Groups},
+    index::{IndexMode, Indexer, IndexerSettings, Timestamp},
+    input::{BlockLine, Decompression, Input, InputHolder, InputReference, Stream},
+    iox::{CompleteLines, LengthHeader, LengthPrefixed},
+    listen::{self, ListenAddress},
+    model::{Filter, ParseTrace, Parser, ParserSettings, RawRecord, Record, RecordFilter, RecordWithSourceConstructor},
+    otlp,
+    progress::Progress,
     query::Query,
-    scanning::{BufFactory, Delimit, Delimiter, Scanner, SearchExt, Segment, SegmentBuf, SegmentBufFactory},
+    scanning::{
+        BufFactory, Delimit, Delimiter, PartialPlacement, ReverseScanner, Scanner, SearchExt, Segment, SegmentBuf,
+        SegmentBufFactory,
+    },
     settings::{AsciiMode, FieldShowOption, Fields, Formatting, InputInfo, ResolvedPunctuation},
+    stats::{CountOptions, Stats, StatsOptions},
     theme::{Element, StylingPush, Theme},
     timezone::Tz,
     vfs::LocalFileSystem,
+    winevent,
 };
 
 // test imports
@@ -56,6 +86,7 @@ use crate::testing::Sample;
 
 pub struct Options {
     pub theme: Arc<Theme>,
+    pub colors: bool,
     pub time_format: DateTimeFormat,
     pub raw: bool,
     pub raw_fields: bool,
@@ -72,14 +103,55 @@ pub struct Options {
     pub follow: bool,
     pub sync_interval: Duration,
     pub input_info: InputInfoSet,
+    pub filename: Option<bool>,
     pub input_format: Option<InputFormat>,
+    pub output_format: OutputFormat,
+    pub html_standalone: bool,
     pub dump_index: bool,
+    pub progress: bool,
+    pub index_mode: IndexMode,
     pub app_dirs: Option<AppDirs>,
-    pub tail: u64,
+    pub tail: Option<u64>,
+    pub range: Option<RecordRange>,
     pub delimiter: Delimiter,
+    pub framing: Option<LengthHeader>,
+    pub decompression: Decompression,
     pub unix_ts_unit: Option<UnixTimestampUnit>,
     pub flatten: bool,
+    pub flatten_depth: Option<usize>,
     pub ascii: AsciiMode,
+    pub stats: Option<StatsOptions>,
+    pub count: Option<CountOptions>,
+    pub highlighter: Option<Arc<Highlighter>>,
+    pub output_fields: Vec<String>,
+    pub color_by: Option<String>,
+    pub strict: Option<StrictMode>,
+    pub dedup: bool,
+    pub dedup_window: Option<Duration>,
+    pub group_by: Option<String>,
+    pub watch: Option<WatchOptions>,
+    pub listen: Option<ListenAddress>,
+    pub context: ContextOptions,
+    pub sorted_input: bool,
+    pub wrapper: Option<Wrapper>,
+    pub max_message_width: Option<usize>,
+    pub max_field_width: Option<usize>,
+    pub error_key: Option<String>,
+    pub expand_json: bool,
+    pub render_template: bool,
+    pub drop_template_fields: bool,
+    pub strip_input_ansi: bool,
+    pub pretty: bool,
+    pub field_quote: FieldQuote,
+    pub debug_parse: bool,
+    pub bookmark: Option<String>,
+    pub rules: Vec<StyleRule>,
+    pub normalize: bool,
+    pub original_keys: bool,
+    pub columns: Vec<String>,
+    pub column_width: Option<usize>,
+    pub only: Option<String>,
+    pub theme_file: Option<PathBuf>,
 }
 
 impl Options {
@@ -88,6 +160,11 @@ impl Options {
         Self { theme, ..self }
     }
 
+    #[cfg(test)]
+    fn with_colors(self, colors: bool) -> Self {
+        Self { colors, ..self }
+    }
+
     #[cfg(test)]
     fn with_fields(self, fields: FieldOptions) -> Self {
         Self { fields, ..self }
@@ -103,6 +180,44 @@ impl Options {
         Self { raw, ..self }
     }
 
+    #[cfg(test)]
+    fn with_output_format(self, output_format: OutputFormat) -> Self {
+        Self { output_format, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_columns(self, columns: Vec<String>, column_width: Option<usize>) -> Self {
+        Self {
+            columns,
+            column_width,
+            ..self
+        }
+    }
+
+    #[cfg(test)]
+    fn with_only(self, only: Option<String>) -> Self {
+        Self { only, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_stats(self, stats: Option<StatsOptions>) -> Self {
+        Self { stats, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_framing(self, framing: Option<LengthHeader>, delimiter: Delimiter) -> Self {
+        Self {
+            framing,
+            delimiter,
+            ..self
+        }
+    }
+
+    #[cfg(test)]
+    fn with_count(self, count: Option<CountOptions>) -> Self {
+        Self { count, ..self }
+    }
+
     #[cfg(test)]
     fn with_sort(self, sort: bool) -> Self {
         Self { sort, ..self }
@@ -113,6 +228,51 @@ impl Options {
         Self { filter, ..self }
     }
 
+    #[cfg(test)]
+    fn with_strict(self, strict: Option<StrictMode>) -> Self {
+        Self { strict, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_dedup(self, dedup: bool) -> Self {
+        Self { dedup, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_dedup_window(self, dedup_window: Option<Duration>) -> Self {
+        Self { dedup_window, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_group_by(self, group_by: Option<String>) -> Self {
+        Self { group_by, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_context(self, context: ContextOptions) -> Self {
+        Self { context, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_tail(self, tail: Option<u64>) -> Self {
+        Self { tail, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_range(self, range: Option<RecordRange>) -> Self {
+        Self { range, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_sorted_input(self, sorted_input: bool) -> Self {
+        Self { sorted_input, ..self }
+    }
+
+    #[cfg(test)]
+    fn with_filename(self, filename: Option<bool>) -> Self {
+        Self { filename, ..self }
+    }
+
     #[cfg(test)]
     fn with_input_info(self, input_info: InputInfoSet) -> Self {
         Self { input_info, ..self }
@@ -176,6 +336,127 @@ pub struct FieldOptions {
 pub enum InputFormat {
     Json,
     Logfmt,
+    Syslog,
+    /// OpenTelemetry log records in OTLP JSON encoding.
+    Otlp,
+    /// Graylog Extended Log Format messages.
+    Gelf,
+    /// Windows Event Log records exported with `Get-WinEvent | ConvertTo-Json`.
+    WinEvent,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default, Serialize, Deserialize)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Logfmt,
+    Html,
+    Json,
+}
+
+/// Defines how lines that cannot be parsed are handled in strict mode.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum StrictMode {
+    /// Report invalid lines and fail after processing.
+    Error,
+    /// Report invalid lines and continue.
+    Warn,
+}
+
+/// Number of non-matching records to show around each matching one.
+#[derive(Eq, PartialEq, Copy, Clone, Debug, Default)]
+pub struct ContextOptions {
+    pub before: usize,
+    pub after: usize,
+}
+
+impl ContextOptions {
+    pub fn is_empty(&self) -> bool {
+        self.before == 0 && self.after == 0
+    }
+}
+
+/// Window of the records passing the filters to show, numbered from 1, with both bounds included.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct RecordRange {
+    pub first: u64,
+    pub last: Option<u64>,
+}
+
+impl RecordRange {
+    /// Returns the range skipping the first `skip` records and then showing at most `limit` records.
+    ///
+    /// A zero `limit` produces an empty range.
+    pub fn new(skip: u64, limit: Option<u64>) -> Self {
+        Self {
+            first: skip.saturating_add(1),
+            last: limit.map(|limit| skip.saturating_add(limit)),
+        }
+    }
+
+    /// Tells whether the range includes no records at all.
+    pub fn is_empty(&self) -> bool {
+        self.last.is_some_and(|last| last < self.first)
+    }
+}
+
+impl std::str::FromStr for RecordRange {
+    type Err = Error;
+
+    /// Parses `START:END`, where either bound can be omitted, e.g. `10000:10100`, `100:` or `:50`.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason| Error::InvalidRange {
+            value: s.to_owned(),
+            reason,
+        };
+        let bound = |value: &str| match value.trim() {
+            "" => Ok(None),
+            value => match value.parse::<u64>() {
+                Ok(0) => Err(error("records are numbered from 1")),
+                Ok(n) => Ok(Some(n)),
+                Err(_) => Err(error("expected START:END with positive numbers")),
+            },
+        };
+
+        let (first, last) = s
+            .split_once(':')
+            .ok_or_else(|| error("expected START:END with positive numbers"))?;
+        let first = bound(first)?.unwrap_or(1);
+        let last = bound(last)?;
+        if last.is_some_and(|last| last < first) {
+            return Err(error("END is less than START"));
+        }
+
+        Ok(Self { first, last })
+    }
+}
+
+/// Directory followed for new files in follow mode.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct WatchOptions {
+    pub dir: PathBuf,
+    /// Wildcard pattern matching the names of the files to follow.
+    pub glob: String,
+}
+
+impl WatchOptions {
+    /// Returns the files in the directory with names matching the pattern, sorted by name.
+    fn scan(&self) -> std::io::Result<Vec<PathBuf>> {
+        let pattern = Pattern::new(self.glob.as_str());
+        let mut result = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let matches = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| pattern.matches(name));
+            if matches && path.is_file() {
+                result.push(path);
+            }
+        }
+        result.sort();
+        Ok(result)
+    }
 }
 
 // ---
@@ -212,47 +493,117 @@ pub struct App {
     options: Options,
     punctuation: Arc<ResolvedPunctuation>,
     formatter: DynRecordWithSourceFormatter,
+    matched: AtomicBool,
+    invalid_lines: AtomicU64,
 }
 
 pub type Output = dyn Write + Send + Sync;
 
 impl App {
     pub fn new(mut options: Options) -> Self {
-        if options.raw && options.input_info.intersects(InputInfo::None | InputInfo::Auto) {
+        let plain = options.raw || options.output_format != OutputFormat::Text;
+        if plain && options.input_info.intersects(InputInfo::None | InputInfo::Auto) {
             options.input_info = InputInfo::None.into()
         }
+        if plain && options.filename.is_none() {
+            options.filename = Some(false)
+        }
         options.input_info = InputInfo::resolve(options.input_info);
 
         let punctuation = Arc::new(options.formatting.punctuation.resolve(options.ascii));
 
-        let formatter = Self::new_formatter(&options, punctuation.clone());
+        let formatter = Self::new_formatter(&options, options.theme.clone(), punctuation.clone());
 
         Self {
             options,
             punctuation,
             formatter,
+            matched: AtomicBool::new(false),
+            invalid_lines: AtomicU64::new(0),
         }
     }
 
+    /// Returns true if records are aggregated instead of being formatted.
+    fn aggregating(&self) -> bool {
+        self.options.stats.is_some() || self.options.count.is_some()
+    }
+
+    /// Returns true if the records are rendered as a table with a header row.
+    fn tabular(&self) -> bool {
+        !self.options.columns.is_empty() && !self.options.raw && self.options.only.is_none() && !self.aggregating()
+    }
+
+    /// Returns true if any message has passed the filters so far.
+    pub fn matched(&self) -> bool {
+        self.matched.load(Ordering::Relaxed)
+    }
+
     pub fn run(&self, inputs: Vec<InputHolder>, output: &mut Output) -> Result<()> {
-        if self.options.follow {
-            self.follow(inputs.into_iter().map(|x| x.reference).collect(), output)
+        let html = self.options.output_format == OutputFormat::Html && !self.aggregating();
+        if html {
+            let mut buf = Vec::new();
+            html::prologue(&mut buf, self.options.html_standalone);
+            output.write_all(&buf)?;
+        }
+
+        if self.tabular() {
+            let mut buf = Vec::new();
+            Self::new_columns_formatter(&self.options).format_header(&mut buf);
+            buf.push(b'\n');
+            output.write_all(&buf)?;
+        }
+
+        if let Some(stats) = &self.options.stats {
+            self.stats(inputs, output, stats)?;
+        } else if let Some(count) = &self.options.count {
+            self.aggregate(inputs, count.group_by())?.write_counts(output, count)?;
+        } else if self.options.follow {
+            self.follow(inputs.into_iter().map(|x| x.reference).collect(), output)?;
         } else if self.options.sort {
-            self.sort(inputs, output)
+            self.sort(inputs, output)?;
+        } else if let Some(key) = &self.options.group_by {
+            self.group(inputs, output, key)?;
+        } else if let Some(range) = self.options.range {
+            self.slice(inputs, output, range)?;
+        } else if let Some(n) = self.options.tail {
+            self.tail(inputs, output, n.try_into().unwrap_or(usize::MAX))?;
         } else {
-            self.cat(inputs, output)
+            self.cat(inputs, output)?;
         }
+
+        if html {
+            let mut buf = Vec::new();
+            html::epilogue(&mut buf, self.options.html_standalone);
+            output.write_all(&buf)?;
+        }
+
+        let count = self.invalid_lines.load(Ordering::Relaxed);
+        if self.options.strict == Some(StrictMode::Error) && count != 0 {
+            return Err(Error::InvalidLines { count });
+        }
+
+        Ok(())
     }
 
     fn cat(&self, inputs: Vec<InputHolder>, output: &mut Output) -> Result<()> {
         let input_badges = self.input_badges(inputs.iter().map(|x| &x.reference));
 
+        let names: Vec<_> = inputs.iter().map(|x| x.reference.description()).collect();
         let inputs = inputs
             .into_iter()
-            .map(|x| x.open())
+            .map(|x| self.open(x))
             .collect::<std::io::Result<Vec<_>>>()?;
 
-        let n = self.options.concurrency;
+        // in chronologically sorted inputs, nothing can match after the first record past the upper time bound
+        let until = self.options.filter.basic.until.filter(|_| self.options.sorted_input);
+        let past_until: Vec<AtomicBool> = inputs.iter().map(|_| AtomicBool::new(false)).collect();
+
+        // deduplication and context tracking need to see all records in order, so a single processing thread is used
+        let n = if self.options.dedup || !self.options.context.is_empty() {
+            1
+        } else {
+            self.options.concurrency
+        };
         let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
         let bfo = BufFactory::new(self.options.buffer_size.into());
         let parser = self.parser();
@@ -262,12 +613,28 @@ impl App {
             // prepare receive/transmit channels for output data
             let (txo, rxo): (Vec<_>, Vec<_>) = (0..n).map(|_| channel::bounded::<(usize, SegmentBuf)>(1)).unzip();
             // spawn reader thread
-            let reader = scope.spawn(closure!(clone sfi, |_| -> Result<()> {
+            let reader = scope.spawn(closure!(clone sfi, ref past_until, |_| -> Result<()> {
                 let mut tx = StripedSender::new(txi);
                 let scanner = Scanner::new(sfi, &self.options.delimiter);
+                // lines are counted only in strict mode to be able to report locations of invalid lines
+                let delim = self.options.strict.map(|_| self.options.delimiter.clone().into_searcher());
                 for (i, mut input) in inputs.into_iter().enumerate() {
+                    let mut line = 0;
                     for item in scanner.items(&mut input.stream.as_sequential()).with_max_segment_size(self.options.max_message_size.into()) {
-                        if tx.send((i, item?)).is_none() {
+                        if past_until[i].load(Ordering::Relaxed) {
+                            break;
+                        }
+                        let item = item?;
+                        let first_line = delim.as_ref().map(|delim| {
+                            let first = line;
+                            line += match &item {
+                                Segment::Complete(segment) => delim.split(segment.data()).count(),
+                                Segment::Incomplete(_, PartialPlacement::Last) => 1,
+                                Segment::Incomplete(_, _) => 0,
+                            };
+                            first
+                        });
+                        if tx.send((i, first_line, item)).is_none() {
                             break;
                         }
                     }
@@ -276,26 +643,47 @@ impl App {
             }));
             // spawn processing threads
             for (rxi, txo) in izip!(rxi, txo) {
-                scope.spawn(closure!(ref bfo, ref parser, ref sfi, ref input_badges, |_| {
+                scope.spawn(closure!(ref bfo, ref parser, ref sfi, ref input_badges, ref names, ref past_until, |_| {
                     let mut processor = self.new_segment_processor(parser);
-                    for (i, segment) in rxi.iter() {
+                    // sends records held back by the processor
+                    let flush = |processor: &mut _, i| {
+                        let mut buf = bfo.new_buf();
+                        SegmentProcess::flush(processor, &mut buf);
+                        if buf.is_empty() {
+                            bfo.recycle(buf);
+                            return true;
+                        }
+                        txo.send((i, buf.into())).is_ok()
+                    };
+                    let mut last = None;
+                    for (i, first_line, segment) in rxi.iter() {
+                        if let Some(prev) = last.replace(i).filter(|&prev| prev != i) {
+                            if !flush(&mut processor, prev) {
+                                return;
+                            }
+                        }
                         let prefix = input_badges.as_ref().map(|b|b[i].as_str()).unwrap_or("");
                         match segment {
                             Segment::Complete(segment) => {
                                 let mut buf = bfo.new_buf();
-                                processor.process(segment.data(), &mut buf, prefix, None, &mut RecordIgnorer{});
+                                let tracker = UntilTracker::new(until, &past_until[i]);
+                                let mut observer = InvalidLineReporter::new(tracker, self, &names[i], first_line);
+                                processor.process(segment.data(), &mut buf, prefix, None, &mut observer);
                                 sfi.recycle(segment);
                                 if txo.send((i, buf.into())).is_err() {
-                                    break;
+                                    return;
                                 };
                             }
                             Segment::Incomplete(segment, _) => {
-                                if txo.send((i, segment)).is_err() {
-                                    break;
+                                if !flush(&mut processor, i) || txo.send((i, segment)).is_err() {
+                                    return;
                                 }
                             }
                         }
                     }
+                    if let Some(last) = last {
+                        flush(&mut processor, last);
+                    }
                 }));
             }
             // spawn writer thread
@@ -316,16 +704,252 @@ impl App {
         Ok(())
     }
 
+    /// Writes the last `n` records passing the filters.
+    ///
+    /// Seekable inputs are read backwards from the end until enough records are found,
+    /// other inputs are read entirely keeping only the last records.
+    fn tail(&self, inputs: Vec<InputHolder>, output: &mut Output, n: usize) -> Result<()> {
+        let input_badges = self.input_badges(inputs.iter().map(|x| &x.reference));
+        let inputs = inputs
+            .into_iter()
+            .map(|x| self.open(x))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let parser = self.parser();
+        let mut processor = self.new_segment_processor(&parser);
+        let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
+
+        // formatted records, starting from the last one
+        let mut records = VecDeque::with_capacity(n.min(MAX_TAIL_PREALLOCATION));
+        for (i, mut input) in inputs.into_iter().enumerate().rev() {
+            if records.len() >= n {
+                break;
+            }
+            let name = input.reference.description();
+            let prefix = input_badges.as_ref().map(|b| b[i].as_str()).unwrap_or("");
+            let limit = n - records.len();
+            match &mut input.stream {
+                Stream::RandomAccess(stream) => {
+                    let scanner = ReverseScanner::new(&self.options.delimiter, self.options.buffer_size.into());
+                    for segment in scanner.items(stream)? {
+                        let found = self.tail_records(&mut processor, &segment?, prefix, &name);
+                        records.extend(found.into_iter().rev().take(n - records.len()));
+                        if records.len() >= n {
+                            break;
+                        }
+                    }
+                }
+                stream @ Stream::Sequential(_) => {
+                    let mut last = VecDeque::with_capacity(limit.min(MAX_TAIL_PREALLOCATION));
+                    let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+                    let items = scanner
+                        .items(&mut stream.as_sequential())
+                        .with_max_segment_size(self.options.max_message_size.into());
+                    for item in items {
+                        if let Segment::Complete(segment) = item? {
+                            for record in self.tail_records(&mut processor, segment.data(), prefix, &name) {
+                                if last.len() == limit {
+                                    last.pop_front();
+                                }
+                                last.push_back(record);
+                            }
+                            sfi.recycle(segment);
+                        }
+                    }
+                    records.extend(last.into_iter().rev());
+                }
+            }
+        }
+
+        for record in records.iter().rev() {
+            output.write_all(record)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the records passing the filters within the range.
+    ///
+    /// Inputs are read sequentially, and reading stops as soon as the last record of the range is written.
+    fn slice(&self, inputs: Vec<InputHolder>, output: &mut Output, range: RecordRange) -> Result<()> {
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        let input_badges = self.input_badges(inputs.iter().map(|x| &x.reference));
+        let inputs = inputs
+            .into_iter()
+            .map(|x| self.open(x))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let parser = self.parser();
+        let mut processor = self.new_segment_processor(&parser);
+        let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
+        let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+
+        // number of records passing the filters seen so far
+        let mut n = 0;
+        for (i, mut input) in inputs.into_iter().enumerate() {
+            let name = input.reference.description();
+            let prefix = input_badges.as_ref().map(|b| b[i].as_str()).unwrap_or("");
+            let items = scanner
+                .items(&mut input.stream.as_sequential())
+                .with_max_segment_size(self.options.max_message_size.into());
+            for item in items {
+                if let Segment::Complete(segment) = item? {
+                    for record in self.tail_records(&mut processor, segment.data(), prefix, &name) {
+                        n += 1;
+                        if n >= range.first {
+                            output.write_all(&record)?;
+                        }
+                        if range.last.is_some_and(|last| n >= last) {
+                            return Ok(());
+                        }
+                    }
+                    sfi.recycle(segment);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the formatted records of the segment passing the filters, each one terminated by a line break.
+    ///
+    /// Invalid lines are reported as coming from the input with the given name.
+    fn tail_records<P: SegmentProcess>(
+        &self,
+        processor: &mut P,
+        data: &[u8],
+        prefix: &str,
+        input: &str,
+    ) -> Vec<Vec<u8>> {
+        let mut buf = Vec::new();
+        let mut locations = Vec::new();
+        let observer = |_: &Record, location: Range<usize>| locations.push(location);
+        let mut observer = InvalidLineReporter::new(observer, self, input, None);
+        processor.process(data, &mut buf, prefix, None, &mut observer);
+        locations
+            .into_iter()
+            .map(|location| {
+                let mut record = buf[location].to_vec();
+                record.push(b'\n');
+                record
+            })
+            .collect()
+    }
+
+    /// Writes the records passing the filters in sections grouped by the value of the field with the given key.
+    fn group(&self, inputs: Vec<InputHolder>, output: &mut Output, key: &str) -> Result<()> {
+        let input_badges = self.input_badges(inputs.iter().map(|x| &x.reference));
+        let inputs = inputs
+            .into_iter()
+            .map(|x| self.open(x))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
+        let parser = self.parser();
+        let mut processor = self.new_segment_processor(&parser);
+        let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+        let mut groups = Groups::new();
+        let mut buf = Vec::new();
+
+        for (i, mut input) in inputs.into_iter().enumerate() {
+            let name = input.reference.description();
+            let prefix = input_badges.as_ref().map(|b| b[i].as_str()).unwrap_or("");
+            for item in scanner
+                .items(&mut input.stream.as_sequential())
+                .with_max_segment_size(self.options.max_message_size.into())
+            {
+                if let Segment::Complete(segment) = item? {
+                    let mut found = Vec::new();
+                    let observer = |record: &Record, location: Range<usize>| {
+                        let ts = record.ts.as_ref().and_then(|ts| ts.unix_utc());
+                        found.push((grouping::key(record, key), ts, location));
+                    };
+                    let mut observer = InvalidLineReporter::new(observer, self, &name, None);
+                    processor.process(segment.data(), &mut buf, prefix, None, &mut observer);
+                    for (key, ts, location) in found {
+                        let mut record = buf[location].to_vec();
+                        record.push(b'\n');
+                        groups.add(key, ts, record);
+                    }
+                    buf.clear();
+                    sfi.recycle(segment);
+                }
+            }
+        }
+
+        groups.write(output, &self.options.theme, key)?;
+
+        Ok(())
+    }
+
+    fn stats(&self, inputs: Vec<InputHolder>, output: &mut Output, options: &StatsOptions) -> Result<()> {
+        let stats = self.aggregate(inputs, &options.group_by)?;
+        let tsf = DateTimeFormatter::new(self.options.time_format.clone(), self.options.time_zone);
+        stats.write(output, options, &tsf)?;
+
+        Ok(())
+    }
+
+    /// Collects statistics of the records passing the filters without formatting them.
+    fn aggregate(&self, inputs: Vec<InputHolder>, group_by: &str) -> Result<Stats> {
+        let inputs = inputs
+            .into_iter()
+            .map(|x| self.open(x))
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
+        let parser = self.parser();
+        let mut processor = self.new_segment_processor(&parser);
+        let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+        let delim = self
+            .options
+            .strict
+            .map(|_| self.options.delimiter.clone().into_searcher());
+        let mut stats = Stats::new();
+        let mut buf = Vec::new();
+
+        for mut input in inputs {
+            let name = input.reference.description();
+            let mut line = 0;
+            for item in scanner
+                .items(&mut input.stream.as_sequential())
+                .with_max_segment_size(self.options.max_message_size.into())
+            {
+                match item? {
+                    Segment::Complete(segment) => {
+                        let observer = |record: &Record, _: Range<usize>| stats.add(record, group_by);
+                        let mut observer = InvalidLineReporter::new(observer, self, &name, Some(line));
+                        processor.process(segment.data(), &mut buf, "", None, &mut observer);
+                        if let Some(delim) = &delim {
+                            line += delim.split(segment.data()).count();
+                        }
+                        buf.clear();
+                        sfi.recycle(segment);
+                    }
+                    Segment::Incomplete(_, PartialPlacement::Last) => line += 1,
+                    Segment::Incomplete(_, _) => {}
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
     fn sort(&self, inputs: Vec<InputHolder>, output: &mut Output) -> Result<()> {
         let mut output = BufWriter::new(output);
         let indexer_settings = IndexerSettings {
             buffer_size: self.options.buffer_size.try_into()?,
             max_message_size: self.options.max_message_size.try_into()?,
             fields: &self.options.fields.settings.predefined,
+            aliases: &self.options.fields.settings.aliases,
             delimiter: self.options.delimiter.clone(),
             allow_prefix: self.options.allow_prefix,
             unix_ts_unit: self.options.unix_ts_unit,
             format: self.options.input_format,
+            mode: self.options.index_mode,
             ..IndexerSettings::with_fs(LocalFileSystem)
         };
         let param_hash = hex::encode(indexer_settings.hash()?);
@@ -336,15 +960,23 @@ impl App {
             .map(|dirs| dirs.cache_dir.clone())
             .unwrap_or_else(|| PathBuf::from(".cache"))
             .join(param_hash);
-        fs::create_dir_all(&cache_dir)?;
+        if self.options.index_mode != IndexMode::Disabled {
+            fs::create_dir_all(&cache_dir)?;
+        }
 
-        let indexer = Indexer::new(self.options.concurrency, cache_dir, indexer_settings);
+        let progress = self.options.progress.then(|| Arc::new(Progress::new()));
+        let indexer =
+            Indexer::new(self.options.concurrency, cache_dir, indexer_settings).with_progress(progress.clone());
         let input_badges = self.input_badges(inputs.iter().map(|x| &x.reference));
+        let names: Vec<_> = inputs.iter().map(|x| x.reference.description()).collect();
 
         let inputs = inputs
             .into_iter()
-            .map(|x| x.index(&indexer))
+            .map(|x| self.open(x)?.indexed(&indexer))
             .collect::<Result<Vec<_>>>()?;
+        if let Some(progress) = progress {
+            progress.finish();
+        }
 
         if self.options.dump_index {
             for input in inputs {
@@ -398,7 +1030,7 @@ impl App {
                                 }
                             }
                             if let Some(level) = self.options.filter.basic.level {
-                                if !src.match_level(level) {
+                                if !self.options.filter.basic.unknown_level && !src.match_level(level) {
                                     return None;
                                 }
                             }
@@ -423,32 +1055,34 @@ impl App {
             // spawn worker threads
             let mut workers = Vec::with_capacity(n);
             for (rxp, txw) in izip!(rxp, txw) {
-                workers.push(scope.spawn(closure!(ref parser, |_| -> Result<()> {
+                workers.push(scope.spawn(closure!(ref parser, ref names, |_| -> Result<()> {
                     let mut processor = self.new_segment_processor(parser);
                     for (block, ts_min, i, j) in rxp.iter() {
                         let mut buf = Vec::with_capacity(2 * usize::try_from(block.size())?);
                         let mut items = Vec::with_capacity(2 * usize::try_from(block.lines_valid())?);
+                        // messages without a valid timestamp follow the last seen timestamp of the block
+                        let mut last_ts = ts_min;
                         for line in block.into_lines()? {
                             if line.is_empty() {
                                 continue;
                             }
+                            let observer = |record: &Record, location: Range<usize>| {
+                                match record.ts.as_ref().map(|ts| (ts, ts.unix_utc())) {
+                                    Some((_, Some(unix_ts))) => last_ts = unix_ts.into(),
+                                    Some((ts, None)) => log::warn!(
+                                        "could not parse timestamp of a message, using the last seen timestamp: {:#?}",
+                                        ts.raw()
+                                    ),
+                                    None => {}
+                                }
+                                items.push((last_ts, location));
+                            };
                             processor.process(
                                 line.bytes(),
                                 &mut buf,
                                 "",
                                 Some(1),
-                                &mut |record: &Record, location: Range<usize>| {
-                                    if let Some(ts) = &record.ts {
-                                        if let Some(unix_ts) = ts.unix_utc() {
-                                            items.push((unix_ts.into(), location));
-                                        } else {
-                                            log::warn!(
-                                                "skipped a message because its timestamp could not be parsed: {:#?}",
-                                                ts.raw()
-                                            )
-                                        }
-                                    }
-                                },
+                                &mut InvalidLineReporter::new(observer, self, &names[i], None),
                             );
                         }
 
@@ -527,79 +1161,134 @@ impl App {
         Ok(())
     }
 
-    fn follow(&self, inputs: Vec<InputReference>, output: &mut Output) -> Result<()> {
-        let input_badges = self.input_badges(inputs.iter());
+    fn follow(&self, mut inputs: Vec<InputReference>, output: &mut Output) -> Result<()> {
+        // records up to the bookmarked one were already shown for the same sources and are skipped,
+        // including the given number of records sharing its time
+        let bookmark = match &self.options.bookmark {
+            Some(name) => Some(Bookmark::new(&self.bookmark_dir(), name)?),
+            None => None,
+        };
+        let sources = self.follow_sources(&inputs);
+        let since = bookmark
+            .as_ref()
+            .and_then(|b| b.load(&sources))
+            .map(|position| (Timestamp::from(position.ts), position.count));
+
+        // files already present in the watched directory are followed like the files given explicitly
+        let mut known = HashSet::new();
+        if let Some(watch) = &self.options.watch {
+            for path in watch.scan()? {
+                known.insert(path.clone());
+                inputs.push(InputReference::File(path.try_into()?));
+            }
+        }
+
+        // badges cannot be aligned for inputs discovered later, so they are not shown in watch mode,
+        // and records received from the network are prefixed with the address of the sender instead
+        let input_badges = RwLock::new(match (&self.options.watch, &self.options.listen) {
+            (_, Some(_)) => Some(Vec::new()),
+            (Some(_), None) => None,
+            (None, None) => self.input_badges(inputs.iter()),
+        });
+        let names = RwLock::new(inputs.iter().map(|x| x.description()).collect::<Vec<_>>());
+        // registers a source discovered while following and returns its index
+        let add_source = |name: String, badge: Option<String>| {
+            let mut names = names.write().unwrap();
+            names.push(name);
+            if let (Some(badges), Some(badge)) = (input_badges.write().unwrap().as_mut(), badge) {
+                badges.push(badge);
+            }
+            names.len() - 1
+        };
+
+        let theme = LiveTheme::new(self.options.theme.clone());
+        if let Some(path) = &self.options.theme_file {
+            theme.watch(path.clone());
+        }
 
         let m = inputs.len();
         let n = self.options.concurrency;
         let parser = self.parser();
         let sfi = Arc::new(SegmentBufFactory::new(self.options.buffer_size.into()));
         let bfo = BufFactory::new(self.options.buffer_size.into());
+        // resuming from a bookmark needs the whole inputs unless the number of preloaded records is set explicitly
+        let preload = match (since, self.options.tail) {
+            (Some(_), None) => None,
+            (_, tail) => Some(tail.unwrap_or(DEFAULT_FOLLOW_TAIL)),
+        };
         thread::scope(|scope| -> Result<()> {
             // prepare receive/transmit channels for input data
             let (txi, rxi) = channel::bounded(1);
             // prepare receive/transmit channels for output data
             let (txo, rxo) = channel::bounded(1);
             // spawn reader threads
-            let mut readers = Vec::with_capacity(m);
+            let mut readers = Vec::with_capacity(m + 1);
             for (i, input_ref) in inputs.into_iter().enumerate() {
                 let reader = scope.spawn(closure!(clone sfi, clone txi, |_| -> Result<()> {
-                    let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
-                    let mut meta = None;
-                    if let InputReference::File(path) = &input_ref {
-                        meta = Some(fs::metadata(&path.canonical)?);
-                    }
-                    let mut input = Some(input_ref.open()?.tail(self.options.tail)?);
-                    let is_file = |meta: &Option<fs::Metadata>| meta.as_ref().map(|m|m.is_file()).unwrap_or(false);
-                    let process = |input: &mut Option<Input>, is_file: bool| {
-                        if let Some(input) = input {
-                            for (j, item) in scanner.items(&mut input.stream.as_sequential()).with_max_segment_size(self.options.max_message_size.into()).enumerate() {
-                                if txi.send((i, j, item?)).is_err() {
-                                    break;
-                                }
+                    self.follow_input(i, input_ref, preload, &sfi, &txi)
+                }));
+                readers.push(reader);
+            }
+            // spawn directory watcher thread starting a reader for each new matching file
+            if let Some(watch) = &self.options.watch {
+                let watcher = scope.spawn(closure!(clone sfi, clone txi, ref add_source, |scope| -> Result<()> {
+                    fsmon::run_dir(watch.dir.clone(), |_| {
+                        for path in watch.scan()? {
+                            if !known.insert(path.clone()) {
+                                continue;
                             }
-                            Ok(!is_file)
-                        } else {
-                            Ok(false)
-                        }
-                    };
-                    if let InputReference::File(path) = &input_ref {
-                        if process(&mut input, is_file(&meta))? {
-                            return Ok(())
+                            log::debug!("following new file {}", path.display());
+                            let input_ref = InputReference::File(path.try_into()?);
+                            let i = add_source(input_ref.description(), None);
+                            // new files are read from the beginning, and a file that cannot be followed does not stop the others
+                            scope.spawn(closure!(clone sfi, clone txi, |_| {
+                                let name = input_ref.description();
+                                if let Err(err) = self.follow_input(i, input_ref, None, &sfi, &txi) {
+                                    log::warn!("stopped following {name}: {err}");
+                                }
+                            }));
                         }
-                        fsmon::run(vec![path.canonical.clone()], |event| {
-                            match event.kind {
-                                EventKind::Modify(_) | EventKind::Create(_) | EventKind::Any | EventKind::Other => {
-                                    if let (Some(old_meta), Ok(new_meta)) = (&meta, fs::metadata(&path.canonical)) {
-                                        if old_meta.len() > new_meta.len() {
-                                            input = None;
-                                        }
-                                        #[cfg(unix)]
-                                        if old_meta.ino() != new_meta.ino() || old_meta.dev() != new_meta.dev() {
-                                            input = None;
-                                        }
-                                        meta = Some(new_meta);
-                                    }
-                                    if input.is_none() {
-                                        input = input_ref.open().ok();
-                                    }
-                                    if process(&mut input, is_file(&meta))? {
-                                        return Ok(())
-                                    }
-                                    Ok(())
+                        Ok(())
+                    })
+                }));
+                readers.push(watcher);
+            }
+            // spawn network listener thread starting a reader for each new connection
+            match self.options.listen {
+                Some(ListenAddress::Tcp(addr)) => {
+                    let listener = scope.spawn(closure!(clone sfi, clone txi, ref add_source, |scope| -> Result<()> {
+                        listen::accept(addr, |stream, peer| {
+                            log::debug!("accepted connection from {peer}");
+                            let i = add_source(format!("tcp://{peer}"), Some(self.source_badge(&peer.to_string())));
+                            // a connection that fails does not stop the others
+                            scope.spawn(closure!(clone sfi, clone txi, |_| {
+                                if let Err(err) = self.follow_stream(i, stream, &sfi, &txi) {
+                                    log::warn!("stopped reading from {peer}: {err}");
                                 }
-                                EventKind::Remove(_) => {
-                                    input = None;
-                                    Ok(())
-                                },
-                                EventKind::Access(_) => Ok(()),
+                            }));
+                            Ok(())
+                        })
+                    }));
+                    readers.push(listener);
+                }
+                Some(ListenAddress::Udp(addr)) => {
+                    let listener = scope.spawn(closure!(clone txi, ref add_source, |_| -> Result<()> {
+                        let mut peers = HashMap::new();
+                        let mut j = 0;
+                        listen::receive(addr, |datagram, peer| {
+                            let i = *peers
+                                .entry(peer)
+                                .or_insert_with(|| add_source(format!("udp://{peer}"), Some(self.source_badge(&peer.to_string()))));
+                            // each datagram holds a single record
+                            if txi.send((i, j, Segment::Complete(SegmentBuf::from(datagram)))).is_ok() {
+                                j += 1;
                             }
+                            Ok(())
                         })
-                    } else {
-                        process(&mut input, is_file(&meta)).map(|_|())
-                    }
-                }));
-                readers.push(reader);
+                    }));
+                    readers.push(listener);
+                }
+                None => {}
             }
             drop(txi);
 
@@ -607,17 +1296,27 @@ impl App {
             // spawn processing threads
             let mut workers = Vec::with_capacity(n);
             for _ in 0..n {
-                let worker = scope.spawn(closure!(ref bfo, ref parser, ref sfi, ref input_badges, clone rxi, clone txo, |_| {
+                let worker = scope.spawn(closure!(ref bfo, ref parser, ref sfi, ref input_badges, ref names, ref theme, clone rxi, clone txo, |_| {
+                    let (mut generation, _) = theme.get();
                     let mut processor = self.new_segment_processor(parser);
                     for (i, j, segment) in rxi.iter() {
-                        let prefix = input_badges.as_ref().map(|b|b[i].as_str()).unwrap_or("");
+                        // switch to the reloaded theme for the records that follow
+                        let (g, current) = theme.get();
+                        if g != generation {
+                            generation = g;
+                            let formatter = Self::new_formatter(&self.options, current, self.punctuation.clone());
+                            processor = self.new_segment_processor_with(parser, formatter);
+                        }
+                        let prefix = input_badges.read().unwrap().as_ref().and_then(|b| b.get(i)).cloned().unwrap_or_default();
                         match segment {
                             Segment::Complete(segment) => {
                                 let mut buf = bfo.new_buf();
-                                let mut index_builder = TimestampIndexBuilder{result: TimestampIndex::new(j)};
-                                processor.process(segment.data(), &mut buf, prefix, None, &mut index_builder);
+                                let index_builder = TimestampIndexBuilder{result: TimestampIndex::new(j)};
+                                let name = names.read().unwrap().get(i).cloned().unwrap_or_default();
+                                let mut observer = InvalidLineReporter::new(index_builder, self, &name, None);
+                                processor.process(segment.data(), &mut buf, &prefix, None, &mut observer);
                                 sfi.recycle(segment);
-                                if txo.send((i, buf, index_builder.result)).is_err() {
+                                if txo.send((i, buf, observer.inner.result)).is_err() {
                                     return;
                                 };
                             }
@@ -630,14 +1329,25 @@ impl App {
             drop(txo);
 
             // spawn merger thread
-            let merger = scope.spawn(move |_| -> Result<()> {
+            let merger = scope.spawn(closure!(ref theme, |_| -> Result<()> {
                 type Key = (Timestamp, usize, usize, usize); // (ts, input, block, offset)
                 type Line = (Rc<Vec<u8>>, Range<usize>, Instant); // (buf, location, instant)
 
                 let mut window = BTreeMap::<Key,Line>::new();
                 let mut last_ts: Option<Timestamp> = None;
                 let mut prev_ts: Option<Timestamp> = None;
+                let mut skip = since.map_or(0, |(_, count)| count);
+                let mut shown = since; // (ts, number of records shown with it)
+                let mut unsaved = false;
+                let mut saved = Instant::now();
                 let mut mem_usage = 0;
+                let save = |(ts, count): (Timestamp, usize)| {
+                    if let (Some(bookmark), Some(ts)) = (&bookmark, DateTime::from_timestamp(ts.sec, ts.nsec)) {
+                        if let Err(err) = bookmark.save(&sources, Position { ts, count }) {
+                            log::warn!("failed to save bookmark: {err}");
+                        }
+                    }
+                };
                 let mem_limit = n * usize::from(self.options.buffer_size);
 
                 loop {
@@ -647,19 +1357,39 @@ impl App {
                             break;
                         }
                         if let Some(entry) = window.pop_first() {
+                            mem_usage -= entry.1.1.end - entry.1.1.start;
+                            if skip != 0 && since.is_some_and(|(ts, _)| ts == entry.0.0) {
+                                skip -= 1;
+                                continue;
+                            }
+                            let (_, theme) = theme.get();
                             let sync_indicator = if prev_ts.map(|ts| ts <= entry.0.0).unwrap_or(true) {
-                                &self.options.theme.indicators.sync.synced
+                                &theme.indicators.sync.synced
                             } else {
-                                &self.options.theme.indicators.sync.failed
+                                &theme.indicators.sync.failed
                             };
                             prev_ts = Some(entry.0.0);
-                            mem_usage -= entry.1.1.end - entry.1.1.start;
+                            shown = match shown {
+                                Some((ts, count)) if ts == entry.0.0 => Some((ts, count + 1)),
+                                Some((ts, _)) if ts > entry.0.0 => shown,
+                                _ => Some((entry.0.0, 1)),
+                            };
+                            unsaved = true;
                             output.write_all(sync_indicator.value.as_bytes())?;
                             output.write_all(&entry.1.0[entry.1.1.clone()])?;
                             output.write_all(b"\n")?;
                         }
                     }
 
+                    // save the bookmark once the pending records are shown, but not more often than once a second
+                    if let (true, Some(position)) = (unsaved, shown) {
+                        if window.is_empty() || saved.elapsed() >= BOOKMARK_SAVE_INTERVAL {
+                            save(position);
+                            unsaved = false;
+                            saved = Instant::now();
+                        }
+                    }
+
                     let next_ts = window.first_entry().map(|e|e.get().2);
                     let timeout = if let (Some(next_ts), Some(deadline)) = (next_ts, deadline) {
                         Some(max(deadline, next_ts) - next_ts)
@@ -670,6 +1400,9 @@ impl App {
                         Ok((i, buf, index)) => {
                             let buf = Rc::new(buf);
                             for line in index.lines {
+                                if since.is_some_and(|(since, _)| line.ts < since) {
+                                    continue;
+                                }
                                 last_ts = Some(last_ts.map(|last_ts| std::cmp::max(last_ts, line.ts)).unwrap_or(line.ts));
                                 mem_usage += line.location.end - line.location.start;
                                 let key = (line.ts, i, index.block, line.location.start);
@@ -686,8 +1419,12 @@ impl App {
                     }
                 }
 
+                if let (true, Some(position)) = (unsaved, shown) {
+                    save(position);
+                }
+
                 Ok(())
-            });
+            }));
 
             for reader in readers {
                 reader.join().unwrap()?;
@@ -706,21 +1443,226 @@ impl App {
         Ok(())
     }
 
+    /// Returns the directory where bookmarks are stored.
+    fn bookmark_dir(&self) -> PathBuf {
+        self.options
+            .app_dirs
+            .as_ref()
+            .map(|dirs| dirs.cache_dir.clone())
+            .unwrap_or_else(|| PathBuf::from(".cache"))
+            .join("bookmarks")
+    }
+
+    /// Describes the sources being followed, so that a bookmark saved for other sources is not applied.
+    fn follow_sources(&self, inputs: &[InputReference]) -> Vec<String> {
+        let mut result = inputs
+            .iter()
+            .map(|input| match input {
+                InputReference::Stdin => "<stdin>".to_owned(),
+                InputReference::File(path) => path.canonical.to_string_lossy().into_owned(),
+            })
+            .collect::<Vec<_>>();
+        if let Some(watch) = &self.options.watch {
+            result.push(format!("watch:{}", watch.dir.display()));
+        }
+        if let Some(listen) = &self.options.listen {
+            result.push(format!("listen:{listen}"));
+        }
+        result
+    }
+
+    /// Reads records from a connection and sends its segments to the processing threads until it is closed.
+    fn follow_stream(
+        &self,
+        i: usize,
+        stream: impl Read + Send,
+        sfi: &Arc<SegmentBufFactory>,
+        txi: &Sender<(usize, usize, Segment)>,
+    ) -> Result<()> {
+        let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+        let mut stream: Box<dyn Read + Send> = match self.options.framing {
+            Some(header) => Box::new(LengthPrefixed::new(
+                stream,
+                header,
+                self.options.delimiter.last_byte().unwrap_or(0),
+            )),
+            None => Box::new(stream),
+        };
+        for (j, item) in scanner
+            .items(stream.as_mut())
+            .with_max_segment_size(self.options.max_message_size.into())
+            .enumerate()
+        {
+            if txi.send((i, j, item?)).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the input and sends its segments to the processing threads, following the changes of a file.
+    ///
+    /// The last `preload` records of the input are read first, or the whole input if it is `None`.
+    fn follow_input(
+        &self,
+        i: usize,
+        input_ref: InputReference,
+        preload: Option<u64>,
+        sfi: &Arc<SegmentBufFactory>,
+        txi: &Sender<(usize, usize, Segment)>,
+    ) -> Result<()> {
+        let scanner = Scanner::new(sfi.clone(), &self.options.delimiter);
+        let mut meta = None;
+        if let InputReference::File(path) = &input_ref {
+            meta = Some(fs::metadata(&path.canonical)?);
+        }
+        let is_file = |meta: &Option<fs::Metadata>| meta.as_ref().map(|m| m.is_file()).unwrap_or(false);
+        // hold back incomplete trailing lines of regular files until they are completed
+        let complete_lines = |input: Input, is_file: bool| -> Box<dyn Read + Send> {
+            let stream = input.stream.into_sequential();
+            match (is_file, self.options.delimiter.last_byte()) {
+                (true, Some(terminator)) => Box::new(CompleteLines::new(stream, terminator)),
+                _ => Box::new(stream),
+            }
+        };
+        let opened = self.framed(input_ref.open_with(self.options.decompression)?);
+        let opened = match preload {
+            Some(n) => opened.tail(n)?,
+            None => opened,
+        };
+        let mut input = Some(complete_lines(opened, is_file(&meta)));
+        let process = |input: &mut Option<Box<dyn Read + Send>>, is_file: bool| {
+            if let Some(input) = input {
+                for (j, item) in scanner
+                    .items(input.as_mut())
+                    .with_max_segment_size(self.options.max_message_size.into())
+                    .enumerate()
+                {
+                    if txi.send((i, j, item?)).is_err() {
+                        break;
+                    }
+                }
+                Ok(!is_file)
+            } else {
+                Ok(false)
+            }
+        };
+        if let InputReference::File(path) = &input_ref {
+            if process(&mut input, is_file(&meta))? {
+                return Ok(());
+            }
+            fsmon::run(vec![path.canonical.clone()], |event| match event.kind {
+                EventKind::Modify(_) | EventKind::Create(_) | EventKind::Any | EventKind::Other => {
+                    if let (Some(old_meta), Ok(new_meta)) = (&meta, fs::metadata(&path.canonical)) {
+                        if old_meta.len() > new_meta.len() {
+                            input = None;
+                        }
+                        #[cfg(unix)]
+                        if old_meta.ino() != new_meta.ino() || old_meta.dev() != new_meta.dev() {
+                            input = None;
+                        }
+                        meta = Some(new_meta);
+                    }
+                    if input.is_none() {
+                        input = input_ref
+                            .open_with(self.options.decompression)
+                            .ok()
+                            .map(|x| complete_lines(self.framed(x), is_file(&meta)));
+                    }
+                    if process(&mut input, is_file(&meta))? {
+                        return Ok(());
+                    }
+                    Ok(())
+                }
+                EventKind::Remove(_) => {
+                    input = None;
+                    Ok(())
+                }
+                EventKind::Access(_) => Ok(()),
+            })
+        } else {
+            process(&mut input, is_file(&meta)).map(|_| ())
+        }
+    }
+
+    /// Opens the input and decodes its frames if framing is configured.
+    fn open(&self, input: InputHolder) -> std::io::Result<Input> {
+        Ok(self.framed(input.open_with(self.options.decompression)?))
+    }
+
+    /// Decodes the frames of the opened input if framing is configured.
+    fn framed(&self, input: Input) -> Input {
+        match self.options.framing {
+            Some(header) => input.framed(header, self.options.delimiter.last_byte().unwrap_or(0)),
+            None => input,
+        }
+    }
+
     fn parser(&self) -> Parser {
-        Parser::new(ParserSettings::new(
-            &self.options.fields.settings.predefined,
-            &self.options.fields.settings.ignore,
-            self.options.unix_ts_unit,
-        ))
+        let predefined = &self.options.fields.settings.predefined;
+        let format_predefined;
+        let predefined = match self.options.input_format {
+            Some(InputFormat::Otlp) => {
+                format_predefined = otlp::predefined_fields(predefined);
+                &format_predefined
+            }
+            Some(InputFormat::Gelf) => {
+                format_predefined = gelf::predefined_fields(predefined);
+                &format_predefined
+            }
+            Some(InputFormat::WinEvent) => {
+                format_predefined = winevent::predefined_fields(predefined);
+                &format_predefined
+            }
+            _ => predefined,
+        };
+
+        Parser::new(
+            ParserSettings::new(
+                predefined,
+                &self.options.fields.settings.ignore,
+                self.options.unix_ts_unit,
+            )
+            .with_aliases(&self.options.fields.settings.aliases),
+        )
+    }
+
+    /// Reports a line that could not be parsed if strict mode is enabled.
+    fn report_invalid_line(&self, input: &str, line: Option<usize>, data: &[u8], error: Option<&Error>) {
+        if self.options.strict.is_none() {
+            return;
+        }
+
+        self.invalid_lines.fetch_add(1, Ordering::Relaxed);
+
+        let location = match line {
+            Some(line) => format!("line {} of {}", line, input),
+            None => input.to_owned(),
+        };
+        let reason = match error {
+            Some(error) => error.to_string(),
+            None => "unrecognized message format".into(),
+        };
+        let data = String::from_utf8_lossy(&data[..data.len().min(MAX_INVALID_LINE_EXCERPT)]);
+        eprintln!("invalid message at {}: {}: {}", location, reason, data);
     }
 
     fn input_badges<'a, I: IntoIterator<Item = &'a InputReference>>(&self, inputs: I) -> Option<Vec<String>> {
+        let inputs = inputs.into_iter().collect_vec();
+
+        match (self.input_info_badges(&inputs), self.filename_badges(&inputs)) {
+            (Some(info), Some(names)) => Some(izip!(info, names).map(|(info, name)| info + &name).collect()),
+            (info, names) => info.or(names),
+        }
+    }
+
+    fn input_info_badges(&self, inputs: &[&InputReference]) -> Option<Vec<String>> {
         let name = |input: &InputReference| match input {
             InputReference::Stdin => "<stdin>".to_owned(),
             InputReference::File(path) => path.original.to_string_lossy().to_string(),
         };
 
-        let mut badges = inputs.into_iter().map(|x| name(x).chars().collect_vec()).collect_vec();
+        let mut badges = inputs.iter().map(|x| name(x).chars().collect_vec()).collect_vec();
 
         let ii = self.options.input_info;
 
@@ -768,9 +1710,10 @@ impl App {
             }
         }
 
-        if let Some(max_len) = badges.iter().map(|badge| badge.len()).max() {
+        let width = |badge: &Vec<char>| badge.iter().map(|c| c.width().unwrap_or(0)).sum::<usize>();
+        if let Some(max_width) = badges.iter().map(width).max() {
             for badge in badges.iter_mut() {
-                badge.extend(std::iter::repeat_n(' ', max_len - badge.len()));
+                badge.extend(std::iter::repeat_n(' ', max_width - width(badge)));
             }
         }
 
@@ -784,7 +1727,7 @@ impl App {
                         s.batch(|buf| buf.extend(opt.input_number_left_separator.as_bytes()));
                         s.element(Element::InputNumberInner, |s| {
                             s.batch(|buf| {
-                                aligned_left(buf, num_width + 1, b' ', |mut buf| {
+                                aligned_left_by_width(buf, num_width + 1, b' ', |buf| {
                                     buf.extend_from_slice(opt.input_number_prefix.as_bytes());
                                     buf.extend_from_slice(format!("{}", i).as_bytes());
                                 });
@@ -814,44 +1757,175 @@ impl App {
         Some(result)
     }
 
+    /// Builds a badge for a source discovered while following, colored by the palette color selected by the name.
+    fn source_badge(&self, name: &str) -> String {
+        let mut buf = Vec::with_capacity(name.len() * 2);
+        self.options.theme.apply(&mut buf, &None, |s| {
+            s.tinted(name.as_bytes(), |buf| buf.extend(name.as_bytes()));
+            s.batch(|buf| buf.push(b' '));
+        });
+        String::from_utf8(buf).unwrap()
+    }
+
+    /// Builds short file name badges, each colored by the palette color selected by the name.
+    fn filename_badges(&self, inputs: &[&InputReference]) -> Option<Vec<String>> {
+        // by default, names are shown for multiple inputs unless input info layouts already include them
+        let ii = self.options.input_info;
+        let auto = inputs.len() > 1 && !ii.intersects(InputInfo::Compact | InputInfo::Full);
+        if !self.options.filename.unwrap_or(auto) {
+            return None;
+        }
+
+        let name = |input: &InputReference| match input {
+            InputReference::Stdin => "<stdin>".to_owned(),
+            InputReference::File(path) => match path.original.file_name() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => path.original.to_string_lossy().to_string(),
+            },
+        };
+
+        let names = inputs.iter().map(|x| name(x)).collect_vec();
+        let width = names.iter().map(|name| name.width()).max().unwrap_or(0);
+
+        let badges = names
+            .iter()
+            .map(|name| {
+                let mut buf = Vec::with_capacity(name.len() * 2);
+                self.options.theme.apply(&mut buf, &None, |s| {
+                    s.tinted(name.as_bytes(), |buf| buf.extend(name.as_bytes()));
+                    s.batch(|buf| buf.extend(std::iter::repeat_n(b' ', width - name.width() + 1)));
+                });
+                String::from_utf8(buf).unwrap()
+            })
+            .collect();
+
+        Some(badges)
+    }
+
     fn new_segment_processor<'a>(&'a self, parser: &'a Parser) -> impl SegmentProcess + 'a {
+        self.new_segment_processor_with(parser, self.formatter.clone())
+    }
+
+    fn new_segment_processor_with<'a>(
+        &'a self,
+        parser: &'a Parser,
+        formatter: DynRecordWithSourceFormatter,
+    ) -> impl SegmentProcess + 'a {
         let options = SegmentProcessorOptions {
             allow_prefix: self.options.allow_prefix,
             allow_unparsed_data: self.options.filter.is_empty(),
             delimiter: self.options.delimiter.clone(),
             input_format: self.options.input_format,
+            dedup: self.options.dedup,
+            dedup_window: self.options.dedup_window,
+            context: self.options.context,
+            debug_parse: self.options.debug_parse,
         };
 
         SegmentProcessor::new(
             parser,
-            self.formatter.clone(),
-            Query::from(&self.options.filter),
+            formatter,
+            MatchTracker::new(Query::from(&self.options.filter), &self.matched),
             options,
         )
     }
 
+    fn new_columns_formatter(options: &Options) -> ColumnsRecordFormatter {
+        ColumnsRecordFormatter::new(options.columns.clone())
+            .with_timestamp_formatter(DateTimeFormatter::new(options.time_format.clone(), options.time_zone))
+            .with_width(options.column_width)
+    }
+
     /// Creates a formatter based on the provided options.
     ///
-    /// Returns either a RawRecordFormatter or a RecordFormatter depending on the options.
-    fn new_formatter(options: &Options, punctuation: Arc<ResolvedPunctuation>) -> DynRecordWithSourceFormatter {
+    /// Returns a NoOpRecordWithSourceFormatter when only statistics or counts are printed,
+    /// otherwise the formatter created by [`Self::new_record_formatter`], wrapped into an HtmlRecordFormatter for HTML output.
+    fn new_formatter(
+        options: &Options,
+        theme: Arc<Theme>,
+        punctuation: Arc<ResolvedPunctuation>,
+    ) -> DynRecordWithSourceFormatter {
+        if options.stats.is_some() || options.count.is_some() {
+            return Arc::new(NoOpRecordWithSourceFormatter);
+        }
+
+        let formatter = Self::new_record_formatter(options, theme, punctuation);
+        if options.output_format == OutputFormat::Html {
+            // raw source lines and field values are escaped as well as the formatted messages
+            Arc::new(HtmlRecordFormatter::new(formatter))
+        } else {
+            formatter
+        }
+    }
+
+    /// Returns either a RawRecordFormatter, a ValueRecordFormatter, a ColumnsRecordFormatter, a LogfmtRecordFormatter, a JsonRecordFormatter or a RecordFormatter depending on the options.
+    fn new_record_formatter(
+        options: &Options,
+        theme: Arc<Theme>,
+        punctuation: Arc<ResolvedPunctuation>,
+    ) -> DynRecordWithSourceFormatter {
         if options.raw {
             Arc::new(RawRecordFormatter {})
-        } else {
+        } else if let Some(key) = &options.only {
+            Arc::new(ValueRecordFormatter::new(key.clone()).with_theme(theme))
+        } else if !options.columns.is_empty() {
+            Arc::new(Self::new_columns_formatter(options))
+        } else if options.output_format == OutputFormat::Logfmt {
             Arc::new(
-                RecordFormatterBuilder::new()
-                    .with_theme(options.theme.clone())
-                    .with_timestamp_formatter(DateTimeFormatter::new(options.time_format.clone(), options.time_zone))
+                LogfmtRecordFormatter::new()
                     .with_empty_fields_hiding(options.hide_empty_fields)
                     .with_field_filter(options.fields.filter.clone())
-                    .with_options(options.formatting.clone())
-                    .with_raw_fields(options.raw_fields)
-                    .with_flatten(options.flatten)
-                    .with_ascii(options.ascii)
-                    .with_always_show_time(options.fields.settings.predefined.time.show == FieldShowOption::Always)
-                    .with_always_show_level(options.fields.settings.predefined.level.show == FieldShowOption::Always)
-                    .with_punctuation(punctuation)
-                    .build(),
+                    .with_field_quote(options.field_quote),
+            )
+        } else if options.output_format == OutputFormat::Json {
+            Arc::new(
+                JsonRecordFormatter::new()
+                    .with_empty_fields_hiding(options.hide_empty_fields)
+                    .with_field_filter(options.fields.filter.clone())
+                    .with_source_parser(
+                        RawRecord::parser()
+                            .allow_prefix(options.allow_prefix)
+                            .format(options.input_format),
+                    )
+                    .with_normalization(options.normalize)
+                    .with_aliases(if options.original_keys {
+                        Default::default()
+                    } else {
+                        options.fields.settings.aliases.clone()
+                    }),
             )
+        } else {
+            let formatter = RecordFormatterBuilder::new()
+                .with_theme(theme)
+                .with_colors(options.colors)
+                .with_timestamp_formatter(DateTimeFormatter::new(options.time_format.clone(), options.time_zone))
+                .with_empty_fields_hiding(options.hide_empty_fields)
+                .with_field_filter(options.fields.filter.clone())
+                .with_options(options.formatting.clone())
+                .with_raw_fields(options.raw_fields)
+                .with_flatten(options.flatten)
+                .with_flatten_depth(options.flatten_depth)
+                .with_ascii(options.ascii)
+                .with_always_show_time(options.fields.settings.predefined.time.show == FieldShowOption::Always)
+                .with_always_show_level(options.fields.settings.predefined.level.show == FieldShowOption::Always)
+                .with_punctuation(punctuation)
+                .with_highlighter(options.highlighter.clone())
+                .with_pinned_fields(options.output_fields.clone())
+                .with_color_by(options.color_by.clone())
+                .with_wrapper(options.wrapper)
+                .with_max_message_width(options.max_message_width)
+                .with_max_field_width(options.max_field_width)
+                .with_error_key(options.error_key.clone())
+                .with_message_keys(options.fields.settings.predefined.message.names.clone())
+                .with_json_expansion(options.expand_json)
+                .with_template_rendering(options.render_template)
+                .with_template_fields_dropping(options.drop_template_fields)
+                .with_ansi_stripping(options.strip_input_ansi)
+                .with_pretty_layout(options.pretty)
+                .with_field_quote(options.field_quote)
+                .with_rules(options.rules.clone())
+                .build();
+            Arc::new(formatter)
         }
     }
 }
@@ -867,6 +1941,9 @@ pub trait SegmentProcess {
         limit: Option<usize>,
         observer: &mut O,
     );
+
+    /// Writes out records held back by the processor, if any.
+    fn flush(&mut self, _buf: &mut Vec<u8>) {}
 }
 
 // ---
@@ -877,6 +1954,10 @@ pub struct SegmentProcessorOptions {
     pub allow_unparsed_data: bool,
     pub delimiter: Delimiter,
     pub input_format: Option<InputFormat>,
+    pub dedup: bool,
+    pub dedup_window: Option<Duration>,
+    pub context: ContextOptions,
+    pub debug_parse: bool,
 }
 
 // ---
@@ -887,11 +1968,18 @@ pub struct SegmentProcessor<'a, Formatter, Filter> {
     filter: Filter,
     options: SegmentProcessorOptions,
     delim: <Delimiter as Delimit>::Searcher,
+    dedup: Option<Deduplicator>,
+    context: Option<ContextTracker>,
 }
 
 impl<'a, Formatter: RecordWithSourceFormatter, Filter: RecordFilter> SegmentProcessor<'a, Formatter, Filter> {
     pub fn new(parser: &'a Parser, formatter: Formatter, filter: Filter, options: SegmentProcessorOptions) -> Self {
         let delim = options.delimiter.clone().into_searcher();
+        let dedup = options
+            .dedup
+            .then(|| Deduplicator::new().with_window(options.dedup_window));
+        let context =
+            (!options.context.is_empty()).then(|| ContextTracker::new(options.context.before, options.context.after));
 
         Self {
             parser,
@@ -899,6 +1987,8 @@ impl<'a, Formatter: RecordWithSourceFormatter, Filter: RecordFilter> SegmentProc
             filter,
             options,
             delim,
+            dedup,
+            context,
         }
     }
 
@@ -918,9 +2008,10 @@ impl<'a, Formatter: RecordWithSourceFormatter, Filter: RecordFilter> SegmentProc
         let mut i = 0;
         let limit = limit.unwrap_or(usize::MAX);
 
-        for line in self.delim.split(data) {
+        for (n, line) in self.delim.split(data).enumerate() {
             if line.is_empty() {
                 if self.show_unparsed() {
+                    self.flush(buf);
                     buf.push(b'\n');
                 }
                 continue;
@@ -932,37 +2023,92 @@ impl<'a, Formatter: RecordWithSourceFormatter, Filter: RecordFilter> SegmentProc
                 .parse(line);
             let mut parsed_some = false;
             let mut produced_some = false;
+            let mut deduped_some = false;
+            let mut limited = false;
+            let mut error = None;
             let mut last_offset = 0;
-            while let Some(Ok(ar)) = stream.next() {
+            while let Some(ar) = stream.next() {
+                let ar = match ar {
+                    Ok(ar) => ar,
+                    Err(err) => {
+                        error = Some(err);
+                        break;
+                    }
+                };
                 i += 1;
                 last_offset = ar.offsets.end;
-                if parsed_some {
+                if parsed_some && self.dedup.is_none() && self.context.is_none() {
                     buf.push(b'\n');
                 }
                 parsed_some = true;
-                let record = self.parser.parse(&ar.record);
-                if record.matches(&self.filter) {
-                    let begin = buf.len();
+                let record = if self.options.debug_parse {
+                    let (record, trace) = self.parser.parse_traced(&ar.record);
+                    report_parse(stream.format_name(), Ok(&trace), &line[ar.offsets.clone()]);
+                    record
+                } else {
+                    self.parser.parse(&ar.record)
+                };
+                let format = |buf: &mut Vec<u8>| {
                     buf.extend(prefix.as_bytes());
                     buf.extend(ar.prefix);
                     if ar.prefix.last().map(|&x| x == b' ') == Some(false) {
                         buf.push(b' ');
                     }
                     self.formatter.format_record(buf, record.with_source(&line[ar.offsets]));
-                    let end = buf.len();
-                    observer.observe_record(&record, begin..end);
-                    produced_some = true;
+                };
+                if record.matches(&self.filter) {
+                    match (&mut self.dedup, &mut self.context) {
+                        (Some(dedup), _) => {
+                            // repeated records are counted, and each distinct one is held back until the next one arrives
+                            if let Some(pending) = dedup.push(&record, buf) {
+                                format(pending);
+                            }
+                            deduped_some = true;
+                        }
+                        (None, Some(context)) => {
+                            // preceding context records are written first, and each record is terminated on its own
+                            let buf = context.push_match(buf);
+                            let begin = buf.len();
+                            format(buf);
+                            let end = buf.len();
+                            buf.push(b'\n');
+                            observer.observe_record(&record, begin..end);
+                        }
+                        (None, None) => {
+                            let begin = buf.len();
+                            format(buf);
+                            let end = buf.len();
+                            observer.observe_record(&record, begin..end);
+                            produced_some = true;
+                        }
+                    }
+                } else {
+                    if let Some(target) = self.context.as_mut().and_then(|context| context.push_unmatched(buf)) {
+                        format(target);
+                        target.push(b'\n');
+                    }
+                    observer.observe_unmatched_record(&record);
                 }
                 if i >= limit {
+                    limited = true;
                     break;
                 }
             }
             let remainder = if parsed_some { &line[last_offset..] } else { line };
+            if !limited && !remainder.trim_ascii().is_empty() {
+                if self.options.debug_parse {
+                    report_parse(stream.format_name(), Err(error.as_ref()), remainder);
+                }
+                observer.observe_invalid_line(n, remainder, error.as_ref());
+            }
             if !remainder.is_empty() && self.show_unparsed() {
-                if !parsed_some {
+                if !parsed_some || (deduped_some && !remainder.trim_ascii().is_empty()) {
+                    // records held back by deduplication are written first so that the remainder keeps its place
+                    self.flush(buf);
                     buf.extend(prefix.as_bytes());
-                }
-                if !parsed_some || produced_some {
+                    buf.extend_from_slice(remainder);
+                    buf.push(b'\n');
+                } else if produced_some {
                     buf.extend_from_slice(remainder);
                     buf.push(b'\n');
                 }
@@ -971,12 +2117,160 @@ impl<'a, Formatter: RecordWithSourceFormatter, Filter: RecordFilter> SegmentProc
             }
         }
     }
+
+    fn flush(&mut self, buf: &mut Vec<u8>) {
+        if let Some(dedup) = &mut self.dedup {
+            dedup.flush(buf);
+        }
+    }
+}
+
+/// Writes a line to stderr telling how a record was parsed, or why a part of a line was not, for --debug-parse.
+fn report_parse(format: Option<&str>, trace: std::result::Result<&ParseTrace, Option<&Error>>, source: &[u8]) {
+    const EXCERPT: usize = 60;
+
+    let source = String::from_utf8_lossy(source);
+    let source = source.trim();
+    let excerpt = match source.char_indices().nth(EXCERPT) {
+        Some((i, _)) => format!("{}...", &source[..i]),
+        None => source.to_owned(),
+    };
+
+    let outcome = match trace {
+        Ok(trace) => trace.to_string(),
+        Err(Some(err)) => format!("unparsed ({})", err),
+        Err(None) => "unparsed".to_owned(),
+    };
+    let line = format!("debug-parse: {} {}: {}\n", format.unwrap_or("-"), outcome, excerpt);
+    std::io::stderr().write_all(line.as_bytes()).ok();
+}
+
+// ---
+
+/// Theme that is replaced each time its file changes, numbered by the generation of the last reload.
+struct LiveTheme {
+    current: Arc<Mutex<(u64, Arc<Theme>)>>,
+}
+
+impl LiveTheme {
+    fn new(theme: Arc<Theme>) -> Self {
+        Self {
+            current: Arc::new(Mutex::new((0, theme))),
+        }
+    }
+
+    fn get(&self) -> (u64, Arc<Theme>) {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// Starts watching the theme file in background.
+    ///
+    /// A theme that fails to load is reported and the previously loaded one is kept.
+    fn watch(&self, path: PathBuf) {
+        let current = self.current.clone();
+        std::thread::spawn(move || {
+            let result = fsmon::run(vec![path.clone()], |event| {
+                match event.kind {
+                    EventKind::Modify(_) | EventKind::Create(_) | EventKind::Any | EventKind::Other => {
+                        match Theme::load_file(&path) {
+                            Ok(theme) => {
+                                log::debug!("reloaded theme from {}", path.display());
+                                let mut current = current.lock().unwrap();
+                                *current = (current.0 + 1, Arc::new(theme));
+                            }
+                            Err(err) => log::warn!("failed to reload theme: {err}"),
+                        }
+                    }
+                    EventKind::Remove(_) | EventKind::Access(_) => {}
+                }
+                Ok(())
+            });
+            if let Err(err) = result {
+                log::warn!("stopped watching theme file {}: {err}", path.display());
+            }
+        });
+    }
+}
+
+// ---
+
+/// Forwards records to the inner observer and reports invalid lines of the given input to the app.
+struct InvalidLineReporter<'a, O> {
+    inner: O,
+    app: &'a App,
+    input: &'a str,
+    first_line: Option<usize>,
+}
+
+impl<'a, O: RecordObserver> InvalidLineReporter<'a, O> {
+    fn new(inner: O, app: &'a App, input: &'a str, first_line: Option<usize>) -> Self {
+        Self {
+            inner,
+            app,
+            input,
+            first_line,
+        }
+    }
+}
+
+impl<'a, O: RecordObserver> RecordObserver for InvalidLineReporter<'a, O> {
+    #[inline]
+    fn observe_record<'r>(&mut self, record: &Record<'r>, location: Range<usize>) {
+        self.inner.observe_record(record, location)
+    }
+
+    #[inline]
+    fn observe_unmatched_record<'r>(&mut self, record: &Record<'r>) {
+        self.inner.observe_unmatched_record(record)
+    }
+
+    #[inline]
+    fn observe_invalid_line(&mut self, index: usize, line: &[u8], error: Option<&Error>) {
+        let line_number = self.first_line.map(|first| first + index + 1);
+        self.app.report_invalid_line(self.input, line_number, line, error)
+    }
+}
+
+// ---
+
+/// Wraps a record filter to remember whether any record has passed it.
+struct MatchTracker<'a, F> {
+    filter: F,
+    matched: &'a AtomicBool,
+}
+
+impl<'a, F: RecordFilter> MatchTracker<'a, F> {
+    fn new(filter: F, matched: &'a AtomicBool) -> Self {
+        Self { filter, matched }
+    }
+}
+
+impl<'a, F: RecordFilter> RecordFilter for MatchTracker<'a, F> {
+    #[inline]
+    fn apply<'r>(&self, record: &Record<'r>) -> bool {
+        let result = self.filter.apply(record);
+        // load first to avoid contention on the shared flag once it is set
+        if result && !self.matched.load(Ordering::Relaxed) {
+            self.matched.store(true, Ordering::Relaxed);
+        }
+        result
+    }
 }
 
 // ---
 
 pub trait RecordObserver {
     fn observe_record<'a>(&mut self, record: &Record<'a>, location: Range<usize>);
+
+    /// Called for a parsed record that did not pass the filter.
+    #[inline]
+    fn observe_unmatched_record<'a>(&mut self, _record: &Record<'a>) {}
+
+    /// Called for a non-empty line, or its trailing part, that could not be parsed.
+    ///
+    /// The index is the number of the line within the processed segment, starting from 0.
+    #[inline]
+    fn observe_invalid_line(&mut self, _index: usize, _line: &[u8], _error: Option<&Error>) {}
 }
 
 // ---
@@ -990,6 +2284,39 @@ impl RecordObserver for RecordIgnorer {
 
 // ---
 
+/// Detects records past the upper time bound in inputs known to be sorted chronologically.
+struct UntilTracker<'a> {
+    until: Option<DateTime<Utc>>,
+    reached: &'a AtomicBool,
+}
+
+impl<'a> UntilTracker<'a> {
+    fn new(until: Option<DateTime<Utc>>, reached: &'a AtomicBool) -> Self {
+        Self { until, reached }
+    }
+}
+
+impl<'a> RecordObserver for UntilTracker<'a> {
+    #[inline]
+    fn observe_record<'r>(&mut self, _: &Record<'r>, _: Range<usize>) {}
+
+    #[inline]
+    fn observe_unmatched_record<'r>(&mut self, record: &Record<'r>) {
+        if let Some(until) = self.until {
+            if record
+                .ts
+                .as_ref()
+                .and_then(|ts| ts.parse())
+                .is_some_and(|ts| ts > until)
+            {
+                self.reached.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+// ---
+
 struct TimestampIndexBuilder {
     result: TimestampIndex,
 }
@@ -1123,5 +2450,19 @@ where
 
 // ---
 
+/// Maximum number of bytes of an invalid line shown in strict mode reports.
+const MAX_INVALID_LINE_EXCERPT: usize = 256;
+
+/// Maximum number of records the buffers of the last records are allocated for up front with --tail option.
+const MAX_TAIL_PREALLOCATION: usize = 1024;
+
+/// Number of last records preloaded from each file in follow mode unless set explicitly.
+const DEFAULT_FOLLOW_TAIL: u64 = 10;
+
+/// Minimum interval between bookmark updates while records keep coming in follow mode.
+const BOOKMARK_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+// ---
+
 #[cfg(test)]
 mod tests;
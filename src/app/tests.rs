@@ -5,9 +5,10 @@ use super::*;
 use std::io::Cursor;
 
 // third-party imports
-use chrono::{Offset, Utc};
+use chrono::{Offset, TimeZone, Utc};
 use chrono_tz::UTC;
 use maplit::hashmap;
+use rstest::rstest;
 
 // local imports
 use crate::{
@@ -61,6 +62,18 @@ fn test_cat_with_theme() {
     );
 }
 
+#[test]
+fn test_cat_with_theme_no_colors() {
+    let input = input(r#"{"level":"warning","msg":"m","ts":"2023-12-07T20:07:05.949Z","a":1}"#);
+    let mut output = Vec::new();
+    let app = App::new(options().with_theme(theme()).with_colors(false));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        "2023-12-07 20:07:05.949 |WRN| m a=1\n"
+    );
+}
+
 #[test]
 fn test_cat_no_msg() {
     let input = input(r#"{"caller":"main.go:539","duration":"15d","level":"info","ts":"2023-12-07T20:07:05.949Z"}"#);
@@ -105,6 +118,44 @@ fn test_cat_field_exclude() {
     );
 }
 
+#[test]
+fn test_cat_logfmt_output() {
+    let input = input(
+        r#"{"caller":"main.go:539","duration":"15d","level":"info","ts":"2023-12-07T20:07:05.949Z","msg":"hello world","a":{"b":1}}"#,
+    );
+    let mut output = Vec::new();
+    let app = App::new(options().with_output_format(OutputFormat::Logfmt));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        "time=2023-12-07T20:07:05.949Z level=info msg=\"hello world\" duration=15d a.b=1 caller=main.go:539\n",
+    );
+}
+
+#[test]
+fn test_cat_html_output() {
+    let input = input(r#"{"level":"info","ts":"2023-12-07T20:07:05.949Z","msg":"a<b>","x":"&"}"#);
+    let mut output = Vec::new();
+    let app = App::new(options().with_output_format(OutputFormat::Html));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        "<pre>2023-12-07 20:07:05.949 |INF| a&lt;b&gt; x=&amp;\n</pre>\n",
+    );
+}
+
+#[test]
+fn test_cat_html_raw_output() {
+    let input = input(r#"{"level":"info","msg":"a<b>","x":"&"}"#);
+    let mut output = Vec::new();
+    let app = App::new(options().with_output_format(OutputFormat::Html).with_raw(true));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        "<pre>{&quot;level&quot;:&quot;info&quot;,&quot;msg&quot;:&quot;a&lt;b&gt;&quot;,&quot;x&quot;:&quot;&amp;&quot;}\n</pre>\n",
+    );
+}
+
 #[test]
 fn test_cat_raw_fields() {
     let input =
@@ -149,6 +200,28 @@ fn test_smart_delim_combo() {
     assert_eq!(std::str::from_utf8(&output).unwrap(), format!("{}\n\n{}\n", L1, L2),);
 }
 
+#[test]
+fn test_length_prefixed_framing() {
+    const R1: &str = "{\n  \"msg\": \"m1\"\n}";
+    const R2: &str = r#"{"msg":"m2"}"#;
+
+    let mut data = String::new();
+    for record in [R1, R2] {
+        data.push_str("\0\0\0");
+        data.push(char::from(record.len() as u8));
+        data.push_str(record);
+    }
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options()
+            .with_raw(true)
+            .with_framing(Some(LengthHeader::U32Be), Delimiter::Byte(0)),
+    );
+    app.run(vec![input(data)], &mut output).unwrap();
+    assert_eq!(std::str::from_utf8(&output).unwrap(), format!("{}\n{}\n", R1, R2));
+}
+
 #[test]
 fn test_sort_with_blank_lines() {
     let input = input(concat!(
@@ -170,6 +243,33 @@ fn test_sort_with_blank_lines() {
     );
 }
 
+#[test]
+fn test_sort_with_missing_timestamps() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"debug","msg":"m3"}"#,
+        "\n",
+        r#"{"level":"debug","msg":"m4"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:09:16.860711+01:00","msg":"m1"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_sort(true));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:09:16.860 |DBG| m1\n",
+            "2024-01-25 18:10:20.435 |DBG| m2\n",
+            "|DBG| m3\n",
+            "|DBG| m4\n",
+        ),
+    );
+}
+
 #[test]
 fn test_filter_with_blank_lines() {
     let input = input(concat!(
@@ -196,6 +296,462 @@ fn test_filter_with_blank_lines() {
     );
 }
 
+#[rstest]
+#[case("msg=m2", false, true)]
+#[case("msg=m3", false, false)]
+#[case("msg=m2", true, true)]
+#[case("msg=m3", true, false)]
+fn test_matched(#[case] filter: &str, #[case] sort: bool, #[case] expected: bool) {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:09:16.860711+01:00","msg":"m1"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options().with_sort(sort).with_filter(
+            Filter {
+                fields: FieldFilterSet::new([filter]).unwrap(),
+                ..Default::default()
+            }
+            .into(),
+        ),
+    );
+    assert!(!app.matched());
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(app.matched(), expected);
+}
+
+#[test]
+fn test_cat_sorted_input_until() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T18:09:16Z","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T18:10:20Z","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T18:11:30Z","msg":"m3"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options().with_sorted_input(true).with_filter(
+            Filter {
+                until: Some(Utc.with_ymd_and_hms(2024, 1, 25, 18, 10, 20).unwrap()),
+                ..Default::default()
+            }
+            .into(),
+        ),
+    );
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:09:16.000 |DBG| m1\n",
+            "2024-01-25 18:10:20.000 |DBG| m2\n",
+        ),
+    );
+}
+
+#[test]
+fn test_count() {
+    let data = concat!(
+        r#"{"level":"debug","ts":"2024-01-25T18:09:16Z","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T18:10:20Z","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T18:11:30Z","msg":"m1"}"#,
+        "\n",
+    );
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options().with_count(Some(CountOptions::default())).with_filter(
+            Filter {
+                level: Some(Level::Error),
+                ..Default::default()
+            }
+            .into(),
+        ),
+    );
+    app.run(vec![input(data)], &mut output).unwrap();
+    assert_eq!(std::str::from_utf8(&output).unwrap(), "2\n");
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_count(Some(CountOptions { by: Some("msg".into()) })));
+    app.run(vec![input(data)], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!("         2  m1\n", "         1  m2\n"),
+    );
+}
+
+#[test]
+fn test_until_tracker() {
+    let parser = Parser::new(ParserSettings::default());
+    let raw = |s: &'static str| RawRecord::parser().parse(s.as_bytes()).next().unwrap().unwrap().record;
+
+    let reached = AtomicBool::new(false);
+    let mut tracker = UntilTracker::new(Some(Utc.with_ymd_and_hms(2024, 1, 25, 0, 0, 0).unwrap()), &reached);
+
+    let rec = raw(r#"{"ts":"2024-01-24T00:00:00Z"}"#);
+    tracker.observe_unmatched_record(&parser.parse(&rec));
+    assert!(!reached.load(Ordering::Relaxed));
+
+    let rec = raw(r#"{"msg":"no time"}"#);
+    tracker.observe_unmatched_record(&parser.parse(&rec));
+    assert!(!reached.load(Ordering::Relaxed));
+
+    let rec = raw(r#"{"ts":"2024-01-26T00:00:00Z"}"#);
+    tracker.observe_unmatched_record(&parser.parse(&rec));
+    assert!(reached.load(Ordering::Relaxed));
+}
+
+#[rstest]
+#[case(Some(StrictMode::Error), false, None, false)]
+#[case(Some(StrictMode::Error), true, None, false)]
+#[case(Some(StrictMode::Error), false, Some(5), false)]
+#[case(Some(StrictMode::Warn), false, None, true)]
+#[case(Some(StrictMode::Warn), true, None, true)]
+#[case(Some(StrictMode::Warn), false, Some(5), true)]
+#[case(None, false, None, true)]
+fn test_strict(#[case] strict: Option<StrictMode>, #[case] sort: bool, #[case] tail: Option<u64>, #[case] ok: bool) {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m2"}"#,
+        "\n",
+        "{invalid}\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:09:16.860711+01:00","msg":"m1"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_strict(strict).with_sort(sort).with_tail(tail));
+    let result = app.run(vec![input], &mut output);
+    if ok {
+        result.unwrap();
+    } else {
+        assert!(matches!(result, Err(Error::InvalidLines { count: 1 })));
+    }
+}
+
+#[test]
+fn test_dedup() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"user 1 logged in"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:21.435369+01:00","msg":"user 2 logged in"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:22.435369+01:00","msg":"done"}"#,
+        "\n",
+        "{invalid}\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:23.435369+01:00","msg":"user 3 logged in"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_dedup(true));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:20.435 |DBG| user 1 logged in (x2)\n",
+            "2024-01-25 18:10:22.435 |DBG| done\n",
+            "{invalid}\n",
+            "2024-01-25 18:10:23.435 |DBG| user 3 logged in\n",
+        ),
+    );
+}
+
+#[test]
+fn test_dedup_remainder() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"user 1 logged in"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:21.435369+01:00","msg":"user 2 logged in"} trailer"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:22.435369+01:00","msg":"done"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_dedup(true));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:20.435 |DBG| user 1 logged in (x2)\n",
+            " trailer\n",
+            "2024-01-25 18:10:22.435 |DBG| done\n",
+        ),
+    );
+}
+
+#[test]
+fn test_dedup_window() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"retry 1 failed"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:21.435369+01:00","msg":"connecting"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:22.935369+01:00","msg":"retry 2 failed"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:30.435369+01:00","msg":"retry 3 failed"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options()
+            .with_dedup(true)
+            .with_dedup_window(Some(Duration::from_secs(5))),
+    );
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:20.435 |DBG| retry 1 failed (x2 in 2s 500ms)\n",
+            "2024-01-25 18:10:21.435 |DBG| connecting\n",
+            "2024-01-25 18:10:30.435 |DBG| retry 3 failed\n",
+        ),
+    );
+}
+
+#[test]
+fn test_context() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:21.435369+01:00","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:22.435369+01:00","msg":"m3"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:23.435369+01:00","msg":"m4"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:24.435369+01:00","msg":"m5"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:25.435369+01:00","msg":"m6"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:26.435369+01:00","msg":"m7"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(
+        options()
+            .with_filter(
+                Filter {
+                    level: Some(Level::Error),
+                    ..Default::default()
+                }
+                .into(),
+            )
+            .with_context(ContextOptions { before: 1, after: 1 }),
+    );
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:21.435 |DBG| m2\n",
+            "2024-01-25 18:10:22.435 |ERR| m3\n",
+            "2024-01-25 18:10:23.435 |DBG| m4\n",
+            "--\n",
+            "2024-01-25 18:10:25.435 |DBG| m6\n",
+            "2024-01-25 18:10:26.435 |ERR| m7\n",
+        ),
+    );
+}
+
+#[test]
+fn test_tail_unbounded() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:21.435369+01:00","msg":"m2"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_tail(Some(u64::MAX)));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:20.435 |DBG| m1\n",
+            "2024-01-25 18:10:21.435 |DBG| m2\n",
+        ),
+    );
+}
+
+#[rstest]
+#[case(InputReference::Stdin, 4096)]
+#[case(InputReference::File(crate::input::InputPath::ephemeral("test.log".into())), 4096)]
+#[case(InputReference::File(crate::input::InputPath::ephemeral("test.log".into())), 7)]
+fn test_tail(#[case] reference: InputReference, #[case] buffer_size: usize) {
+    let data = concat!(
+        r#"{"level":"error","ts":"2024-01-25T19:10:20.435369+01:00","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:21.435369+01:00","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:22.435369+01:00","msg":"m3"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:23.435369+01:00","msg":"m4"}"#,
+        "\n",
+        "{invalid}\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:24.435369+01:00","msg":"m5"}"#,
+        "\n",
+        r#"{"level":"debug","ts":"2024-01-25T19:10:25.435369+01:00","msg":"m6"}"#,
+    );
+    let input = InputHolder::new(reference, Some(Box::new(Cursor::new(data))));
+
+    let mut options = options()
+        .with_filter(
+            Filter {
+                level: Some(Level::Error),
+                ..Default::default()
+            }
+            .into(),
+        )
+        .with_tail(Some(2));
+    options.buffer_size = NonZeroUsize::new(buffer_size).unwrap();
+
+    let mut output = Vec::new();
+    let app = App::new(options);
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "2024-01-25 18:10:22.435 |ERR| m3\n",
+            "2024-01-25 18:10:24.435 |ERR| m5\n",
+        ),
+    );
+}
+
+#[rstest]
+#[case(RecordRange::new(1, Some(2)), "m3\nm5\n")]
+#[case(RecordRange::new(0, Some(0)), "")]
+#[case(RecordRange::new(u64::MAX, None), "")]
+#[case("1:2".parse().unwrap(), "m1\nm3\n")]
+#[case("3:".parse().unwrap(), "m5\n")]
+#[case(":1".parse().unwrap(), "m1\n")]
+fn test_range(#[case] range: RecordRange, #[case] expected: &str) {
+    let data = concat!(
+        r#"{"level":"error","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"debug","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"error","msg":"m3"}"#,
+        "\n",
+        r#"{"level":"error","msg":"m5"}"#,
+        "\n",
+    );
+
+    let options = options()
+        .with_filter(
+            Filter {
+                level: Some(Level::Error),
+                ..Default::default()
+            }
+            .into(),
+        )
+        .with_range(Some(range));
+
+    let mut output = Vec::new();
+    let app = App::new(options);
+    app.run(vec![input(data)], &mut output).unwrap();
+    let messages = std::str::from_utf8(&output)
+        .unwrap()
+        .lines()
+        .map(|line| line.rsplit(' ').next().unwrap().to_owned() + "\n")
+        .collect::<String>();
+    assert_eq!(messages, expected);
+}
+
+#[test]
+fn test_range_parse() {
+    assert_eq!(
+        "10000:10100".parse::<RecordRange>().unwrap(),
+        RecordRange {
+            first: 10000,
+            last: Some(10100)
+        }
+    );
+    assert!(matches!("0:5".parse::<RecordRange>(), Err(Error::InvalidRange { .. })));
+    assert!(matches!("5:4".parse::<RecordRange>(), Err(Error::InvalidRange { .. })));
+    assert!(matches!("5".parse::<RecordRange>(), Err(Error::InvalidRange { .. })));
+}
+
+#[test]
+fn test_group_by() {
+    let input = input(concat!(
+        r#"{"level":"info","ts":"2024-01-25T19:10:22.435369+01:00","req":"b","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"info","ts":"2024-01-25T19:10:21.435369+01:00","req":"a","msg":"m2"}"#,
+        "\n",
+        r#"{"level":"info","ts":"2024-01-25T19:10:20.435369+01:00","req":"b","msg":"m3"}"#,
+        "\n",
+        r#"{"level":"info","ts":"2024-01-25T19:10:23.435369+01:00","msg":"m4"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_group_by(Some("req".into())));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "==> req=b (2 records) <==\n",
+            "2024-01-25 18:10:20.435 |INF| m3 req=b\n",
+            "2024-01-25 18:10:22.435 |INF| m1 req=b\n",
+            "\n",
+            "==> req=a (1 record) <==\n",
+            "2024-01-25 18:10:21.435 |INF| m2 req=a\n",
+            "\n",
+            "==> no req (1 record) <==\n",
+            "2024-01-25 18:10:23.435 |INF| m4\n",
+        ),
+    );
+}
+
+#[test]
+fn test_columns() {
+    let input = input(concat!(
+        r#"{"level":"debug","ts":"2024-01-25T19:10:20.435369+01:00","svc":"api","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:21.435369+01:00","msg":"m2"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let columns = vec!["time".into(), "level".into(), "svc".into(), "msg".into()];
+    let app = App::new(options().with_columns(columns, Some(4)));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&output).unwrap(),
+        concat!(
+            "TIME                     LEVEL  SVC   MSG\n",
+            "2024-01-25 18:10:20.435  DBG    api   m1\n",
+            "2024-01-25 18:10:21.435  ERR          m2\n",
+        ),
+    );
+}
+
+#[test]
+fn test_only() {
+    let input = input(concat!(
+        r#"{"level":"info","ts":"2024-01-25T19:10:20.435369+01:00","svc":"api","msg":"m1"}"#,
+        "\n",
+        r#"{"level":"error","ts":"2024-01-25T19:10:21.435369+01:00","msg":"m2"}"#,
+        "\n",
+    ));
+
+    let mut output = Vec::new();
+    let app = App::new(options().with_only(Some("svc".into())));
+    app.run(vec![input], &mut output).unwrap();
+    assert_eq!(std::str::from_utf8(&output).unwrap(), "api\n\n");
+}
+
 #[test]
 fn test_sort_with_clingy_lines() {
     let input = input(concat!(
@@ -368,6 +924,7 @@ fn input<S: Into<String>>(s: S) -> InputHolder {
 fn options() -> Options {
     Options {
         theme: Arc::new(Theme::none()),
+        colors: true,
         time_format: LinuxDateFormat::new("%Y-%m-%d %T.%3N").compile(),
         raw: false,
         raw_fields: false,
@@ -389,14 +946,55 @@ fn options() -> Options {
         follow: false,
         sync_interval: Duration::from_secs(1),
         input_info: Default::default(),
+        filename: None,
         input_format: None,
+        output_format: OutputFormat::Text,
+        html_standalone: false,
         dump_index: false,
+        progress: false,
+        index_mode: IndexMode::default(),
         app_dirs: None,
-        tail: 0,
+        tail: None,
+        range: None,
+        group_by: None,
+        watch: None,
+        listen: None,
         delimiter: Delimiter::default(),
+        framing: None,
+        decompression: Decompression::Auto,
         unix_ts_unit: None,
         flatten: false,
+        flatten_depth: None,
         ascii: AsciiMode::Off,
+        stats: None,
+        count: None,
+        highlighter: None,
+        output_fields: Vec::new(),
+        color_by: None,
+        strict: None,
+        dedup: false,
+        dedup_window: None,
+        context: ContextOptions::default(),
+        sorted_input: false,
+        wrapper: None,
+        max_message_width: None,
+        max_field_width: None,
+        error_key: None,
+        expand_json: false,
+        render_template: false,
+        drop_template_fields: false,
+        strip_input_ansi: false,
+        pretty: false,
+        field_quote: FieldQuote::Auto,
+        debug_parse: false,
+        bookmark: None,
+        rules: Vec::new(),
+        normalize: false,
+        original_keys: false,
+        columns: Vec::new(),
+        column_width: None,
+        only: None,
+        theme_file: None,
     }
 }
 
@@ -518,6 +1116,24 @@ fn test_input_badges_with_ascii_mode() {
     assert_ne!(badges_a, badges_u, "ASCII and Unicode badges should be different");
 }
 
+#[rstest]
+#[case(None, 1, None)]
+#[case(None, 2, Some(vec!["a.log    ", "long.log "]))]
+#[case(Some(true), 1, Some(vec!["a.log "]))]
+#[case(Some(false), 2, None)]
+fn test_filename_badges(#[case] filename: Option<bool>, #[case] n: usize, #[case] expected: Option<Vec<&str>>) {
+    let inputs = ["/path/to/a.log", "/path/to/long.log"].map(|path| {
+        InputReference::File(crate::input::InputPath {
+            original: std::path::PathBuf::from(path),
+            canonical: std::path::PathBuf::from(path),
+        })
+    });
+
+    let app = App::new(options().with_filename(filename));
+    let badges = app.input_badges(inputs[..n].iter());
+    assert_eq!(badges, expected.map(|b| b.into_iter().map(String::from).collect()));
+}
+
 fn theme() -> Arc<Theme> {
     Sample::sample()
 }
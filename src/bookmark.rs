@@ -0,0 +1,107 @@
+//! Named positions in the inputs up to which records were shown, used to resume following them later.
+
+// std imports
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+// third-party imports
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// local imports
+use crate::error::{Error, Result};
+
+// ---
+
+/// Bookmark stored in a file named after it, telling the position of the last record shown for a set of inputs.
+pub struct Bookmark {
+    path: PathBuf,
+}
+
+impl Bookmark {
+    /// Returns the bookmark with the given name in the directory.
+    ///
+    /// The name may contain only letters, digits, `-`, `_` and `.`, and may not start with `.`.
+    pub fn new(dir: &Path, name: &str) -> Result<Self> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(Error::InvalidBookmarkName(name.to_owned()));
+        }
+
+        Ok(Self {
+            path: dir.join(format!("{name}.json")),
+        })
+    }
+
+    /// Returns the position of the last record shown for the same inputs.
+    ///
+    /// Returns `None` if the bookmark was not saved yet, cannot be read or was saved for other inputs,
+    /// so following starts from the beginning.
+    pub fn load(&self, inputs: &[String]) -> Option<Position> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                log::warn!("ignoring bookmark {}: {err}", self.path.display());
+                return None;
+            }
+        };
+        match serde_json::from_slice::<State>(&data) {
+            Ok(state) if state.inputs == inputs => Some(state.position),
+            Ok(_) => {
+                log::warn!("ignoring bookmark {} saved for other inputs", self.path.display());
+                None
+            }
+            Err(err) => {
+                log::warn!("ignoring bookmark {}: {err}", self.path.display());
+                None
+            }
+        }
+    }
+
+    /// Saves the position of the last record shown for the inputs, replacing the file at once.
+    pub fn save(&self, inputs: &[String], position: Position) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let state = State {
+            inputs: inputs.to_vec(),
+            position,
+        };
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(&state)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+// ---
+
+/// Position of the last record shown.
+///
+/// Records often share the same time, so the number of records shown with exactly that time is kept as well,
+/// telling how many of them to skip when resuming.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub ts: DateTime<Utc>,
+    #[serde(default)]
+    pub count: usize,
+}
+
+// ---
+
+#[derive(Serialize, Deserialize)]
+struct State {
+    inputs: Vec<String>,
+    #[serde(flatten)]
+    position: Position,
+}
+
+#[cfg(test)]
+mod tests;
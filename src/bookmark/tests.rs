@@ -0,0 +1,67 @@
+use super::*;
+
+use chrono::TimeZone;
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("hl-test-bookmark-{}-{}", name, std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    dir
+}
+
+#[test]
+fn test_save_load() {
+    let dir = dir("save-load");
+    let bookmark = Bookmark::new(&dir, "incident-1").unwrap();
+    let inputs = vec!["/var/log/app.log".to_owned()];
+    let position = Position {
+        ts: Utc.timestamp_opt(1700000000, 123456789).unwrap(),
+        count: 3,
+    };
+
+    assert_eq!(bookmark.load(&inputs), None);
+    bookmark.save(&inputs, position).unwrap();
+    assert_eq!(bookmark.load(&inputs), Some(position));
+    assert_eq!(bookmark.load(&["/var/log/other.log".to_owned()]), None);
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_load_without_count() {
+    let dir = dir("without-count");
+    let bookmark = Bookmark::new(&dir, "b").unwrap();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("b.json"), br#"{"inputs":[],"ts":"2023-11-14T22:13:20Z"}"#).unwrap();
+
+    assert_eq!(
+        bookmark.load(&[]),
+        Some(Position {
+            ts: Utc.timestamp_opt(1700000000, 0).unwrap(),
+            count: 0,
+        })
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_invalid_file() {
+    let dir = dir("invalid-file");
+    let bookmark = Bookmark::new(&dir, "b").unwrap();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("b.json"), b"{not json").unwrap();
+
+    assert_eq!(bookmark.load(&[]), None);
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_invalid_name() {
+    for name in ["", ".hidden", "a/b", "..", "a b"] {
+        assert!(
+            matches!(Bookmark::new(Path::new("."), name), Err(Error::InvalidBookmarkName(_))),
+            "{name:?}"
+        );
+    }
+}
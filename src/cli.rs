@@ -1,5 +1,5 @@
 // std imports
-use std::{num::NonZeroUsize, path::PathBuf};
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
 
 // third-party imports
 use clap::{
@@ -13,8 +13,10 @@ use clap_complete::Shell;
 use crate::{
     config,
     error::*,
+    input::Decompression,
+    iox::LengthHeader,
     level::{LevelValueParser, RelaxedLevel},
-    settings::{self, AsciiModeOpt, InputInfo},
+    settings::{self, AsciiModeOpt, ColorMode, InputInfo},
     themecfg,
 };
 use enumset_ext::convert::str::EnumSet;
@@ -105,9 +107,75 @@ pub struct Opt {
     #[arg(long, short = 'F', overrides_with = "follow")]
     pub follow: bool,
 
-    /// Number of last messages to preload from each file in --follow mode.
-    #[arg(long, default_value = "10", overrides_with = "tail", value_name = "N")]
-    pub tail: u64,
+    /// Follow the files in the directory with names matching --glob, including the files created later, like --follow does.
+    #[arg(
+        long,
+        overrides_with = "watch",
+        conflicts_with_all = ["files", "range", "head", "skip", "group_by", "dedup", "dedup_window", "context", "before_context", "after_context", "count", "count_by"],
+        value_name = "DIR"
+    )]
+    pub watch: Option<PathBuf>,
+
+    /// Wildcard pattern matching the names of the files followed in the directory set by --watch option.
+    #[arg(
+        long,
+        default_value = "*",
+        requires = "watch",
+        overrides_with = "glob",
+        value_name = "PATTERN"
+    )]
+    pub glob: String,
+
+    /// Receive messages sent to the socket at the given address, e.g. 'tcp://127.0.0.1:5170' or 'udp://0.0.0.0:12201', like --follow does,
+    /// each TCP connection is a stream of delimited messages and each UDP datagram is a single message, prefixed with the sender address.
+    #[arg(
+        long,
+        overrides_with = "listen",
+        conflicts_with_all = ["files", "watch", "range", "head", "skip", "group_by", "dedup", "dedup_window", "context", "before_context", "after_context", "count", "count_by"],
+        value_name = "ADDRESS"
+    )]
+    pub listen: Option<String>,
+
+    /// Remember the last message shown in --follow mode under the given name and skip the messages up to it when following the same inputs with the same bookmark again.
+    #[arg(long, overrides_with = "bookmark", value_name = "NAME")]
+    pub bookmark: Option<String>,
+
+    /// Show only the last N messages passing the filters, or number of last messages to preload from each file in --follow mode [default: 10 in --follow mode].
+    #[arg(
+        long,
+        short = 'n',
+        overrides_with = "tail",
+        conflicts_with_all = ["sort", "dedup", "dedup_window", "context", "before_context", "after_context"],
+        value_name = "N"
+    )]
+    pub tail: Option<u64>,
+
+    /// Show only the messages passing the filters with numbers from START to END, both included and counted from 1, either bound can be omitted.
+    #[arg(
+        long,
+        overrides_with = "range",
+        conflicts_with_all = ["sort", "follow", "tail", "head", "skip", "group_by", "dedup", "dedup_window", "context", "before_context", "after_context"],
+        value_name = "START:END"
+    )]
+    pub range: Option<String>,
+
+    /// Show only the first N messages passing the filters, after the ones skipped by --skip option.
+    #[arg(
+        long,
+        overrides_with = "head",
+        conflicts_with_all = ["sort", "follow", "tail", "group_by", "dedup", "dedup_window", "context", "before_context", "after_context"],
+        value_name = "N"
+    )]
+    pub head: Option<u64>,
+
+    /// Skip the first N messages passing the filters.
+    #[arg(
+        long,
+        overrides_with = "skip",
+        conflicts_with_all = ["sort", "follow", "tail", "group_by", "dedup", "dedup_window", "context", "before_context", "after_context"],
+        value_name = "N"
+    )]
+    pub skip: Option<u64>,
 
     /// Synchronization interval for live streaming mode enabled by --follow option.
     #[arg(
@@ -146,6 +214,18 @@ pub struct Opt {
     )]
     pub level: Option<RelaxedLevel>,
 
+    /// Show or hide messages with unknown level when --level filter is used.
+    #[arg(
+        long,
+        default_value = "hide",
+        env = "HL_UNKNOWN_LEVEL",
+        overrides_with = "unknown_level",
+        value_name = "WHEN",
+        value_enum,
+        help_heading = heading::FILTERING
+    )]
+    pub unknown_level: UnknownLevelOption,
+
     /// Filter messages by timestamp >= <TIME> (--time-zone and --local options are honored).
     #[arg(
         long,
@@ -166,6 +246,10 @@ pub struct Opt {
     )]
     pub until: Option<String>,
 
+    /// Assume messages in each input are sorted chronologically and stop reading an input after the first message past --until.
+    #[arg(long, help_heading = heading::FILTERING)]
+    pub sorted_input: bool,
+
     /// Filter messages by field values
     /// [k=v, k~=v, k~~=v, 'k!=v', 'k!~=v', 'k!~~=v']
     /// where ~ does substring match and ~~ does regular expression match.
@@ -177,12 +261,52 @@ pub struct Opt {
     #[arg(short, long, num_args = 1, help_heading = heading::FILTERING)]
     pub query: Vec<String>,
 
-    /// Color output control.
+    /// Show only messages matching the field filter, accepts expressions from --filter, combined with --query using 'and'.
+    #[arg(long, num_args = 1, value_name = "FILTER", help_heading = heading::FILTERING)]
+    pub include: Vec<String>,
+
+    /// Hide messages matching the field filter, accepts expressions from --filter, combined with --query using 'and'.
+    #[arg(long, num_args = 1, value_name = "FILTER", help_heading = heading::FILTERING)]
+    pub exclude: Vec<String>,
+
+    /// Show N non-matching messages following each matching message.
     #[arg(
+        short = 'A',
         long,
-        default_value = "auto",
+        overrides_with = "after_context",
+        conflicts_with_all = ["sort", "follow", "stats", "count", "dedup", "dedup_window"],
+        value_name = "N",
+        help_heading = heading::FILTERING
+    )]
+    pub after_context: Option<usize>,
+
+    /// Show N non-matching messages preceding each matching message.
+    #[arg(
+        short = 'B',
+        long,
+        overrides_with = "before_context",
+        conflicts_with_all = ["sort", "follow", "stats", "count", "dedup", "dedup_window"],
+        value_name = "N",
+        help_heading = heading::FILTERING
+    )]
+    pub before_context: Option<usize>,
+
+    /// Show N non-matching messages around each matching message, same as -A N -B N.
+    #[arg(
+        long,
+        overrides_with = "context",
+        conflicts_with_all = ["sort", "follow", "stats", "count", "dedup", "dedup_window"],
+        value_name = "N",
+        help_heading = heading::FILTERING
+    )]
+    pub context: Option<usize>,
+
+    /// Color output control, auto mode also honors NO_COLOR environment variable.
+    #[arg(
+        long,
+        default_value_t = ColorOption::from(config::global::get().color),
         env = "HL_COLOR",
-        overrides_with_all = ["color", "color_always"],
+        overrides_with_all = ["color", "color_always", "no_color"],
         default_missing_value = "always",
         num_args = 0..=1,
         value_name = "WHEN",
@@ -194,12 +318,20 @@ pub struct Opt {
     /// Handful alias for --color=always, overrides --color option.
     #[arg(
         short,
-        overrides_with_all = ["color", "color_always"],
+        overrides_with_all = ["color", "color_always", "no_color"],
         help_heading = heading::OUTPUT
     )]
     pub color_always: bool,
 
-    /// Color theme.
+    /// Handful alias for --color=never, overrides --color option.
+    #[arg(
+        long,
+        overrides_with_all = ["color", "color_always", "no_color"],
+        help_heading = heading::OUTPUT
+    )]
+    pub no_color: bool,
+
+    /// Color theme, either a stock or custom theme name or a path to a theme file, 'list' prints the available themes.
     #[arg(
         long,
         default_value_t = config::global::get().theme.clone(),
@@ -221,6 +353,15 @@ pub struct Opt {
     #[arg(long, overrides_with = "raw_fields", help_heading = heading::OUTPUT)]
     pub raw_fields: bool,
 
+    /// Do not highlight text matched by substring and regular expression filters and --highlight queries.
+    #[arg(long, env = "HL_NO_HIGHLIGHT", help_heading = heading::OUTPUT)]
+    pub no_highlight: bool,
+
+    /// Mark messages matching the query with 'highlight-style' from the configuration file without hiding the others,
+    /// accepts expressions from --query.
+    #[arg(long, value_name = "QUERY", help_heading = heading::OUTPUT)]
+    pub highlight: Vec<String>,
+
     /// Hide or reveal fields with the specified keys, prefix with ! to reveal, specify '!*' to reveal all.
     #[arg(
         long,
@@ -231,6 +372,18 @@ pub struct Opt {
     )]
     pub hide: Vec<String>,
 
+    /// Show only fields with the specified keys, can be combined with --hide to hide some of them.
+    #[arg(long, num_args = 1, value_name = "KEY", help_heading = heading::OUTPUT)]
+    pub show: Vec<String>,
+
+    /// Comma-separated list of field keys to show first, in the given order, before the rest of the fields.
+    #[arg(long, value_delimiter = ',', value_name = "KEYS", env = "HL_OUTPUT_FIELDS", help_heading = heading::OUTPUT)]
+    pub output_fields: Vec<String>,
+
+    /// Start each message with a marker colored by the value of the field with the specified key, e.g. service.
+    #[arg(long, value_name = "KEY", env = "HL_COLOR_BY", help_heading = heading::OUTPUT)]
+    pub color_by: Option<String>,
+
     /// Whether to flatten objects.
     #[arg(
         long,
@@ -246,6 +399,16 @@ pub struct Opt {
     )]
     pub flatten: FlattenOption,
 
+    /// Maximum depth of flattened objects, deeper objects are shown as compact JSON.
+    #[arg(
+        long,
+        env = "HL_FLATTEN_DEPTH",
+        value_name = "N",
+        overrides_with = "flatten_depth",
+        help_heading = heading::OUTPUT
+    )]
+    pub flatten_depth: Option<usize>,
+
     /// Time format, see https://man7.org/linux/man-pages/man1/date.1.html.
     #[arg(
         short,
@@ -278,6 +441,10 @@ pub struct Opt {
     #[arg(long, overrides_with = "local", help_heading = heading::OUTPUT)]
     _no_local: bool,
 
+    /// Use UTC time zone, overrides --time-zone and --local options.
+    #[arg(long, overrides_with_all = ["local", "utc"], help_heading = heading::OUTPUT)]
+    pub utc: bool,
+
     /// Hide empty fields, applies for null, string, object and array fields only.
     #[arg(
         long,
@@ -309,6 +476,14 @@ pub struct Opt {
     )]
     pub input_info: InputInfoSet,
 
+    /// Prefix each record with its input file name, enabled by default for multiple inputs.
+    #[arg(long, overrides_with_all = ["with_filename", "no_filename"], help_heading = heading::OUTPUT)]
+    pub with_filename: bool,
+
+    /// Do not prefix records with input file names, overrides --with-filename option.
+    #[arg(long, overrides_with_all = ["with_filename", "no_filename"], help_heading = heading::OUTPUT)]
+    pub no_filename: bool,
+
     /// Controls whether to restrict punctuation to ASCII characters only.
     ///
     /// When enabled, unicode punctuation (like fancy quotes) will be replaced with ASCII equivalents.
@@ -325,10 +500,250 @@ pub struct Opt {
     )]
     pub ascii: AsciiOption,
 
-    /// Output file.
+    /// Output file, created along with its parent directories, colors are disabled unless --color=always is set.
     #[arg(long, short = 'o', overrides_with = "output", value_name = "FILE", help_heading = heading::OUTPUT)]
     pub output: Option<String>,
 
+    /// Rotate the output file set by --output option when it grows beyond the size, renaming it to FILE.1 and shifting the older ones.
+    #[arg(
+        long,
+        requires = "output",
+        value_parser = parse_non_zero_size,
+        overrides_with = "output_max_size",
+        value_name = "SIZE",
+        help_heading = heading::OUTPUT
+    )]
+    pub output_max_size: Option<NonZeroUsize>,
+
+    /// Wrap long lines at the terminal width.
+    #[arg(
+        long,
+        default_value = "never",
+        env = "HL_WRAP",
+        overrides_with = "wrap",
+        value_name = "MODE",
+        value_enum,
+        help_heading = heading::OUTPUT
+    )]
+    pub wrap: WrapOption,
+
+    /// Truncate messages longer than the given number of columns.
+    #[arg(
+        long,
+        env = "HL_MAX_MESSAGE_WIDTH",
+        overrides_with = "max_message_width",
+        value_name = "N",
+        help_heading = heading::OUTPUT
+    )]
+    pub max_message_width: Option<usize>,
+
+    /// Truncate string field values longer than the given number of columns.
+    #[arg(
+        long,
+        env = "HL_MAX_FIELD_WIDTH",
+        overrides_with = "max_field_width",
+        value_name = "N",
+        help_heading = heading::OUTPUT
+    )]
+    pub max_field_width: Option<usize>,
+
+    /// Render the field with the specified key as a structured error, showing its stack trace and causes below the message.
+    #[arg(
+        long,
+        env = "HL_ERROR_KEY",
+        overrides_with = "error_key",
+        value_name = "KEY",
+        help_heading = heading::OUTPUT
+    )]
+    pub error_key: Option<String>,
+
+    /// Render string values containing a JSON object or array as nested structures, up to 4 levels deep.
+    #[arg(long, env = "HL_EXPAND_JSON", overrides_with = "expand_json", help_heading = heading::OUTPUT)]
+    pub expand_json: bool,
+
+    /// Treat the message as a template and substitute its named placeholders, e.g. '{UserId}', with the field values.
+    #[arg(long, env = "HL_RENDER_TEMPLATE", overrides_with = "render_template", help_heading = heading::OUTPUT)]
+    pub render_template: bool,
+
+    /// Hide the fields substituted into the message by --render-template.
+    #[arg(long, requires = "render_template", overrides_with = "drop_template_fields", help_heading = heading::OUTPUT)]
+    pub drop_template_fields: bool,
+
+    /// Remove ANSI escape sequences embedded in string values of the input, such as colors added by the source.
+    #[arg(long, env = "HL_STRIP_INPUT_ANSI", overrides_with = "strip_input_ansi", help_heading = heading::OUTPUT)]
+    pub strip_input_ansi: bool,
+
+    /// Print each field on its own indented line below the line with the time, level and message.
+    #[arg(long, env = "HL_PRETTY", overrides_with_all = ["pretty", "compact"], help_heading = heading::OUTPUT)]
+    pub pretty: bool,
+
+    /// Print all fields on the same line as the message, overrides --pretty option.
+    #[arg(long, overrides_with_all = ["pretty", "compact"], help_heading = heading::OUTPUT)]
+    pub compact: bool,
+
+    /// Control when string field values are quoted in text and logfmt output, auto quotes only the values containing spaces or special characters.
+    #[arg(
+        long,
+        default_value = "auto",
+        env = "HL_FIELD_QUOTE",
+        overrides_with = "field_quote",
+        value_name = "WHEN",
+        value_enum,
+        help_heading = heading::OUTPUT
+    )]
+    pub field_quote: FieldQuoteOption,
+
+    /// Print messages in sections grouped by the value of the field with the specified key, under a header with the count,
+    /// sections follow the first occurrence of each value and messages within a section are ordered by time.
+    #[arg(
+        long,
+        overrides_with = "group_by",
+        value_name = "KEY",
+        conflicts_with_all = ["sort", "follow", "tail", "stats", "count", "dedup", "dedup_window", "context", "before_context", "after_context"],
+        help_heading = heading::OUTPUT
+    )]
+    pub group_by: Option<String>,
+
+    /// Collapse consecutive messages that differ only in numbers and identifiers into one line with a repeat count.
+    #[arg(
+        long,
+        overrides_with = "dedup",
+        conflicts_with_all = ["sort", "follow", "stats", "count", "count_by"],
+        help_heading = heading::OUTPUT
+    )]
+    pub dedup: bool,
+
+    /// Collapse messages that differ only in numbers and identifiers and occur within the specified time window
+    /// from the first of them into one line with a repeat count and time span, even if other messages come in between,
+    /// e.g. '5s' or '1m'.
+    #[arg(
+        long,
+        overrides_with = "dedup_window",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        conflicts_with_all = ["sort", "follow", "stats", "count", "count_by"],
+        help_heading = heading::OUTPUT
+    )]
+    pub dedup_window: Option<Duration>,
+
+    /// Suppress output, exit with status 1 if no messages matched or 0 otherwise.
+    #[arg(long, help_heading = heading::OUTPUT)]
+    pub quiet: bool,
+
+    /// Output format.
+    #[arg(
+        long,
+        env = "HL_OUTPUT_FORMAT",
+        default_value = "text",
+        overrides_with = "output_format",
+        value_name = "FORMAT",
+        help_heading = heading::OUTPUT
+    )]
+    pub output_format: OutputFormat,
+
+    /// Pass each message as a JSON object on its own line to the stdin of the command and print the command output instead,
+    /// the command is started once for all messages unless --exec-per-message is set.
+    ///
+    /// Messages are always passed as JSON, so --output-format cannot be used with this option.
+    #[arg(
+        long,
+        overrides_with = "exec",
+        conflicts_with = "output_format",
+        value_name = "COMMAND",
+        help_heading = heading::OUTPUT
+    )]
+    pub exec: Option<String>,
+
+    /// Start the command set by --exec option for each message separately, which is much slower.
+    #[arg(long, requires = "exec", overrides_with = "exec_per_message", help_heading = heading::OUTPUT)]
+    pub exec_per_message: bool,
+
+    /// Render messages as a table with the given comma-separated columns and a header row,
+    /// 'time' and 'level' stand for the recognized timestamp and level.
+    #[arg(long, value_delimiter = ',', value_name = "KEYS", env = "HL_COLUMNS", help_heading = heading::OUTPUT)]
+    pub columns: Vec<String>,
+
+    /// Width of the --columns other than 'time' and 'level', longer values are truncated [default: 16].
+    #[arg(long, overrides_with = "column_width", value_name = "N", help_heading = heading::OUTPUT)]
+    pub column_width: Option<usize>,
+
+    /// Print only the value of the given field of each message, without styles unless --color=always is used.
+    #[arg(long, overrides_with = "only", value_name = "KEY", help_heading = heading::OUTPUT)]
+    pub only: Option<String>,
+
+    /// Print only the text of each message, same as --only=msg.
+    #[arg(long, conflicts_with = "only", overrides_with = "only_message", help_heading = heading::OUTPUT)]
+    pub only_message: bool,
+
+    /// Skip messages without the field selected by --only or --only-message instead of printing an empty line.
+    #[arg(long, overrides_with = "skip_missing", help_heading = heading::OUTPUT)]
+    pub skip_missing: bool,
+
+    /// Add normalized 'ts' and 'level' fields in front of the others when --output-format=json is used.
+    #[arg(long, overrides_with = "normalize", help_heading = heading::OUTPUT)]
+    pub normalize: bool,
+
+    /// Keep original keys of fields renamed by 'fields.aliases' setting when --output-format=json is used.
+    #[arg(long, overrides_with = "original_keys", help_heading = heading::OUTPUT)]
+    pub original_keys: bool,
+
+    /// Emit a complete HTML document with a dark background when --output-format=html is used.
+    #[arg(long, overrides_with = "html_standalone", help_heading = heading::OUTPUT)]
+    pub html_standalone: bool,
+
+    /// Print statistics of the matching records instead of the records themselves.
+    #[arg(long, overrides_with = "stats", help_heading = heading::OUTPUT)]
+    pub stats: bool,
+
+    /// Field to group records by in statistics.
+    #[arg(
+        long,
+        default_value = "msg",
+        overrides_with = "stats_group_by",
+        value_name = "KEY",
+        help_heading = heading::OUTPUT
+    )]
+    pub stats_group_by: String,
+
+    /// Number of top groups to show in statistics.
+    #[arg(
+        long,
+        default_value_t = 10,
+        overrides_with = "stats_top",
+        value_name = "N",
+        help_heading = heading::OUTPUT
+    )]
+    pub stats_top: usize,
+
+    /// Statistics output format.
+    #[arg(
+        long,
+        default_value = "table",
+        overrides_with = "stats_format",
+        value_name = "FORMAT",
+        help_heading = heading::OUTPUT
+    )]
+    pub stats_format: StatsFormat,
+
+    /// Print the number of matching records instead of the records themselves.
+    #[arg(
+        long,
+        overrides_with = "count",
+        conflicts_with_all = ["stats", "follow", "tail", "range", "head", "skip"],
+        help_heading = heading::OUTPUT
+    )]
+    pub count: bool,
+
+    /// Break the number of matching records down by the given field, 'level' stands for the recognized level, implies --count.
+    #[arg(
+        long,
+        overrides_with = "count_by",
+        conflicts_with_all = ["stats", "follow", "tail", "range", "head", "skip"],
+        value_name = "KEY",
+        help_heading = heading::OUTPUT
+    )]
+    pub count_by: Option<String>,
+
     /// Input format.
     #[arg(
         long,
@@ -351,14 +766,88 @@ pub struct Opt {
     )]
     pub unix_timestamp_unit: UnixTimestampUnit,
 
+    /// Timestamp field keys to look for before the configured ones, the first key found in a message wins.
+    #[arg(
+        long,
+        num_args = 1,
+        value_delimiter = ',',
+        env = "HL_TIME_KEY",
+        value_name = "KEYS",
+        help_heading = heading::INPUT
+    )]
+    pub time_key: Vec<String>,
+
+    /// Message field keys to look for before the configured ones, the first key found in a message with a non-empty value wins.
+    #[arg(
+        long,
+        num_args = 1,
+        value_delimiter = ',',
+        env = "HL_MESSAGE_KEY",
+        value_name = "KEYS",
+        help_heading = heading::INPUT
+    )]
+    pub message_key: Vec<String>,
+
+    /// Report lines that cannot be parsed and fail, or just warn about them with --strict=warn.
+    #[arg(
+        long,
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "error",
+        value_name = "MODE",
+        value_enum,
+        env = "HL_STRICT",
+        help_heading = heading::INPUT
+    )]
+    pub strict: Option<StrictOption>,
+
     /// Allow non-JSON prefixes before JSON messages.
     #[arg(long, env = "HL_ALLOW_PREFIX", overrides_with = "allow_prefix", help_heading = heading::INPUT)]
     pub allow_prefix: bool,
 
-    /// Log message delimiter, [NUL, CR, LF, CRLF] or any custom string.
+    /// Log message delimiter, [NUL, CR, LF, CRLF] or any custom string with optional escape sequences, e.g. '\0' or '\x1e'.
     #[arg(long, overrides_with = "delimiter", help_heading = heading::INPUT)]
     pub delimiter: Option<String>,
 
+    /// Input framing, either lines separated by the delimiter or length-prefixed frames.
+    ///
+    /// With length-prefixed framing, each record is read as a length header followed by exactly that many bytes,
+    /// so records may contain embedded newlines and --delimiter is ignored.
+    #[arg(
+        long,
+        default_value = "lines",
+        overrides_with = "framing",
+        value_name = "FRAMING",
+        value_enum,
+        env = "HL_FRAMING",
+        help_heading = heading::INPUT
+    )]
+    pub framing: Framing,
+
+    /// Encoding of the frame length header used with --framing=length-prefixed.
+    #[arg(
+        long,
+        default_value = "u32-be",
+        overrides_with = "frame_header",
+        value_name = "ENCODING",
+        value_enum,
+        env = "HL_FRAME_HEADER",
+        help_heading = heading::INPUT
+    )]
+    pub frame_header: FrameHeader,
+
+    /// Input decompression, detected by the leading magic bytes in auto mode regardless of the file names.
+    #[arg(
+        long,
+        default_value = "auto",
+        overrides_with = "decompress",
+        value_name = "FORMAT",
+        value_enum,
+        env = "HL_DECOMPRESS",
+        help_heading = heading::INPUT
+    )]
+    pub decompress: Decompress,
+
     /// Number of interrupts to ignore, i.e. Ctrl-C (SIGINT).
     #[arg(
         long,
@@ -429,10 +918,34 @@ pub struct Opt {
     ]
     pub list_themes: Option<Option<ThemeTagSet>>,
 
+    /// Print sample messages formatted using the given theme and exit.
+    #[arg(long, value_name = "THEME", overrides_with = "preview_theme", help_heading = heading::ADVANCED)]
+    pub preview_theme: Option<String>,
+
+    /// Index cache usage (in --sort mode): reuse if up to date, always rebuild or keep in memory only.
+    #[arg(
+        long,
+        default_value = "auto",
+        env = "HL_INDEX",
+        overrides_with = "index",
+        value_name = "MODE",
+        value_enum,
+        help_heading = heading::ADVANCED
+    )]
+    pub index: IndexOption,
+
     /// Print debug index metadata (in --sort mode) and exit.
     #[arg(long, requires = "sort", help_heading = heading::ADVANCED)]
     pub dump_index: bool,
 
+    /// Do not show progress of building indexes (in --sort mode) on stderr, which is shown only if stderr is a terminal.
+    #[arg(long, env = "HL_NO_PROGRESS", help_heading = heading::ADVANCED)]
+    pub no_progress: bool,
+
+    /// Print to stderr how each record was parsed: the detected format, the fields used as time, level and message, and the ignored or unrecognized fields.
+    #[arg(long, help_heading = heading::ADVANCED)]
+    pub debug_parse: bool,
+
     /// Print help.
     #[arg(long, default_value_t = false, action = ArgAction::SetTrue)]
     pub help: bool,
@@ -456,11 +969,108 @@ pub enum PagingOption {
     Never,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrapOption {
+    Never,
+    Word,
+    Char,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldQuoteOption {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownLevelOption {
+    Show,
+    Hide,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictOption {
+    Error,
+    Warn,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexOption {
+    Auto,
+    Rebuild,
+    None,
+}
+
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputFormat {
     Auto,
     Json,
     Logfmt,
+    Syslog,
+    Otlp,
+    Gelf,
+    Winevent,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Framing {
+    Lines,
+    LengthPrefixed,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameHeader {
+    Varint,
+    U16Be,
+    U32Be,
+    U32Le,
+    U64Be,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decompress {
+    Auto,
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl From<Decompress> for Decompression {
+    fn from(value: Decompress) -> Self {
+        match value {
+            Decompress::Auto => Self::Auto,
+            Decompress::None => Self::None,
+            Decompress::Gzip => Self::Gzip,
+            Decompress::Zstd => Self::Zstd,
+        }
+    }
+}
+
+impl From<FrameHeader> for LengthHeader {
+    fn from(value: FrameHeader) -> Self {
+        match value {
+            FrameHeader::Varint => Self::Varint,
+            FrameHeader::U16Be => Self::U16Be,
+            FrameHeader::U32Be => Self::U32Be,
+            FrameHeader::U32Le => Self::U32Le,
+            FrameHeader::U64Be => Self::U64Be,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Logfmt,
+    Html,
+    Json,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Table,
+    Json,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
@@ -485,6 +1095,16 @@ pub enum AsciiOption {
     Always,
 }
 
+impl From<ColorMode> for ColorOption {
+    fn from(value: ColorMode) -> Self {
+        match value {
+            ColorMode::Auto => Self::Auto,
+            ColorMode::Always => Self::Always,
+            ColorMode::Never => Self::Never,
+        }
+    }
+}
+
 impl From<AsciiModeOpt> for AsciiOption {
     fn from(value: AsciiModeOpt) -> Self {
         match value {
@@ -76,3 +76,47 @@ fn test_ascii_option() {
         }
     }
 }
+
+fn parses(args: &[&str]) -> bool {
+    Opt::try_parse_from(std::iter::once("hl").chain(args.iter().copied())).is_ok()
+}
+
+#[test]
+fn test_slice_conflicts() {
+    assert!(parses(&["--head", "5", "--skip", "2"]));
+    assert!(!parses(&["--head", "5", "--dedup"]));
+    assert!(!parses(&["--skip", "5", "--dedup-window", "5s"]));
+    assert!(!parses(&["--range", "1:5", "--dedup"]));
+}
+
+#[test]
+fn test_dedup_conflicts() {
+    assert!(parses(&["--dedup"]));
+    assert!(!parses(&["--dedup", "--count"]));
+    assert!(!parses(&["--dedup-window", "5s", "--count-by", "level"]));
+}
+
+#[test]
+fn test_follow_variant_conflicts() {
+    assert!(parses(&["--watch", "/var/log", "--glob", "*.log", "--tail", "5"]));
+    assert!(!parses(&["--watch", "/var/log", "--group-by", "level"]));
+    assert!(!parses(&["--watch", "/var/log", "--head", "5"]));
+    assert!(!parses(&["--listen", "tcp://127.0.0.1:5170", "--range", "1:5"]));
+    assert!(!parses(&["--listen", "tcp://127.0.0.1:5170", "--dedup"]));
+    assert!(!parses(&["--listen", "tcp://127.0.0.1:5170", "--count"]));
+}
+
+#[test]
+fn test_exec_conflicts() {
+    assert!(parses(&["--exec", "jq .", "--exec-per-message"]));
+    assert!(!parses(&["--exec", "jq .", "--output-format", "logfmt"]));
+    assert!(!parses(&["--exec", "jq .", "--output-format=text"]));
+}
+
+#[test]
+fn test_count_conflicts() {
+    assert!(parses(&["--count", "--count-by", "level"]));
+    assert!(!parses(&["--count", "-n", "10"]));
+    assert!(!parses(&["--count", "--head", "10"]));
+    assert!(!parses(&["--count-by", "level", "--range", "1:5"]));
+}
@@ -11,6 +11,7 @@ use once_cell::sync::Lazy;
 use crate::{
     appdirs::AppDirs,
     error::Result,
+    pathx,
     settings::{Settings, Source, SourceFile},
 };
 
@@ -60,10 +61,11 @@ impl Loader {
     }
 
     pub fn load(self) -> Result<Settings> {
+        let custom = self.custom()?;
         if self.no_default {
-            Settings::load(self.custom())
+            Settings::load(custom)
         } else {
-            Settings::load(self.system().chain(self.user()).chain(self.custom()))
+            Settings::load(self.system().chain(self.user()).chain(custom))
         }
     }
 
@@ -83,10 +85,11 @@ impl Loader {
             .into_iter()
     }
 
-    fn custom<'a>(&'a self) -> impl Iterator<Item = Source> + 'a {
+    fn custom(&self) -> Result<Vec<Source>> {
         self.paths
             .iter()
-            .map(|path| SourceFile::new(path).required(true).into())
+            .map(|path| Ok(SourceFile::new(pathx::expand_path(path)?).required(true).into()))
+            .collect()
     }
 
     fn config(dir: &Path) -> PathBuf {
@@ -0,0 +1,80 @@
+// std imports
+use std::collections::VecDeque;
+
+// ---
+
+const SEPARATOR: &[u8] = b"--\n";
+
+/// Tracks records surrounding the matching ones, like `grep -A`, `-B` and `-C` do.
+///
+/// Up to `before` preceding non-matching records are held back in a ring buffer until a match arrives,
+/// and up to `after` following non-matching records are written right away.
+/// Groups of records that are not adjacent are separated with a `--` line.
+#[derive(Default)]
+pub struct ContextTracker {
+    before: usize,
+    after: usize,
+    held: VecDeque<Vec<u8>>,
+    countdown: usize,
+    skipped: bool,
+    written: bool,
+}
+
+impl ContextTracker {
+    pub fn new(before: usize, after: usize) -> Self {
+        Self {
+            before,
+            after,
+            held: VecDeque::with_capacity(before),
+            ..Default::default()
+        }
+    }
+
+    /// Accounts a matching record.
+    ///
+    /// Writes the separator if needed and the held back preceding records to the buffer
+    /// and returns the buffer the matching record should be formatted into.
+    pub fn push_match<'a>(&mut self, buf: &'a mut Vec<u8>) -> &'a mut Vec<u8> {
+        if self.skipped && self.written {
+            buf.extend(SEPARATOR);
+        }
+        for record in self.held.drain(..) {
+            buf.extend(record);
+        }
+        self.skipped = false;
+        self.written = true;
+        self.countdown = self.after;
+        buf
+    }
+
+    /// Accounts a non-matching record.
+    ///
+    /// Returns the buffer the record should be formatted into,
+    /// either the output buffer if the record follows a match closely enough or a buffer to hold the record back in.
+    /// Returns `None` if the record cannot become a part of any context.
+    pub fn push_unmatched<'a>(&'a mut self, buf: &'a mut Vec<u8>) -> Option<&'a mut Vec<u8>> {
+        if self.countdown != 0 {
+            self.countdown -= 1;
+            return Some(buf);
+        }
+
+        if self.before == 0 {
+            self.skipped = true;
+            return None;
+        }
+
+        let record = if self.held.len() == self.before {
+            self.skipped = true;
+            let mut record = self.held.pop_front().unwrap();
+            record.clear();
+            record
+        } else {
+            Vec::new()
+        };
+        self.held.push_back(record);
+        self.held.back_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests;
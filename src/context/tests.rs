@@ -0,0 +1,42 @@
+use super::*;
+
+fn run(tracker: &mut ContextTracker, records: &str) -> String {
+    let mut buf = Vec::new();
+    for record in records.chars() {
+        let target = if record.is_ascii_uppercase() {
+            Some(tracker.push_match(&mut buf))
+        } else {
+            tracker.push_unmatched(&mut buf)
+        };
+        if let Some(target) = target {
+            target.push(record as u8);
+            target.push(b'\n');
+        }
+    }
+    String::from_utf8(buf).unwrap().replace('\n', " ")
+}
+
+#[test]
+fn test_context_before() {
+    let mut tracker = ContextTracker::new(2, 0);
+    assert_eq!(run(&mut tracker, "abcDeFghijKl"), "b c D e F -- i j K ");
+}
+
+#[test]
+fn test_context_after() {
+    let mut tracker = ContextTracker::new(0, 1);
+    assert_eq!(run(&mut tracker, "aBcdEFgHi"), "B c -- E F g H i ");
+}
+
+#[test]
+fn test_context_both() {
+    let mut tracker = ContextTracker::new(1, 1);
+    assert_eq!(run(&mut tracker, "abCdeFgh"), "b C d e F g ");
+    assert_eq!(run(&mut tracker, "ijK"), "-- j K ");
+}
+
+#[test]
+fn test_context_none() {
+    let mut tracker = ContextTracker::new(0, 0);
+    assert_eq!(run(&mut tracker, "AbCD"), "A -- C D ");
+}
@@ -5,6 +5,7 @@ use std::cmp::{PartialOrd, min};
 use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike};
 use chrono_tz::OffsetName;
 use enumset::{EnumSet, EnumSetType, enum_set as mask};
+use unicode_width::UnicodeWidthStr;
 
 // workspace imports
 use enumset_ext::EnumSetExt;
@@ -49,6 +50,15 @@ impl DateTimeFormatter {
         }
     }
 
+    /// Returns the number of terminal columns taken by the longest formatted timestamp.
+    pub fn max_width(&self) -> usize {
+        let mut buf = Vec::new();
+        let ts = DateTime::from_timestamp(1654041600, 999_999_999).unwrap().naive_utc();
+        let ts = DateTime::from_naive_utc_and_offset(ts, self.tz.offset_from_utc_date(&ts.date()).fix());
+        self.format(&mut buf, ts);
+        String::from_utf8_lossy(&buf).width()
+    }
+
     #[inline]
     pub fn max_length(&self) -> usize {
         let mut counter = Counter::new();
@@ -0,0 +1,185 @@
+// std imports
+use std::{collections::VecDeque, io::Write, time::Duration};
+
+// third-party imports
+use chrono::{DateTime, FixedOffset, TimeDelta};
+
+// workspace imports
+use encstr::AnyEncodedString;
+
+// local imports
+use crate::model::{RawValue, Record};
+
+// ---
+
+/// Maximum number of groups held back at once in time window mode, the oldest one is written out when it is exceeded.
+const MAX_OPEN_GROUPS: usize = 256;
+
+/// Collapses records having the same message template into a single line with a repeat count.
+///
+/// By default, only consecutive records are collapsed. The last distinct record is held back until a record
+/// with a different template arrives or [`Deduplicator::flush`] is called, so that the repeat count can be appended to it.
+///
+/// With a time window set, records having the same template are collapsed if their timestamps fall within the window
+/// starting at the first of them, even if other records come in between. Each group is written out once a record
+/// with a timestamp past its window arrives, followed by the repeat count and the time span of the repeats.
+/// Groups are written in the order they were started, and records without a timestamp or a message
+/// are not collapsed but still keep their place in that order.
+#[derive(Default)]
+pub struct Deduplicator {
+    window: Option<TimeDelta>,
+    groups: VecDeque<Group>,
+}
+
+impl Deduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time window to collapse records within, instead of collapsing consecutive records only.
+    pub fn with_window(self, window: Option<Duration>) -> Self {
+        Self {
+            window: window.map(|window| TimeDelta::from_std(window).unwrap_or(TimeDelta::MAX)),
+            ..self
+        }
+    }
+
+    /// Accounts the record.
+    ///
+    /// Returns `None` if the record repeats a pending one.
+    /// Otherwise, flushes the pending records that are complete to the buffer and returns an empty buffer
+    /// the new record should be formatted into.
+    pub fn push(&mut self, record: &Record, buf: &mut Vec<u8>) -> Option<&mut Vec<u8>> {
+        let template = record.message.map(message_template);
+        let mut ts = None;
+
+        match self.window {
+            None => {
+                if let Some(group) = self.groups.back_mut() {
+                    if template.is_some() && template == group.template {
+                        group.count += 1;
+                        return None;
+                    }
+                }
+                self.flush(buf);
+            }
+            Some(window) => {
+                ts = record.ts.as_ref().and_then(|ts| ts.parse());
+                if let Some(ts) = ts {
+                    while self.groups.front().is_some_and(|group| group.expired(ts, window)) {
+                        self.write_front(buf);
+                    }
+                    if let Some(group) = self
+                        .groups
+                        .iter_mut()
+                        .rev()
+                        .find(|group| template.is_some() && group.template == template && group.covers(ts, window))
+                    {
+                        group.count += 1;
+                        group.last = group.last.max(Some(ts));
+                        return None;
+                    }
+                }
+                if self.groups.len() >= MAX_OPEN_GROUPS {
+                    self.write_front(buf);
+                }
+            }
+        }
+
+        self.groups.push_back(Group {
+            template,
+            first: ts,
+            last: ts,
+            count: 1,
+            pending: Vec::new(),
+        });
+        self.groups.back_mut().map(|group| &mut group.pending)
+    }
+
+    /// Writes all pending records to the buffer, each followed by its repeat count if it was repeated.
+    pub fn flush(&mut self, buf: &mut Vec<u8>) {
+        while !self.groups.is_empty() {
+            self.write_front(buf);
+        }
+    }
+
+    fn write_front(&mut self, buf: &mut Vec<u8>) {
+        let Some(group) = self.groups.pop_front() else {
+            return;
+        };
+
+        buf.extend(&group.pending);
+        if group.count > 1 {
+            write!(buf, " (x{}", group.count).ok();
+            if let (Some(_), Some(first), Some(last)) = (self.window, group.first, group.last) {
+                let span = Duration::from_millis((last - first).num_milliseconds().max(0) as u64);
+                write!(buf, " in {}", humantime::format_duration(span)).ok();
+            }
+            buf.push(b')');
+        }
+        buf.push(b'\n');
+    }
+}
+
+// ---
+
+/// Records collapsed into a single line.
+struct Group {
+    template: Option<String>,
+    first: Option<DateTime<FixedOffset>>,
+    last: Option<DateTime<FixedOffset>>,
+    count: usize,
+    pending: Vec<u8>,
+}
+
+impl Group {
+    /// Tells whether the time window of the group includes the timestamp.
+    ///
+    /// Groups without a timestamp cannot include anything.
+    fn covers(&self, ts: DateTime<FixedOffset>, window: TimeDelta) -> bool {
+        self.first.is_some_and(|first| ts >= first && ts - first <= window)
+    }
+
+    /// Tells whether the time window of the group ended before the timestamp, so that nothing more can join the group.
+    fn expired(&self, ts: DateTime<FixedOffset>, window: TimeDelta) -> bool {
+        self.first.is_none_or(|first| ts - first > window)
+    }
+}
+
+// ---
+
+/// Builds a template of the message by replacing each word containing digits with `#`,
+/// so that e.g. `user 123 logged in` and `user 456 logged in` produce the same template.
+pub fn message_template(message: RawValue) -> String {
+    let text = match message {
+        RawValue::String(value) => {
+            let mut buf = Vec::new();
+            match value.decode(&mut buf) {
+                Ok(()) => String::from_utf8_lossy(&buf).into_owned(),
+                Err(_) => value.source().to_owned(),
+            }
+        }
+        _ => message.raw_str().to_owned(),
+    };
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    while let Some(start) = rest.find(char::is_alphanumeric) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len());
+        let word = &rest[..end];
+        if word.bytes().any(|b| b.is_ascii_digit()) {
+            result.push('#');
+        } else {
+            result.push_str(word);
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,101 @@
+use super::*;
+
+use crate::timestamp::Timestamp;
+use encstr::EncodedString;
+use rstest::rstest;
+
+fn record(msg: &str) -> Record<'_> {
+    Record {
+        message: Some(RawValue::String(EncodedString::raw(msg))),
+        ..Default::default()
+    }
+}
+
+#[rstest]
+#[case("user 123 logged in", "user # logged in")]
+#[case("request 95c72499d9ec failed after 0.5s", "request # failed after #.#")]
+#[case("id=a1-b2, retry #3", "id=#-#, retry ##")]
+#[case("no numbers here", "no numbers here")]
+#[case("", "")]
+fn test_message_template(#[case] message: &str, #[case] expected: &str) {
+    assert_eq!(
+        message_template(RawValue::String(EncodedString::raw(message))),
+        expected
+    );
+}
+
+#[test]
+fn test_message_template_number() {
+    assert_eq!(message_template(RawValue::Number("42")), "#");
+}
+
+#[test]
+fn test_deduplicator() {
+    let mut dedup = Deduplicator::new();
+    let mut buf = Vec::new();
+
+    for msg in [
+        "user 1 logged in",
+        "user 2 logged in",
+        "user 3 logged in",
+        "done",
+        "user 4 logged in",
+    ] {
+        if let Some(pending) = dedup.push(&record(msg), &mut buf) {
+            pending.extend(msg.as_bytes());
+        }
+    }
+    assert_eq!(std::str::from_utf8(&buf).unwrap(), "user 1 logged in (x3)\ndone\n");
+
+    dedup.flush(&mut buf);
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        "user 1 logged in (x3)\ndone\nuser 4 logged in\n"
+    );
+
+    dedup.flush(&mut buf);
+    assert_eq!(buf.len(), "user 1 logged in (x3)\ndone\nuser 4 logged in\n".len());
+}
+
+#[test]
+fn test_deduplicator_no_message() {
+    let mut dedup = Deduplicator::new();
+    let mut buf = Vec::new();
+
+    for _ in 0..2 {
+        if let Some(pending) = dedup.push(&Record::default(), &mut buf) {
+            pending.extend(b"x");
+        }
+    }
+    dedup.flush(&mut buf);
+    assert_eq!(std::str::from_utf8(&buf).unwrap(), "x\nx\n");
+}
+
+#[test]
+fn test_deduplicator_window() {
+    let mut dedup = Deduplicator::new().with_window(Some(Duration::from_secs(5)));
+    let mut buf = Vec::new();
+
+    for (ts, msg) in [
+        ("2024-01-25T19:10:20.000Z", "retry 1 failed"),
+        ("2024-01-25T19:10:21.000Z", "connecting"),
+        ("2024-01-25T19:10:22.500Z", "retry 2 failed"),
+        ("2024-01-25T19:10:24.000Z", "retry 3 failed"),
+        ("2024-01-25T19:10:26.000Z", "retry 4 failed"),
+    ] {
+        let record = Record {
+            ts: Some(Timestamp::new(ts)),
+            ..record(msg)
+        };
+        if let Some(pending) = dedup.push(&record, &mut buf) {
+            pending.extend(msg.as_bytes());
+        }
+    }
+    assert_eq!(std::str::from_utf8(&buf).unwrap(), "retry 1 failed (x3 in 4s)\n");
+
+    dedup.flush(&mut buf);
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        "retry 1 failed (x3 in 4s)\nconnecting\nretry 4 failed\n"
+    );
+}
@@ -101,6 +101,12 @@ pub enum Error {
     },
     #[error("invalid index header")]
     InvalidIndexHeader,
+    #[error("found {} invalid lines", .count.hl())]
+    InvalidLines { count: u64 },
+    #[error("no log record found")]
+    NoRecord,
+    #[error("failed to parse syslog message: {0}")]
+    SyslogParseError(&'static str),
     #[error("failed to parse json: {0}")]
     JsonParseError(#[from] serde_json::Error),
     #[error("failed to parse logfmt: {0}")]
@@ -116,6 +122,10 @@ pub enum Error {
     },
     #[error("failed to parse query:\n{0}")]
     QueryParseError(Box<pest::error::Error<crate::query::Rule>>),
+    #[error("query operator {} cannot be applied to {operand}", .op.hlq())]
+    InvalidQueryOperand { op: &'static str, operand: &'static str },
+    #[error("timestamp cannot be compared with number {}, use a time like 2024-01-01T00:00:00Z or -1h instead", .value.hlq())]
+    AmbiguousTimeComparison { value: String },
     #[error(transparent)]
     LevelParseError(#[from] level::ParseError),
     #[error(transparent)]
@@ -124,6 +134,23 @@ pub enum Error {
     ParseIntError(#[from] ParseIntError),
     #[error("failed to detect application directories")]
     AppDirs,
+    #[error("invalid delimiter {}: {reason}", .value.hlq())]
+    InvalidDelimiter { value: String, reason: &'static str },
+    #[error("invalid listen address {}: {reason}", .value.hlq())]
+    InvalidListenAddress { value: String, reason: &'static str },
+    #[error("invalid record range {}: {reason}", .value.hlq())]
+    InvalidRange { value: String, reason: &'static str },
+    #[error("invalid bookmark name {}: only letters, digits, '-', '_' and '.' are allowed", .0.hlq())]
+    InvalidBookmarkName(String),
+    #[error("environment variable {} referenced in {} is not set", .name.hl(), .value.hlq())]
+    UndefinedEnvVar { name: String, value: String },
+    #[error("invalid command {}", .0.hlq())]
+    InvalidCommand(String),
+    #[error("command {} {status}", .command.hlq())]
+    CommandFailed {
+        command: String,
+        status: std::process::ExitStatus,
+    },
 }
 
 impl Error {
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, hash::Hash};
+use std::{borrow::Cow, collections::HashMap, hash::Hash};
 use wildflower::{Pattern, WILDCARD_MANY_CHAR, WILDCARD_SINGLE_CHAR};
 
 // ---
@@ -79,9 +79,22 @@ impl<N: KeyNormalize + Default> Default for MatchOptions<N> {
 
 // ---
 
+/// Returns the key addressed by a JSON Pointer segment, i.e. with `~1` and `~0` replaced by `/` and `~`.
+pub fn pointer_segment(segment: &str) -> Cow<'_, str> {
+    if segment.contains('~') {
+        segment.replace("~1", "/").replace("~0", "~").into()
+    } else {
+        segment.into()
+    }
+}
+
+// ---
+
 #[derive(Default)]
 pub struct IncludeExcludeKeyFilter<N: KeyNormalize> {
     children: HashMap<Key, IncludeExcludeKeyFilter<N>>,
+    // children added using JSON Pointer segments, they match whole keys only
+    exact: HashMap<Key, IncludeExcludeKeyFilter<N>>,
     patterns: Vec<(Pattern<String>, IncludeExcludeKeyFilter<N>)>,
     fallback: Option<Box<IncludeExcludeKeyFilter<N>>>,
     options: MatchOptions<N>,
@@ -92,6 +105,7 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
     pub fn new(options: MatchOptions<N>) -> Self {
         Self {
             children: HashMap::new(),
+            exact: HashMap::new(),
             patterns: Vec::new(),
             fallback: None,
             options,
@@ -99,7 +113,16 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
         }
     }
 
+    /// Returns the entry for the given key, creating it if needed.
+    ///
+    /// Keys starting with `/` are treated as JSON Pointers, e.g. `/a/b`, their segments match whole keys only,
+    /// so `/a.b` addresses a field named `a.b` but not field `b` nested in field `a`.
+    /// Other keys are split by the delimiter and match both.
     pub fn entry<'a>(&'a mut self, key: &'a str) -> &'a mut IncludeExcludeKeyFilter<N> {
+        if let Some(pointer) = key.strip_prefix('/') {
+            return self.pointer_entry(pointer);
+        }
+
         let (head, tail) = self.split(key);
 
         if Self::is_pattern(&head) {
@@ -107,7 +130,14 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
         }
 
         self.set_fallback(self.setting);
-        let child = self.children.entry(head).or_insert(Self::new(self.options.clone()));
+        // a new child inherits the setting of the pattern it matches, so that the pattern still applies to it
+        let setting = self.pattern_setting(&head);
+        let options = &self.options;
+        let child = self.children.entry(head).or_insert_with(|| {
+            let mut child = Self::new(options.clone());
+            child.setting = setting;
+            child
+        });
         match tail {
             None => child,
             Some(tail) => child.entry(tail),
@@ -123,6 +153,12 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
             };
         }
 
+        if !self.exact.is_empty() {
+            if let Some(child) = self.exact.get(&self.key(key.as_bytes())) {
+                return Some(child);
+            }
+        }
+
         let (head, tail) = self.split(key);
 
         let found = |child: &'a Self| match tail {
@@ -179,18 +215,43 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
 
     #[inline(always)]
     pub fn leaf(&self) -> bool {
-        self.children.is_empty() && self.patterns.is_empty()
+        self.children.is_empty() && self.exact.is_empty() && self.patterns.is_empty()
+    }
+
+    fn pointer_entry(&mut self, pointer: &str) -> &mut IncludeExcludeKeyFilter<N> {
+        let (segment, tail) = match pointer.split_once('/') {
+            Some((segment, tail)) => (segment, Some(tail)),
+            None => (pointer, None),
+        };
+        let head = self.key(pointer_segment(segment).as_bytes());
+
+        self.set_fallback(self.setting);
+        let setting = self.pattern_setting(&head);
+        let options = &self.options;
+        let child = self.exact.entry(head).or_insert_with(|| {
+            let mut child = Self::new(options.clone());
+            child.setting = setting;
+            child
+        });
+        match tail {
+            None => child,
+            Some(tail) => child.pointer_entry(tail),
+        }
+    }
+
+    fn key(&self, bytes: &[u8]) -> Key {
+        let key = bytes.iter().map(|&x| self.options.norm.normalize(x));
+        if bytes.len() <= 64 {
+            Key::Short(key.collect())
+        } else {
+            Key::Long(key.collect())
+        }
     }
 
     fn split<'a>(&self, key: &'a str) -> (Key, Option<&'a str>) {
         let bytes = key.as_bytes();
         let n = bytes.iter().take_while(|&&x| x != self.options.delimiter).count();
-        let head = bytes[..n].iter().map(|&x| self.options.norm.normalize(x));
-        let head = if n <= 64 {
-            Key::Short(head.collect())
-        } else {
-            Key::Long(head.collect())
-        };
+        let head = self.key(&bytes[..n]);
         let tail = if n == key.len() { None } else { Some(&key[n + 1..]) };
         (head, tail)
     }
@@ -203,6 +264,7 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
     fn add_pattern<'a>(&'a mut self, key: Key, tail: Option<&'a str>) -> &'a mut IncludeExcludeKeyFilter<N> {
         let pattern = Pattern::new(key.to_string());
         self.children.retain(|k, _| !pattern.matches(k.as_str()));
+        self.exact.retain(|k, _| !pattern.matches(k.as_str()));
         let item = match self.patterns.iter().position(|(p, _)| p == &pattern) {
             Some(i) => &mut self.patterns[i].1,
             None => {
@@ -217,6 +279,16 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
         }
     }
 
+    fn pattern_setting(&self, key: &Key) -> IncludeExcludeSetting {
+        let key = key.as_str();
+        self.patterns
+            .iter()
+            .rev()
+            .find(|(pattern, _)| pattern.matches(key))
+            .map(|(_, child)| child.setting)
+            .unwrap_or_default()
+    }
+
     fn update_fallback(&mut self) {
         if self.setting == IncludeExcludeSetting::Unspecified || self.leaf() {
             self.fallback = None;
@@ -240,6 +312,7 @@ impl<N: KeyNormalize> IncludeExcludeKeyFilter<N> {
 
     fn reset(&mut self, setting: IncludeExcludeSetting) {
         self.children.clear();
+        self.exact.clear();
         self.patterns.clear();
         self.fallback = None;
         self.setting = setting;
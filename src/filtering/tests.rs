@@ -115,3 +115,56 @@ fn test_filter() {
 
     assert_eq!(filter.get("x").unwrap().setting(), IncludeExcludeSetting::Include);
 }
+
+#[test]
+fn test_filter_pointer() {
+    let mut filter = IncludeExcludeKeyFilter::new(MatchOptions::<DefaultNormalizing>::default());
+    filter.entry("/a.b").exclude();
+    filter.entry("/c/d").exclude();
+    filter.entry("/e~1f~0").exclude();
+
+    let ab = filter.get("a.b").unwrap();
+    assert_eq!(ab.setting(), IncludeExcludeSetting::Exclude);
+    assert!(filter.get("a").is_none());
+
+    let c = filter.get("c").unwrap();
+    assert_eq!(c.setting(), IncludeExcludeSetting::Unspecified);
+    let cd = c.get("d").unwrap();
+    assert_eq!(cd.setting(), IncludeExcludeSetting::Exclude);
+    assert!(filter.get("c.d").is_none());
+
+    let ef = filter.get("e/f~").unwrap();
+    assert_eq!(ef.setting(), IncludeExcludeSetting::Exclude);
+}
+
+#[test]
+fn test_pointer_segment() {
+    assert_eq!(pointer_segment("a.b"), "a.b");
+    assert_eq!(pointer_segment("a~1b~0c"), "a/b~c");
+    assert_eq!(pointer_segment("~01"), "~1");
+}
+
+#[test]
+fn test_filter_allowlist_patterns() {
+    let mut filter = IncludeExcludeKeyFilter::new(MatchOptions::<DefaultNormalizing>::default()).excluded();
+    filter.entry("req*").include();
+    filter.entry("user.id").include();
+    filter.entry("request.headers").exclude();
+
+    assert_eq!(filter.get("x").unwrap().setting(), IncludeExcludeSetting::Exclude);
+    assert_eq!(filter.get("req").unwrap().setting(), IncludeExcludeSetting::Include);
+    assert_eq!(
+        filter.get("request.method").unwrap().setting(),
+        IncludeExcludeSetting::Include
+    );
+    assert_eq!(
+        filter.get("request.headers").unwrap().setting(),
+        IncludeExcludeSetting::Exclude
+    );
+    assert_eq!(
+        filter.get("request.headers.accept").unwrap().setting(),
+        IncludeExcludeSetting::Exclude
+    );
+    assert_eq!(filter.get("user.id").unwrap().setting(), IncludeExcludeSetting::Include);
+    assert!(filter.get("user.name").is_none());
+}
@@ -1,5 +1,7 @@
 use std::cmp::min;
 
+use unicode_width::UnicodeWidthChar;
+
 // ---
 
 pub trait Push<T> {
@@ -427,5 +429,39 @@ where
     f(BufferedAligner::new(out, Padding::new(pad, width), Alignment::Center));
 }
 
+/// Writes the text produced by `f` and pads it with `pad` to the given number of terminal columns,
+/// or truncates it at a character boundary if it takes more.
+///
+/// Unlike [`aligned_left`], the width is measured in columns, so wide characters take two of them
+/// and combining characters take none. Text that is not valid UTF-8 is measured in bytes.
+pub fn aligned_left_by_width<F>(out: &mut Vec<u8>, width: usize, pad: u8, f: F)
+where
+    F: FnOnce(&mut Vec<u8>),
+{
+    let begin = out.len();
+    f(out);
+
+    let mut columns = 0;
+    let mut end = out.len();
+    match std::str::from_utf8(&out[begin..]) {
+        Ok(text) => {
+            for (i, c) in text.char_indices() {
+                let n = c.width().unwrap_or(0);
+                if columns + n > width {
+                    end = begin + i;
+                    break;
+                }
+                columns += n;
+            }
+        }
+        Err(_) => {
+            columns = min(end - begin, width);
+            end = begin + columns;
+        }
+    }
+    out.truncate(end);
+    out.extend(std::iter::repeat_n(pad, width - columns));
+}
+
 #[cfg(test)]
 mod tests;
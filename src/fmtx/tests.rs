@@ -172,3 +172,18 @@ fn test_counter_default() {
     assert_eq!(counter1.result(), counter2.result());
     assert_eq!(counter1.result(), 0);
 }
+
+#[test]
+fn test_aligned_left_by_width() {
+    let aligned = |text: &str, width: usize| {
+        let mut buf = b">".to_vec();
+        aligned_left_by_width(&mut buf, width, b'.', |buf| buf.extend(text.as_bytes()));
+        String::from_utf8(buf).unwrap()
+    };
+
+    assert_eq!(aligned("abc", 5), ">abc..");
+    assert_eq!(aligned("日本", 5), ">日本.");
+    assert_eq!(aligned("e\u{301}", 2), ">e\u{301}.");
+    assert_eq!(aligned("日本語", 5), ">日本.");
+    assert_eq!(aligned("abcdef", 3), ">abc");
+}
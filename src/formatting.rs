@@ -1,18 +1,23 @@
 // std imports
-use std::sync::Arc;
+use std::{io::Write, sync::Arc};
+
+// third-party imports
+use serde_json as json;
 
 // workspace imports
-use encstr::EncodedString;
+use encstr::{AnyEncodedString, EncodedString};
 
 // local imports
 use crate::{
     IncludeExcludeKeyFilter,
     datefmt::DateTimeFormatter,
     filtering::IncludeExcludeSetting,
-    fmtx::{OptimizedBuf, Push, aligned_left, centered},
+    fmtx::{OptimizedBuf, Push, aligned_left_by_width, centered},
     model::{self, Level, RawValue},
+    query::Query,
     settings::{AsciiMode, Formatting, ResolvedPunctuation},
-    theme::{Element, StylingPush, Theme},
+    theme::{Element, Styler, StylingPush, Theme},
+    themecfg,
 };
 
 // test imports
@@ -20,7 +25,10 @@ use crate::{
 use crate::testing::Sample;
 
 // relative imports
-use string::{DynMessageFormat, Format, ValueFormatAuto};
+use highlight::Highlighter;
+use string::{DynMessageFormat, Format, ValueFormatAuto, ValueFormatDoubleQuoted, ValueFormatRaw};
+use template::Token;
+use wrap::Wrapper;
 
 // ---
 
@@ -71,13 +79,56 @@ pub type DynRecordWithSourceFormatter = Arc<dyn RecordWithSourceFormatter + Send
 
 // ---
 
+/// Defines when string field values are quoted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FieldQuote {
+    /// Values are quoted only if they contain spaces or special characters or would be mistaken for another type.
+    #[default]
+    Auto,
+    /// Values are always quoted with double quotes and escaped.
+    Always,
+    /// Values are printed as is.
+    Never,
+}
+
+impl Format for FieldQuote {
+    #[inline]
+    fn format<'a>(&self, input: EncodedString<'a>, buf: &mut Vec<u8>) -> encstr::Result<()> {
+        match self {
+            Self::Auto => ValueFormatAuto.format(input, buf),
+            Self::Always => ValueFormatDoubleQuoted.format(input, buf),
+            Self::Never => ValueFormatRaw.format(input, buf),
+        }
+    }
+}
+
+// ---
+
+/// Style applied to the records matching the query, either to the whole record or only to one of its fields.
+#[derive(Clone)]
+pub struct StyleRule {
+    pub query: Query,
+    pub field: Option<String>,
+    pub style: themecfg::Style,
+}
+
+struct ResolvedStyleRule {
+    query: Query,
+    field: Option<String>,
+    theme: Theme,
+}
+
+// ---
+
 #[derive(Default, Clone)]
 pub struct RecordFormatterBuilder {
     theme: Option<Arc<Theme>>,
+    colors: Option<bool>,
     raw_fields: bool,
     ts_formatter: Option<DateTimeFormatter>,
     hide_empty_fields: bool,
     flatten: bool,
+    flatten_depth: Option<usize>,
     ascii: AsciiMode,
     always_show_time: bool,
     always_show_level: bool,
@@ -85,6 +136,21 @@ pub struct RecordFormatterBuilder {
     cfg: Option<Formatting>,
     punctuation: Option<Arc<ResolvedPunctuation>>,
     message_format: Option<DynMessageFormat>,
+    highlighter: Option<Arc<Highlighter>>,
+    pinned_fields: Vec<String>,
+    color_by: Option<String>,
+    wrapper: Option<Wrapper>,
+    max_message_width: Option<usize>,
+    max_field_width: Option<usize>,
+    error_key: Option<String>,
+    message_keys: Vec<String>,
+    expand_json: bool,
+    rules: Vec<StyleRule>,
+    render_template: bool,
+    drop_template_fields: bool,
+    strip_ansi: bool,
+    pretty: bool,
+    field_quote: FieldQuote,
 }
 
 impl RecordFormatterBuilder {
@@ -110,6 +176,12 @@ impl RecordFormatterBuilder {
         Self { flatten, ..self }
     }
 
+    /// Limits flattening to the given depth of nested objects.
+    /// Objects nested deeper are rendered as compact inline JSON.
+    pub fn with_flatten_depth(self, flatten_depth: Option<usize>) -> Self {
+        Self { flatten_depth, ..self }
+    }
+
     pub fn with_ascii(self, ascii: AsciiMode) -> Self {
         Self { ascii, ..self }
     }
@@ -135,6 +207,14 @@ impl RecordFormatterBuilder {
         }
     }
 
+    /// Enables or disables styling regardless of the theme, when disabled the output contains no escape sequences.
+    pub fn with_colors(self, enabled: bool) -> Self {
+        Self {
+            colors: Some(enabled),
+            ..self
+        }
+    }
+
     pub fn with_timestamp_formatter(self, value: DateTimeFormatter) -> Self {
         Self {
             ts_formatter: Some(value),
@@ -170,21 +250,137 @@ impl RecordFormatterBuilder {
         }
     }
 
+    /// Highlights fragments of messages and string values matched by the highlighter.
+    pub fn with_highlighter(self, highlighter: Option<Arc<Highlighter>>) -> Self {
+        Self { highlighter, ..self }
+    }
+
+    /// Shows fields with the given keys first, in the given order.
+    pub fn with_pinned_fields(self, pinned_fields: Vec<String>) -> Self {
+        Self { pinned_fields, ..self }
+    }
+
+    /// Starts each message with a marker tinted by the value of the field with the given key.
+    pub fn with_color_by(self, color_by: Option<String>) -> Self {
+        Self { color_by, ..self }
+    }
+
+    /// Wraps long lines, aligning continuation lines with the message.
+    pub fn with_wrapper(self, wrapper: Option<Wrapper>) -> Self {
+        Self { wrapper, ..self }
+    }
+
+    /// Truncates messages longer than the given number of columns.
+    pub fn with_max_message_width(self, max_message_width: Option<usize>) -> Self {
+        Self {
+            max_message_width,
+            ..self
+        }
+    }
+
+    /// Truncates string field values longer than the given number of columns.
+    pub fn with_max_field_width(self, max_field_width: Option<usize>) -> Self {
+        Self {
+            max_field_width,
+            ..self
+        }
+    }
+
+    /// Sets the key of the field rendered as a structured error, with its stack trace and causes below the record.
+    pub fn with_error_key(self, error_key: Option<String>) -> Self {
+        Self { error_key, ..self }
+    }
+
+    /// Sets the names of the message field, the one found in the record is used as the key of a non-text message.
+    pub fn with_message_keys(self, message_keys: Vec<String>) -> Self {
+        Self { message_keys, ..self }
+    }
+
+    /// Renders string values containing a JSON object or array as nested structures.
+    pub fn with_json_expansion(self, enabled: bool) -> Self {
+        Self {
+            expand_json: enabled,
+            ..self
+        }
+    }
+
+    /// Sets the style rules, only the first rule matching a record is applied to it.
+    pub fn with_rules(self, rules: Vec<StyleRule>) -> Self {
+        Self { rules, ..self }
+    }
+
+    /// Substitutes named placeholders in the message, e.g. `{UserId}`, with the values of the fields.
+    pub fn with_template_rendering(self, enabled: bool) -> Self {
+        Self {
+            render_template: enabled,
+            ..self
+        }
+    }
+
+    /// Hides the fields substituted into the message by template rendering.
+    pub fn with_template_fields_dropping(self, enabled: bool) -> Self {
+        Self {
+            drop_template_fields: enabled,
+            ..self
+        }
+    }
+
+    /// Removes ANSI escape sequences found in string values of the input.
+    pub fn with_ansi_stripping(self, enabled: bool) -> Self {
+        Self {
+            strip_ansi: enabled,
+            ..self
+        }
+    }
+
+    /// Places each field on its own indented line below the line with the time, level and message.
+    pub fn with_pretty_layout(self, enabled: bool) -> Self {
+        Self {
+            pretty: enabled,
+            ..self
+        }
+    }
+
+    pub fn with_field_quote(self, value: FieldQuote) -> Self {
+        Self {
+            field_quote: value,
+            ..self
+        }
+    }
+
     pub fn build(self) -> RecordFormatter {
         let cfg = self.cfg.unwrap_or_default();
         let punctuation = self
             .punctuation
             .unwrap_or_else(|| cfg.punctuation.resolve(self.ascii).into());
         let ts_formatter = self.ts_formatter.unwrap_or_default();
-        let ts_width = ts_formatter.max_length();
+        let ts_width = ts_formatter.max_width();
+
+        let theme = match self.colors {
+            Some(false) => Default::default(),
+            _ => self.theme.unwrap_or_default(),
+        };
+        let rules = match self.colors {
+            Some(false) => Vec::new(),
+            _ => self
+                .rules
+                .into_iter()
+                .map(|rule| ResolvedStyleRule {
+                    theme: theme.patched(&rule.style),
+                    query: rule.query,
+                    field: rule.field,
+                })
+                .collect(),
+        };
 
         RecordFormatter {
-            theme: self.theme.unwrap_or_default(),
+            theme,
             unescape_fields: !self.raw_fields,
             ts_formatter,
             ts_width,
             hide_empty_fields: self.hide_empty_fields,
             flatten: self.flatten,
+            flatten_depth: self.flatten_depth,
             always_show_time: self.always_show_time,
             always_show_level: self.always_show_level,
             fields: self.fields.unwrap_or_default(),
@@ -192,6 +388,21 @@ impl RecordFormatterBuilder {
                 .message_format
                 .unwrap_or_else(|| DynMessageFormat::new(&cfg, self.ascii)),
             punctuation,
+            highlighter: self.highlighter,
+            pinned_fields: self.pinned_fields,
+            color_by: self.color_by,
+            wrapper: self.wrapper,
+            max_message_width: self.max_message_width,
+            max_field_width: self.max_field_width,
+            error_key: self.error_key,
+            message_keys: self.message_keys,
+            expand_json: self.expand_json,
+            rules,
+            render_template: self.render_template,
+            drop_template_fields: self.drop_template_fields,
+            strip_ansi: self.strip_ansi,
+            pretty: self.pretty,
+            field_quote: self.field_quote,
         }
     }
 }
@@ -213,18 +424,74 @@ pub struct RecordFormatter {
     ts_width: usize,
     hide_empty_fields: bool,
     flatten: bool,
+    flatten_depth: Option<usize>,
     always_show_time: bool,
     always_show_level: bool,
     fields: Arc<IncludeExcludeKeyFilter>,
     message_format: DynMessageFormat,
     punctuation: Arc<ResolvedPunctuation>,
+    highlighter: Option<Arc<Highlighter>>,
+    pinned_fields: Vec<String>,
+    color_by: Option<String>,
+    wrapper: Option<Wrapper>,
+    max_message_width: Option<usize>,
+    max_field_width: Option<usize>,
+    error_key: Option<String>,
+    message_keys: Vec<String>,
+    expand_json: bool,
+    rules: Vec<ResolvedStyleRule>,
+    render_template: bool,
+    drop_template_fields: bool,
+    strip_ansi: bool,
+    pretty: bool,
+    field_quote: FieldQuote,
 }
 
 impl RecordFormatter {
+    /// Formats the record and writes it to the output, without a trailing line break.
+    pub fn format<W: Write + ?Sized>(&self, rec: &model::Record, out: &mut W) -> std::io::Result<()> {
+        let mut buf = Buf::new();
+        self.format_record(&mut buf, rec);
+        out.write_all(&buf)
+    }
+
     pub fn format_record(&self, buf: &mut Buf, rec: &model::Record) {
-        let mut fs = FormattingState::new(self.flatten && self.unescape_fields);
+        let mut fs = FormattingState::new(
+            self.flatten && self.unescape_fields,
+            self.pretty && self.unescape_fields,
+        );
+        let begin = buf.len();
+        let mut indent_end = begin;
+
+        let rule = self.rules.iter().find(|rule| rule.query.matches(rec));
+        let (theme, field_rule) = match rule {
+            Some(rule) if rule.field.is_none() => (&rule.theme, None),
+            _ => (&*self.theme, rule),
+        };
 
-        self.theme.apply(buf, &rec.level, |s| {
+        let mut decoded = Vec::new();
+        let template = self.message_template(rec, &mut decoded);
+        let substituted = |key: &str| {
+            self.drop_template_fields
+                && template.is_some_and(|template| {
+                    template::tokens(template).any(|token| match token {
+                        Token::Placeholder { name, .. } => name == key,
+                        Token::Text(_) => false,
+                    })
+                })
+        };
+
+        theme.apply(buf, &rec.level, |s| {
+            //
+            // color-by marker
+            //
+            if let Some(key) = &self.color_by {
+                let marker = &self.punctuation.color_by_marker;
+                match rec.get(key) {
+                    Some(value) => s.tinted(value.raw_str().as_bytes(), |buf| buf.extend(marker.as_bytes())),
+                    None => s.batch(|buf| buf.extend(std::iter::repeat_n(b' ', marker.chars().count()))),
+                }
+            }
             //
             // time
             //
@@ -232,14 +499,14 @@ impl RecordFormatter {
                 fs.add_element(|| {});
                 s.element(Element::Time, |s| {
                     s.batch(|buf| {
-                        aligned_left(buf, self.ts_width, b' ', |mut buf| {
+                        aligned_left_by_width(buf, self.ts_width, b' ', |buf| {
                             if ts
                                 .as_rfc3339()
-                                .and_then(|ts| self.ts_formatter.reformat_rfc3339(&mut buf, ts))
+                                .and_then(|ts| self.ts_formatter.reformat_rfc3339(buf, ts))
                                 .is_none()
                             {
                                 if let Some(ts) = ts.parse() {
-                                    self.ts_formatter.format(&mut buf, ts);
+                                    self.ts_formatter.format(buf, ts);
                                 } else {
                                     buf.extend_from_slice(ts.raw().as_bytes());
                                 }
@@ -280,6 +547,9 @@ impl RecordFormatter {
                     s.batch(|buf| buf.extend_from_slice(self.punctuation.level_right_separator.as_bytes()));
                 });
             }
+            if self.wrapper.is_some() {
+                s.batch(|buf| indent_end = buf.len());
+            }
 
             //
             // logger
@@ -296,54 +566,108 @@ impl RecordFormatter {
             //
             // message text
             //
-            if let Some(value) = &rec.message {
-                self.format_message(s, &mut fs, *value);
+            if let Some(template) = template {
+                self.format_message_template(s, &mut fs, rec, template);
+            } else if let Some(value) = &rec.message {
+                self.format_message(s, &mut fs, self.message_key(rec), *value);
             } else {
                 s.reset();
             }
             //
+            // caller, kept on the first line if fields follow on their own lines
+            //
+            if fs.pretty {
+                self.format_caller(s, &rec.caller);
+            }
+            //
             // fields
             //
             let mut some_fields_hidden = false;
+            let mut error = None;
+            for pinned in &self.pinned_fields {
+                if let Some((k, v)) = rec.fields().find(|(k, _)| *k == pinned.as_str()) {
+                    if (!self.hide_empty_fields || !v.is_empty()) && !substituted(k) {
+                        some_fields_hidden |= !self.format_field_with_rule(s, k, *v, &mut fs, &mut error, field_rule);
+                    }
+                }
+            }
             for (k, v) in rec.fields() {
+                if self.pinned_fields.iter().any(|pinned| pinned == k) || substituted(k) {
+                    continue;
+                }
                 if !self.hide_empty_fields || !v.is_empty() {
-                    some_fields_hidden |= !self.format_field(s, k, *v, &mut fs, Some(&self.fields));
+                    some_fields_hidden |= !self.format_field_with_rule(s, k, *v, &mut fs, &mut error, field_rule);
                 }
             }
             if some_fields_hidden || (fs.some_nested_fields_hidden && fs.flatten) {
-                s.element(Element::Ellipsis, |s| {
-                    s.batch(|buf| buf.extend_from_slice(self.punctuation.hidden_fields_indicator.as_bytes()))
-                });
+                self.format_hidden_fields_indicator(s, &fs);
             }
             //
             // caller
             //
-            if !rec.caller.is_empty() {
-                let caller = rec.caller;
-                s.element(Element::Caller, |s| {
-                    s.batch(|buf| {
-                        buf.push(b' ');
-                        buf.extend(self.punctuation.source_location_separator.as_bytes())
-                    });
-                    s.element(Element::CallerInner, |s| {
-                        s.batch(|buf| {
-                            if !caller.name.is_empty() {
-                                buf.extend(caller.name.as_bytes());
-                            }
-                            if !caller.file.is_empty() || !caller.line.is_empty() {
-                                if !caller.name.is_empty() {
-                                    buf.extend(self.punctuation.caller_name_file_separator.as_bytes());
-                                }
-                                buf.extend(caller.file.as_bytes());
-                                if !caller.line.is_empty() {
-                                    buf.push(b':');
-                                    buf.extend(caller.line.as_bytes());
-                                }
-                            }
-                        });
-                    });
-                });
+            if !fs.pretty {
+                self.format_caller(s, &rec.caller);
+            }
+            //
+            // error details
+            //
+            if let Some(error) = error {
+                self.format_error_details(s, error);
+            }
+        });
+
+        if let Some(wrapper) = &self.wrapper {
+            let text = buf.split_off(begin);
+            let indent = match wrap::display_width(&text[..indent_end - begin]) {
+                0 => 0,
+                width => width + 1,
             };
+            wrapper.wrap(&text, indent, buf);
+        }
+    }
+
+    fn format_caller<S: StylingPush<Buf>>(&self, s: &mut S, caller: &model::Caller) {
+        if caller.is_empty() {
+            return;
+        }
+
+        s.element(Element::Caller, |s| {
+            s.batch(|buf| {
+                buf.push(b' ');
+                buf.extend(self.punctuation.source_location_separator.as_bytes())
+            });
+            s.element(Element::CallerInner, |s| {
+                s.batch(|buf| {
+                    if !caller.name.is_empty() {
+                        buf.extend(caller.name.as_bytes());
+                    }
+                    if !caller.file.is_empty() || !caller.line.is_empty() {
+                        if !caller.name.is_empty() {
+                            buf.extend(self.punctuation.caller_name_file_separator.as_bytes());
+                        }
+                        buf.extend(caller.file.as_bytes());
+                        if !caller.line.is_empty() {
+                            buf.push(b':');
+                            buf.extend(caller.line.as_bytes());
+                        }
+                    }
+                });
+            });
+        });
+    }
+
+    /// Writes the indicator of hidden fields, on its own line in pretty layout.
+    fn format_hidden_fields_indicator<S: StylingPush<Buf>>(&self, s: &mut S, fs: &FormattingState) {
+        let indicator = &self.punctuation.hidden_fields_indicator;
+        s.element(Element::Ellipsis, |s| {
+            s.batch(|buf| {
+                if fs.pretty {
+                    fs.new_line(buf);
+                    buf.extend(indicator.trim_start().as_bytes());
+                } else {
+                    buf.extend(indicator.as_bytes());
+                }
+            })
         });
     }
 
@@ -360,23 +684,329 @@ impl RecordFormatter {
         fv.format(s, key, value, fs, filter, IncludeExcludeSetting::Unspecified)
     }
 
+    /// Formats the field, or only the message of the error if the field is the structured error field.
+    ///
+    /// The error is stored to render its details after the rest of the record.
+    fn format_field_or_error<'a, S: StylingPush<Buf>>(
+        &self,
+        s: &mut S,
+        key: &str,
+        value: RawValue<'a>,
+        fs: &mut FormattingState,
+        error: &mut Option<ErrorValue<'a>>,
+    ) -> bool {
+        if error.is_none() && self.error_key.as_deref() == Some(key) {
+            if let Some(value) = ErrorValue::parse(value) {
+                let visible = match value.message {
+                    Some(message) => self.format_field(s, key, message, fs, Some(&self.fields)),
+                    None => !self.is_hidden(key),
+                };
+                if visible {
+                    *error = Some(value);
+                }
+                return visible;
+            }
+        }
+        self.format_field(s, key, value, fs, Some(&self.fields))
+    }
+
+    /// Formats the field using the theme of the rule if the rule targets this field.
+    fn format_field_with_rule<'a, 't>(
+        &'t self,
+        s: &mut Styler<'t, Buf>,
+        key: &str,
+        value: RawValue<'a>,
+        fs: &mut FormattingState,
+        error: &mut Option<ErrorValue<'a>>,
+        rule: Option<&'t ResolvedStyleRule>,
+    ) -> bool {
+        match rule {
+            Some(rule) if rule.field.as_deref() == Some(key) => {
+                s.restyled(&rule.theme, |s| self.format_field_or_error(s, key, value, fs, error))
+            }
+            _ => self.format_field_or_error(s, key, value, fs, error),
+        }
+    }
+
+    fn is_hidden(&self, key: &str) -> bool {
+        let setting = IncludeExcludeSetting::Unspecified.apply(self.fields.setting());
+        let (setting, leaf) = match self.fields.get(key) {
+            Some(filter) => (setting.apply(filter.setting()), filter.leaf()),
+            None => (setting, true),
+        };
+        setting == IncludeExcludeSetting::Exclude && leaf
+    }
+
+    /// Writes the stack trace of the error and its causes as indented lines following the record.
+    fn format_error_details<S: StylingPush<Buf>>(&self, s: &mut S, error: ErrorValue) {
+        const MAX_DEPTH: usize = 16;
+
+        let mut error = Some(error);
+        for _ in 0..MAX_DEPTH {
+            let Some(current) = error.take() else {
+                break;
+            };
+            if let Some(stack) = current.stack {
+                for line in ErrorValue::stack_lines(stack) {
+                    s.reset();
+                    s.batch(|buf| buf.extend(b"\n    "));
+                    s.element(Element::Caller, |s| s.batch(|buf| buf.extend(line.trim_ascii())));
+                }
+            }
+            let Some(cause) = current.cause else {
+                break;
+            };
+            let message = match ErrorValue::parse(cause) {
+                Some(cause) => {
+                    let message = cause.message;
+                    error = Some(cause);
+                    message
+                }
+                None => Some(cause),
+            };
+            s.reset();
+            s.batch(|buf| buf.extend(b"\n  "));
+            s.element(Element::Caller, |s| s.batch(|buf| buf.extend(b"caused by:")));
+            if let Some(message) = message {
+                s.batch(|buf| buf.push(b' '));
+                s.element(Element::Message, |s| s.batch(|buf| ErrorValue::text(message, buf)));
+            }
+        }
+    }
+
     #[inline]
-    fn format_message<'a, S: StylingPush<Buf>>(&self, s: &mut S, fs: &mut FormattingState, value: RawValue<'a>) {
+    fn format_message<'a, S: StylingPush<Buf>>(
+        &self,
+        s: &mut S,
+        fs: &mut FormattingState,
+        key: &str,
+        value: RawValue<'a>,
+    ) {
+        let mut expanded = Vec::new();
+        if let RawValue::String(value) = value {
+            if let Some(value) = self.expanded_json(value, fs, &mut expanded) {
+                fs.expansion_depth += 1;
+                self.format_field(s, key, value, fs, Some(self.fields.as_ref()));
+                fs.expansion_depth -= 1;
+                return;
+            }
+        }
+
         match value {
             RawValue::String(value) => {
+                let mut stripped = String::new();
+                let value = self.stripped(value, &mut stripped);
                 if !value.is_empty() {
                     fs.add_element(|| {
                         s.reset();
                         s.space();
                     });
                     s.element(Element::Message, |s| {
-                        s.batch(|buf| self.message_format.format(value, buf).unwrap())
+                        self.truncated(s, self.max_message_width, |buf| {
+                            self.message_format.format(value, buf).unwrap()
+                        })
                     });
                 }
                 false
             }
-            _ => self.format_field(s, "msg", value, fs, Some(self.fields.as_ref())),
+            _ => self.format_field(s, key, value, fs, Some(self.fields.as_ref())),
+        };
+    }
+
+    /// Returns the key of the message field found in the record, or the first configured name if it is not known.
+    #[inline]
+    fn message_key<'a>(&'a self, rec: &model::Record<'a>) -> &'a str {
+        rec.predefined
+            .iter()
+            .map(|&(key, _)| key)
+            .find(|key| self.message_keys.iter().any(|name| name == key))
+            .or_else(|| self.message_keys.first().map(String::as_str))
+            .unwrap_or("msg")
+    }
+
+    /// Returns the string without ANSI escape sequences if stripping is enabled and the string contains any,
+    /// the buffer holds the resulting text.
+    fn stripped<'b>(&self, value: EncodedString<'b>, buf: &'b mut String) -> EncodedString<'b> {
+        if !self.strip_ansi || !ansi::may_contain_sequences(value.source()) {
+            return value;
+        }
+
+        let mut decoded = Vec::new();
+        if value.decode(&mut decoded).is_err() {
+            return value;
+        }
+        let Ok(decoded) = std::str::from_utf8(&decoded) else {
+            return value;
+        };
+        ansi::strip(decoded, buf);
+        EncodedString::raw(buf)
+    }
+
+    /// Decodes the message into the buffer and returns it if template rendering is enabled
+    /// and the message is a string containing placeholders.
+    fn message_template<'b>(&self, rec: &model::Record, buf: &'b mut Vec<u8>) -> Option<&'b str> {
+        if !self.render_template || !self.unescape_fields {
+            return None;
+        }
+        let Some(RawValue::String(value)) = rec.message else {
+            return None;
         };
+        if !value.source().contains('{') {
+            return None;
+        }
+
+        value.decode(buf).ok()?;
+        if self.strip_ansi && ansi::may_contain_sequences(value.source()) {
+            let mut stripped = String::new();
+            ansi::strip(std::str::from_utf8(buf).ok()?, &mut stripped);
+            *buf = stripped.into_bytes();
+        }
+        let text = std::str::from_utf8(buf).ok()?;
+        template::has_placeholders(text).then_some(text)
+    }
+
+    /// Writes the message template with placeholders substituted by the values of the fields, styled by value type.
+    ///
+    /// Placeholders not matching any field are kept as is.
+    fn format_message_template<S: StylingPush<Buf>>(
+        &self,
+        s: &mut S,
+        fs: &mut FormattingState,
+        rec: &model::Record,
+        template: &str,
+    ) {
+        fs.add_element(|| {
+            s.reset();
+            s.space();
+        });
+        s.element(Element::Message, |s| {
+            for token in template::tokens(template) {
+                let (source, name) = match token {
+                    Token::Text(text) => {
+                        self.highlighted(s, |buf| buf.extend(text.as_bytes()));
+                        continue;
+                    }
+                    Token::Placeholder { source, name } => (source, name),
+                };
+                let value = rec.fields().find(|(k, _)| *k == name).map(|(_, v)| *v);
+                match value {
+                    Some(RawValue::String(value)) => s.element(Element::String, |s| {
+                        let mut stripped = String::new();
+                        let value = self.stripped(value, &mut stripped);
+                        self.highlighted(s, |buf| {
+                            let begin = buf.len();
+                            if value.decode(buf).is_err() {
+                                buf.truncate(begin);
+                                buf.extend(value.source().as_bytes());
+                            }
+                        })
+                    }),
+                    Some(RawValue::Number(value)) => {
+                        s.element(Element::Number, |s| s.batch(|buf| buf.extend(value.as_bytes())))
+                    }
+                    Some(RawValue::Boolean(true)) => {
+                        s.element(Element::BooleanTrue, |s| s.batch(|buf| buf.extend(b"true")))
+                    }
+                    Some(RawValue::Boolean(false)) => {
+                        s.element(Element::BooleanFalse, |s| s.batch(|buf| buf.extend(b"false")))
+                    }
+                    Some(RawValue::Null) => s.element(Element::Null, |s| s.batch(|buf| buf.extend(b"null"))),
+                    Some(RawValue::Object(value)) => {
+                        s.element(Element::Object, |s| s.batch(|buf| compact_json(value.get(), buf)))
+                    }
+                    Some(RawValue::Array(value)) => {
+                        s.element(Element::Array, |s| s.batch(|buf| compact_json(value.get(), buf)))
+                    }
+                    None => self.highlighted(s, |buf| buf.extend(source.as_bytes())),
+                }
+            }
+        });
+    }
+
+    /// Decodes the string into the buffer and returns it as an object or an array
+    /// if JSON expansion is enabled, its depth limit is not reached and the string contains a valid JSON object or array.
+    fn expanded_json<'b>(
+        &self,
+        value: EncodedString,
+        fs: &FormattingState,
+        buf: &'b mut Vec<u8>,
+    ) -> Option<RawValue<'b>> {
+        const MAX_DEPTH: usize = 4;
+
+        if !self.expand_json || fs.expansion_depth >= MAX_DEPTH {
+            return None;
+        }
+        // cheap check to avoid decoding strings that cannot contain an object or an array
+        if !value
+            .source()
+            .trim_start_matches('"')
+            .trim_ascii_start()
+            .starts_with(['{', '['])
+        {
+            return None;
+        }
+
+        value.decode(buf).ok()?;
+        let text = std::str::from_utf8(buf).ok()?.trim_ascii();
+        if !matches!(text.as_bytes(), [b'{', .., b'}'] | [b'[', .., b']']) {
+            return None;
+        }
+        let raw: &json::value::RawValue = json::from_str(text).ok()?;
+        Some(RawValue::from(raw))
+    }
+
+    /// Writes the text produced by `f` like [`Self::highlighted`] does, truncating it to the given number of columns.
+    #[inline]
+    fn truncated<S: StylingPush<Buf>, F: FnOnce(&mut Buf)>(&self, s: &mut S, width: Option<usize>, f: F) {
+        let Some(width) = width else {
+            return self.highlighted(s, f);
+        };
+
+        let mut truncated = false;
+        self.highlighted(s, |buf| {
+            let begin = buf.len();
+            f(buf);
+            truncated = wrap::truncate(buf, begin, width);
+        });
+
+        if truncated {
+            s.element(Element::Ellipsis, |s| {
+                s.batch(|buf| buf.extend(self.punctuation.truncation_indicator.as_bytes()))
+            });
+        }
+    }
+
+    /// Writes the text produced by `f`, highlighting fragments matched by the highlighter if any.
+    #[inline]
+    fn highlighted<S: StylingPush<Buf>, F: FnOnce(&mut Buf)>(&self, s: &mut S, f: F) {
+        let Some(highlighter) = &self.highlighter else {
+            return s.batch(f);
+        };
+
+        let mut text = None;
+        s.batch(|buf| {
+            let begin = buf.len();
+            f(buf);
+            if highlighter.is_match(&buf[begin..]) {
+                text = Some(buf.split_off(begin));
+            }
+        });
+
+        if let Some(text) = text {
+            let mut pos = 0;
+            for range in highlighter.find_iter(&text) {
+                if range.start > pos {
+                    s.batch(|buf| buf.extend_from_slice(&text[pos..range.start]));
+                }
+                s.element(Element::Match, |s| {
+                    s.batch(|buf| buf.extend_from_slice(&text[range.clone()]))
+                });
+                pos = range.end;
+            }
+            if pos < text.len() {
+                s.batch(|buf| buf.extend_from_slice(&text[pos..]));
+            }
+        }
     }
 }
 
@@ -389,26 +1019,110 @@ impl RecordWithSourceFormatter for RecordFormatter {
 
 // ---
 
+/// Structured error object, e.g. `{"message":"...","stack":"...","cause":{...}}`.
+struct ErrorValue<'a> {
+    message: Option<RawValue<'a>>,
+    stack: Option<RawValue<'a>>,
+    cause: Option<RawValue<'a>>,
+}
+
+impl<'a> ErrorValue<'a> {
+    fn parse(value: RawValue<'a>) -> Option<Self> {
+        let RawValue::Object(raw) = value else {
+            return None;
+        };
+        let mut object = model::Object::default();
+        raw.parse_into(&mut object).ok()?;
+
+        let mut result = Self {
+            message: None,
+            stack: None,
+            cause: None,
+        };
+        for (k, v) in object.fields.iter() {
+            match *k {
+                "message" | "msg" => result.message = Some(*v),
+                "stack" | "stacktrace" | "stack_trace" => result.stack = Some(*v),
+                "cause" | "inner" => result.cause = Some(*v),
+                _ => {}
+            }
+        }
+
+        (result.message.is_some() || result.stack.is_some()).then_some(result)
+    }
+
+    /// Returns non-empty lines of the stack trace given either as a multi-line string or as an array of strings.
+    fn stack_lines(stack: RawValue<'a>) -> Vec<Vec<u8>> {
+        let mut lines = Vec::new();
+        let mut add = |value: RawValue| {
+            let mut buf = Vec::new();
+            Self::text(value, &mut buf);
+            lines.extend(
+                buf.split(|&b| b == b'\n')
+                    .filter(|line| !line.trim_ascii().is_empty())
+                    .map(|line| line.to_vec()),
+            );
+        };
+        match stack {
+            RawValue::Array(array) => {
+                if let Ok(items) = array.parse::<32>() {
+                    items.iter().for_each(|item| add(*item));
+                }
+            }
+            value => add(value),
+        }
+        lines
+    }
+
+    /// Writes the decoded text of a string value, or the raw text of any other value.
+    fn text(value: RawValue, buf: &mut Buf) {
+        if let RawValue::String(value) = value {
+            let begin = buf.len();
+            if value.decode(buf).is_ok() {
+                return;
+            }
+            buf.truncate(begin);
+        }
+        buf.extend(value.raw_str().as_bytes());
+    }
+}
+
+// ---
+
 struct FormattingState {
     key_prefix: KeyPrefix,
     flatten: bool,
+    depth: usize,
     empty: bool,
     some_nested_fields_hidden: bool,
     has_fields: bool,
+    expansion_depth: usize,
+    pretty: bool,
+    indent: usize,
 }
 
 impl FormattingState {
     #[inline]
-    fn new(flatten: bool) -> Self {
+    fn new(flatten: bool, pretty: bool) -> Self {
         Self {
             key_prefix: KeyPrefix::default(),
             flatten,
+            depth: 0,
             empty: true,
             some_nested_fields_hidden: false,
             has_fields: false,
+            expansion_depth: 0,
+            pretty,
+            indent: 1,
         }
     }
 
+    /// Starts a new line indented according to the nesting level, used in pretty layout.
+    fn new_line(&self, buf: &mut Buf) {
+        buf.push(b'\n');
+        buf.extend(std::iter::repeat_n(b' ', self.indent * 2));
+    }
+
     fn add_element(&mut self, add_space: impl FnOnce()) {
         if self.empty {
             self.empty = false;
@@ -495,7 +1209,13 @@ impl<'a> FieldFormatter<'a> {
         }
         let ffv = self.begin(s, key, value, fs);
         if self.rf.unescape_fields {
-            self.format_value(s, value, fs, filter, setting);
+            match (value, &ffv) {
+                (RawValue::Object(value), FormattedFieldVariant::Normal { flatten: true }) => {
+                    // flatten depth limit is reached
+                    s.element(Element::Object, |s| s.batch(|buf| compact_json(value.get(), buf)));
+                }
+                _ => self.format_value(s, value, fs, filter, setting),
+            }
         } else {
             s.element(Element::String, |s| {
                 s.batch(|buf| buf.extend(value.raw_str().as_bytes()))
@@ -517,10 +1237,23 @@ impl<'a> FieldFormatter<'a> {
             RawValue::String(EncodedString::Raw(value)) => RawValue::auto(value.as_str()),
             _ => value,
         };
+        if let RawValue::String(string) = value {
+            let mut expanded = Vec::new();
+            if let Some(value) = self.rf.expanded_json(string, fs, &mut expanded) {
+                fs.expansion_depth += 1;
+                FieldFormatter::new(self.rf).format_value(s, value, fs, filter, setting);
+                fs.expansion_depth -= 1;
+                return;
+            }
+        }
         match value {
             RawValue::String(value) => {
+                let mut stripped = String::new();
+                let value = self.rf.stripped(value, &mut stripped);
                 s.element(Element::String, |s| {
-                    s.batch(|buf| ValueFormatAuto.format(value, buf).unwrap())
+                    self.rf.truncated(s, self.rf.max_field_width, |buf| {
+                        self.rf.field_quote.format(value, buf).unwrap()
+                    })
                 });
             }
             RawValue::Number(value) => {
@@ -539,6 +1272,22 @@ impl<'a> FieldFormatter<'a> {
                 let mut item = model::Object::default();
                 value.parse_into(&mut item).ok();
                 s.element(Element::Object, |s| {
+                    if fs.pretty && !fs.flatten {
+                        if item.fields.is_empty() {
+                            s.batch(|buf| buf.extend(b" {}"));
+                        }
+                        fs.indent += 1;
+                        let mut some_fields_hidden = false;
+                        for (k, v) in item.fields.iter() {
+                            some_fields_hidden |= !self.format(s, k, *v, fs, filter, setting);
+                        }
+                        if some_fields_hidden {
+                            self.rf.format_hidden_fields_indicator(s, fs);
+                        }
+                        fs.indent -= 1;
+                        fs.some_nested_fields_hidden |= some_fields_hidden;
+                        return;
+                    }
                     if !fs.flatten {
                         s.batch(|buf| buf.push(b'{'));
                     }
@@ -567,6 +1316,8 @@ impl<'a> FieldFormatter<'a> {
                     let mut item = model::Array::default();
                     value.parse_into::<32>(&mut item).ok();
                     s.batch(|buf| buf.push(b'['));
+                    // array items are kept on a single line even in pretty layout
+                    let pretty = std::mem::replace(&mut fs.pretty, false);
                     let mut first = true;
                     for v in item.iter() {
                         if !first {
@@ -576,6 +1327,7 @@ impl<'a> FieldFormatter<'a> {
                         }
                         self.format_value(s, *v, fs, None, IncludeExcludeSetting::Unspecified);
                     }
+                    fs.pretty = pretty;
                     s.batch(|buf| buf.push(b']'));
                 });
             }
@@ -590,13 +1342,15 @@ impl<'a> FieldFormatter<'a> {
         value: RawValue<'a>,
         fs: &mut FormattingState,
     ) -> FormattedFieldVariant {
-        if fs.flatten && matches!(value, RawValue::Object(_)) {
+        if fs.flatten && matches!(value, RawValue::Object(_)) && self.rf.flatten_depth.is_none_or(|max| fs.depth < max)
+        {
+            fs.depth += 1;
             return FormattedFieldVariant::Flattened(fs.key_prefix.push(key));
         }
 
         if !fs.has_fields {
             fs.has_fields = true;
-            if self.rf.message_format.delimited {
+            if self.rf.message_format.delimited && !fs.pretty {
                 fs.add_element(|| s.space());
                 s.element(Element::MessageDelimiter, |s| {
                     s.batch(|buf| buf.extend(self.rf.punctuation.message_delimiter.as_bytes()));
@@ -606,7 +1360,12 @@ impl<'a> FieldFormatter<'a> {
 
         let variant = FormattedFieldVariant::Normal { flatten: fs.flatten };
 
-        fs.add_element(|| s.space());
+        if fs.pretty {
+            fs.empty = false;
+            s.batch(|buf| fs.new_line(buf));
+        } else {
+            fs.add_element(|| s.space());
+        }
         s.element(Element::Key, |s| {
             s.batch(|buf| {
                 if fs.flatten {
@@ -619,8 +1378,14 @@ impl<'a> FieldFormatter<'a> {
                 key.key_prettify(buf);
             });
         });
-        s.element(Element::Field, |s| {
-            s.batch(|buf| buf.extend(self.rf.punctuation.field_key_value_separator.as_bytes()));
+        s.element(Element::KeyValueSeparator, |s| {
+            s.batch(|buf| match (fs.pretty, value) {
+                // fields of a nested object follow on their own lines
+                (true, RawValue::Object(_)) if !matches!(variant, FormattedFieldVariant::Normal { flatten: true }) => {
+                    buf.push(b':')
+                }
+                _ => buf.extend(self.rf.punctuation.field_key_value_separator.as_bytes()),
+            });
         });
 
         variant
@@ -634,6 +1399,7 @@ impl<'a> FieldFormatter<'a> {
             }
             FormattedFieldVariant::Flattened(n) => {
                 fs.key_prefix.pop(n);
+                fs.depth -= 1;
             }
         }
     }
@@ -686,6 +1452,30 @@ impl KeyPrettify for str {
 
 // ---
 
+/// Appends JSON value source to the buffer removing insignificant whitespace.
+fn compact_json(value: &str, buf: &mut Buf) {
+    let mut in_string = false;
+    let mut escaped = false;
+    for &b in value.as_bytes() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+        } else if b.is_ascii_whitespace() {
+            continue;
+        } else if b == b'"' {
+            in_string = true;
+        }
+        buf.push(b);
+    }
+}
+
+// ---
+
 enum FormattedFieldVariant {
     Normal { flatten: bool },
     Flattened(usize),
@@ -693,6 +1483,18 @@ enum FormattedFieldVariant {
 
 // ---
 
+pub mod ansi;
+pub mod columns;
+pub mod highlight;
+pub mod html;
+pub mod json;
+pub mod logfmt;
+pub mod template;
+pub mod value;
+pub mod wrap;
+
+// ---
+
 pub mod string {
     // std imports
     use std::{cmp::min, sync::Arc};
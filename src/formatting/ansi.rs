@@ -0,0 +1,109 @@
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+const CSI: char = '\u{9b}';
+const OSC: char = '\u{9d}';
+const DCS: char = '\u{90}';
+const SOS: char = '\u{98}';
+const PM: char = '\u{9e}';
+const APC: char = '\u{9f}';
+const ST: char = '\u{9c}';
+
+const INTRODUCERS: [char; 7] = [ESC, CSI, OSC, DCS, SOS, PM, APC];
+
+/// Returns true if the source of an encoded string may contain escape sequences,
+/// either as is or as escaped control characters, e.g. `\u001b` in JSON.
+#[inline]
+pub fn may_contain_sequences(source: &str) -> bool {
+    source.contains(INTRODUCERS) || source.contains("\\u00")
+}
+
+/// Appends the text to the buffer without ANSI escape sequences.
+///
+/// Recognizes control sequences (CSI) including SGR, operating system commands (OSC) terminated by BEL or ST,
+/// device control and other string sequences terminated by ST, and two-character escape sequences,
+/// introduced either by ESC or by the equivalent 8-bit control characters.
+/// An unterminated sequence extends to the end of the text.
+pub fn strip(text: &str, buf: &mut String) {
+    let mut rest = text;
+    while let Some(i) = rest.find(INTRODUCERS) {
+        buf.push_str(&rest[..i]);
+        rest = &rest[i..];
+        rest = &rest[sequence_len(rest)..];
+    }
+    buf.push_str(rest);
+}
+
+/// Returns the length of the escape sequence the text starts with, the text must start with one of the introducers.
+fn sequence_len(text: &str) -> usize {
+    let mut chars = text.chars();
+    let first = chars.next().unwrap_or(ESC);
+    let n = first.len_utf8();
+
+    let (kind, n) = if first == ESC {
+        match chars.next() {
+            Some('[') => (Kind::Control, n + 1),
+            Some(']') => (Kind::Command, n + 1),
+            Some('P' | 'X' | '^' | '_') => (Kind::String, n + 1),
+            Some(c @ '\x20'..='\x2f') => (Kind::Escape, n + c.len_utf8()),
+            Some('\x30'..='\x7e') => return n + 1,
+            _ => return n,
+        }
+    } else {
+        match first {
+            CSI => (Kind::Control, n),
+            OSC => (Kind::Command, n),
+            _ => (Kind::String, n),
+        }
+    };
+
+    let body = &text[n..];
+    n + match kind {
+        Kind::Control => {
+            // parameter and intermediate bytes followed by a final byte
+            let bytes = body.as_bytes();
+            let i = bytes
+                .iter()
+                .position(|b| !(0x20..=0x3f).contains(b))
+                .unwrap_or(bytes.len());
+            match bytes.get(i) {
+                Some(0x40..=0x7e) => i + 1,
+                _ => i,
+            }
+        }
+        Kind::Escape => {
+            // intermediate bytes followed by a final byte
+            let bytes = body.as_bytes();
+            let i = bytes
+                .iter()
+                .position(|b| !(0x20..=0x2f).contains(b))
+                .unwrap_or(bytes.len());
+            match bytes.get(i) {
+                Some(0x30..=0x7e) => i + 1,
+                _ => i,
+            }
+        }
+        Kind::Command | Kind::String => {
+            let mut chars = body.char_indices().peekable();
+            loop {
+                match chars.next() {
+                    None => break body.len(),
+                    Some((i, BEL)) if kind == Kind::Command => break i + 1,
+                    Some((i, ST)) => break i + ST.len_utf8(),
+                    Some((i, ESC)) if chars.peek().map(|&(_, c)| c) == Some('\\') => break i + 2,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Control,
+    Command,
+    String,
+    Escape,
+}
+
+#[cfg(test)]
+mod tests;
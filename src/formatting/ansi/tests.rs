@@ -0,0 +1,38 @@
+use super::*;
+
+use rstest::rstest;
+
+fn stripped(text: &str) -> String {
+    let mut buf = String::new();
+    strip(text, &mut buf);
+    buf
+}
+
+#[rstest]
+#[case("plain", "plain")]
+#[case("\x1b[31mred\x1b[0m", "red")]
+#[case("\x1b[1;38;2;255;0;0mtrue color\x1b[m", "true color")]
+#[case("\x1b[38:5:196mcolon\x1b[39m", "colon")]
+#[case("a\x1b[2Kb\x1b[10;20Hc\x1b[?25ld", "abcd")]
+#[case("\x1b]8;;https://example.com\x07link\x1b]8;;\x1b\\", "link")]
+#[case("\x1b]0;title\x1b\\text", "text")]
+#[case("\x1bPdevice\x1b\\x", "x")]
+#[case("\x1b(Bcharset", "charset")]
+#[case("\x1b7saved\x1b8", "saved")]
+#[case("\u{9b}31m8-bit\u{9b}0m", "8-bit")]
+#[case("\u{9d}0;title\u{9c}rest", "rest")]
+#[case("héllo \x1b[1mwörld\x1b[0m ✓", "héllo wörld ✓")]
+#[case("unterminated \x1b[31", "unterminated ")]
+#[case("lone \x1b", "lone ")]
+#[case("\x1b[31\u{e9}", "\u{e9}")]
+fn test_strip(#[case] text: &str, #[case] expected: &str) {
+    assert_eq!(stripped(text), expected);
+}
+
+#[test]
+fn test_may_contain_sequences() {
+    assert!(may_contain_sequences("a\x1b[0m"));
+    assert!(may_contain_sequences(r#""\u001b[0m""#));
+    assert!(may_contain_sequences("\u{9b}0m"));
+    assert!(!may_contain_sequences(r#""plain [0m""#));
+}
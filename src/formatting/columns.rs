@@ -0,0 +1,188 @@
+// third-party imports
+use unicode_width::UnicodeWidthChar;
+
+// workspace imports
+use encstr::AnyEncodedString;
+
+// local imports
+use crate::{
+    datefmt::DateTimeFormatter,
+    model::{self, Level, RawValue},
+};
+
+// relative imports
+use super::{Buf, RecordWithSourceFormatter};
+
+// ---
+
+const TIME_KEY: &str = "time";
+const LEVEL_KEY: &str = "level";
+const LEVEL_WIDTH: usize = 3;
+const DEFAULT_WIDTH: usize = 16;
+const SEPARATOR: &str = "  ";
+const ELLIPSIS: char = '…';
+
+/// Formats records as rows of fixed-width aligned columns.
+///
+/// `time` and `level` columns show the recognized timestamp and level, other columns show the field with the same key.
+/// Cells are padded or truncated to the column width except for the last column, which is left as is.
+/// Missing fields leave the cell blank.
+pub struct ColumnsRecordFormatter {
+    keys: Vec<String>,
+    width: usize,
+    ts_formatter: DateTimeFormatter,
+    ts_width: usize,
+}
+
+impl ColumnsRecordFormatter {
+    pub fn new(keys: Vec<String>) -> Self {
+        let ts_formatter = DateTimeFormatter::default();
+        Self {
+            keys,
+            width: DEFAULT_WIDTH,
+            ts_width: ts_formatter.max_length(),
+            ts_formatter,
+        }
+    }
+
+    pub fn with_timestamp_formatter(self, ts_formatter: DateTimeFormatter) -> Self {
+        Self {
+            ts_width: ts_formatter.max_length(),
+            ts_formatter,
+            ..self
+        }
+    }
+
+    /// Sets the width of the columns other than `time` and `level`, which are as wide as their content or header.
+    pub fn with_width(self, width: Option<usize>) -> Self {
+        Self {
+            width: width.unwrap_or(DEFAULT_WIDTH),
+            ..self
+        }
+    }
+
+    /// Writes the header row with the column keys in upper case.
+    pub fn format_header(&self, buf: &mut Buf) {
+        self.format_row(buf, |buf, key| buf.push_str(&key.to_uppercase()));
+    }
+
+    pub fn format_record(&self, buf: &mut Buf, rec: &model::Record) {
+        self.format_row(buf, |buf, key| match key {
+            TIME_KEY => {
+                if let Some(ts) = &rec.ts {
+                    let mut tb = Vec::new();
+                    if ts
+                        .as_rfc3339()
+                        .and_then(|ts| self.ts_formatter.reformat_rfc3339(&mut tb, ts))
+                        .is_none()
+                    {
+                        match ts.parse() {
+                            Some(ts) => self.ts_formatter.format(&mut tb, ts),
+                            None => tb.extend(ts.raw().as_bytes()),
+                        }
+                    }
+                    buf.push_str(&String::from_utf8_lossy(&tb));
+                }
+            }
+            LEVEL_KEY => buf.push_str(match rec.level {
+                Some(Level::Error) => "ERR",
+                Some(Level::Warning) => "WRN",
+                Some(Level::Info) => "INF",
+                Some(Level::Debug) => "DBG",
+                Some(Level::Trace) => "TRC",
+                None => "",
+            }),
+            key => {
+                if let Some(value) = rec.get(key) {
+                    push_value(buf, value);
+                }
+            }
+        });
+    }
+
+    fn format_row<F>(&self, buf: &mut Buf, mut cell: F)
+    where
+        F: FnMut(&mut String, &str),
+    {
+        let mut text = String::new();
+        for (i, key) in self.keys.iter().enumerate() {
+            if i != 0 {
+                buf.extend(SEPARATOR.as_bytes());
+            }
+            text.clear();
+            cell(&mut text, key);
+            if i + 1 == self.keys.len() {
+                buf.extend(text.as_bytes());
+            } else {
+                fit(buf, &text, self.column_width(key));
+            }
+        }
+    }
+
+    fn column_width(&self, key: &str) -> usize {
+        match key {
+            TIME_KEY => self.ts_width.max(TIME_KEY.len()),
+            LEVEL_KEY => LEVEL_WIDTH.max(LEVEL_KEY.len()),
+            _ => self.width,
+        }
+    }
+}
+
+impl RecordWithSourceFormatter for ColumnsRecordFormatter {
+    #[inline]
+    fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        ColumnsRecordFormatter::format_record(self, buf, rec.record)
+    }
+}
+
+// ---
+
+/// Writes the decoded value replacing control characters with spaces to keep the row on a single line.
+fn push_value(buf: &mut String, value: RawValue) {
+    let text = match value {
+        RawValue::String(value) => {
+            let mut decoded = Vec::new();
+            match value.decode(&mut decoded) {
+                Ok(()) => String::from_utf8_lossy(&decoded).into_owned(),
+                Err(_) => value.source().to_owned(),
+            }
+        }
+        _ => value.raw_str().to_owned(),
+    };
+    buf.extend(text.chars().map(|c| if c.is_control() { ' ' } else { c }));
+}
+
+/// Writes the text padded or truncated to exactly `width` columns.
+///
+/// Wide characters take two columns and combining characters take none.
+fn fit(buf: &mut Buf, text: &str, width: usize) {
+    let n: usize = text.chars().map(char_width).sum();
+    if n <= width {
+        buf.extend(text.as_bytes());
+        buf.extend(std::iter::repeat_n(b' ', width - n));
+    } else if width != 0 {
+        // leave a column for the ellipsis, a wide character that does not fit is replaced with padding
+        let mut col = 0;
+        let mut end = text.len();
+        for (i, c) in text.char_indices() {
+            let n = char_width(c);
+            if col + n > width - 1 {
+                end = i;
+                break;
+            }
+            col += n;
+        }
+        buf.extend(text[..end].as_bytes());
+        buf.extend(std::iter::repeat_n(b' ', width - 1 - col));
+        let mut tmp = [0; 4];
+        buf.extend(ELLIPSIS.encode_utf8(&mut tmp).as_bytes());
+    }
+}
+
+#[inline]
+fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests;
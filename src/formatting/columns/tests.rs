@@ -0,0 +1,69 @@
+use super::*;
+use crate::{
+    model::{Record, RecordFields},
+    timestamp::Timestamp,
+};
+use encstr::EncodedString;
+
+fn format(formatter: &ColumnsRecordFormatter, rec: &Record) -> String {
+    let mut buf = Vec::new();
+    formatter.format_record(&mut buf, rec);
+    String::from_utf8(buf).unwrap()
+}
+
+fn formatter() -> ColumnsRecordFormatter {
+    ColumnsRecordFormatter::new(vec!["level".into(), "service".into(), "message".into()]).with_width(Some(6))
+}
+
+#[test]
+fn test_header() {
+    let mut buf = Vec::new();
+    formatter().format_header(&mut buf);
+    assert_eq!(String::from_utf8(buf).unwrap(), "LEVEL  SERVI…  MESSAGE");
+}
+
+#[test]
+fn test_record() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(r#""line\nbreak""#))),
+        level: Some(Level::Warning),
+        fields: RecordFields::from_slice(&[("service", RawValue::String(EncodedString::raw("api")))]),
+        ..Default::default()
+    };
+    assert_eq!(format(&formatter(), &rec), "WRN    api     line break");
+}
+
+#[test]
+fn test_missing_and_truncated() {
+    let rec = Record {
+        fields: RecordFields::from_slice(&[("service", RawValue::String(EncodedString::raw("scheduler")))]),
+        ..Default::default()
+    };
+    assert_eq!(format(&formatter(), &rec), "       sched…  ");
+}
+
+#[test]
+fn test_time() {
+    let formatter = ColumnsRecordFormatter::new(vec!["time".into(), "n".into()]);
+    let rec = Record {
+        ts: Some(Timestamp::new("2000-01-02T03:04:05.123Z")),
+        fields: RecordFields::from_slice(&[("n", RawValue::Number("42"))]),
+        ..Default::default()
+    };
+    let result = format(&formatter, &rec);
+    assert!(result.ends_with("  42"), "{}", result);
+    assert_eq!(result.chars().count(), formatter.ts_width + 4);
+}
+
+#[test]
+fn test_fit() {
+    let fit = |text, width| {
+        let mut buf = Vec::new();
+        super::fit(&mut buf, text, width);
+        String::from_utf8(buf).unwrap()
+    };
+    assert_eq!(fit("日本", 6), "日本  ");
+    assert_eq!(fit("日本語テ", 6), "日本 …");
+    assert_eq!(fit("日本語", 5), "日本…");
+    assert_eq!(fit("e\u{301}te", 3), "e\u{301}te");
+}
@@ -0,0 +1,50 @@
+// std imports
+use std::ops::Range;
+
+// third-party imports
+use regex::bytes::Regex;
+
+// local imports
+use crate::error::Result;
+
+// ---
+
+/// Finds fragments of formatted text that should be highlighted, e.g. text matched by active filters.
+pub struct Highlighter {
+    regex: Regex,
+}
+
+impl Highlighter {
+    /// Creates a highlighter matching any of the given regular expressions.
+    ///
+    /// Returns `None` if there are no patterns.
+    pub fn new<S: AsRef<str>, I: IntoIterator<Item = S>>(patterns: I) -> Result<Option<Self>> {
+        let patterns: Vec<_> = patterns
+            .into_iter()
+            .filter(|p| !p.as_ref().is_empty())
+            .map(|p| format!("(?:{})", p.as_ref()))
+            .collect();
+
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            regex: Regex::new(&patterns.join("|"))?,
+        }))
+    }
+
+    #[inline]
+    pub fn is_match(&self, text: &[u8]) -> bool {
+        self.regex.is_match(text)
+    }
+
+    /// Returns non-empty matched ranges of the text.
+    #[inline]
+    pub fn find_iter<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = Range<usize>> + 'a {
+        self.regex.find_iter(text).map(|m| m.range()).filter(|r| !r.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn test_highlighter() {
+    let highlighter = Highlighter::new([regex::escape("a.b"), "^x+".into()]).unwrap().unwrap();
+
+    assert!(highlighter.is_match(b"1a.b2"));
+    assert!(!highlighter.is_match(b"1aab2"));
+    assert_eq!(
+        highlighter.find_iter(b"xxa.b a.b").collect::<Vec<_>>(),
+        vec![0..2, 2..5, 6..9]
+    );
+}
+
+#[test]
+fn test_highlighter_empty() {
+    assert!(Highlighter::new(Vec::<String>::new()).unwrap().is_none());
+    assert!(Highlighter::new([""]).unwrap().is_none());
+}
+
+#[test]
+fn test_highlighter_invalid() {
+    assert!(Highlighter::new(["("]).is_err());
+}
+
+#[test]
+fn test_highlighter_empty_matches() {
+    let highlighter = Highlighter::new(["x*"]).unwrap().unwrap();
+    assert_eq!(highlighter.find_iter(b"axxb").collect::<Vec<_>>(), vec![1..3]);
+}
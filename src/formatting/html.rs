@@ -0,0 +1,292 @@
+// std imports
+use std::io::Write;
+
+// local imports
+use crate::model;
+
+// relative imports
+use super::{Buf, RecordWithSourceFormatter};
+
+// ---
+
+/// Formats records as HTML by converting styled output of the inner formatter into `<span>` elements with inline styles.
+///
+/// Colors and modes chosen by the theme are preserved exactly as they appear in the terminal output,
+/// and HTML-special characters are escaped.
+pub struct HtmlRecordFormatter<F> {
+    inner: F,
+}
+
+impl<F: RecordWithSourceFormatter> HtmlRecordFormatter<F> {
+    pub fn new(inner: F) -> Self {
+        Self { inner }
+    }
+}
+
+impl<F: RecordWithSourceFormatter> RecordWithSourceFormatter for HtmlRecordFormatter<F> {
+    fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        let mut styled = Vec::with_capacity(256);
+        self.inner.format_record(&mut styled, rec);
+        render(&styled, buf);
+    }
+}
+
+// ---
+
+/// Writes the beginning of the HTML output.
+///
+/// In standalone mode a complete document with a dark background is started.
+pub fn prologue(buf: &mut Buf, standalone: bool) {
+    if standalone {
+        buf.extend(
+            concat!(
+                "<!DOCTYPE html>\n",
+                "<html>\n",
+                "<head>\n",
+                "<meta charset=\"utf-8\">\n",
+                "<title>hl</title>\n",
+                "</head>\n",
+                "<body style=\"margin:0;background-color:#1e1e1e;color:#d4d4d4\">\n",
+                "<pre style=\"margin:0;padding:1em\">",
+            )
+            .as_bytes(),
+        );
+    } else {
+        buf.extend(b"<pre>");
+    }
+}
+
+/// Writes the end of the HTML output matching the [`prologue`].
+pub fn epilogue(buf: &mut Buf, standalone: bool) {
+    buf.extend(b"</pre>\n");
+    if standalone {
+        buf.extend(b"</body>\n</html>\n");
+    }
+}
+
+/// Converts text with SGR escape sequences into escaped HTML with inline-styled `<span>` elements.
+///
+/// Escape sequences other than SGR are dropped.
+pub fn render(input: &[u8], buf: &mut Buf) {
+    let mut style = Style::default();
+    let mut open: Option<String> = None;
+    let mut i = 0;
+
+    while i < input.len() {
+        if input[i] == 0x1b && input.get(i + 1) == Some(&b'[') {
+            let begin = i + 2;
+            let end = input[begin..]
+                .iter()
+                .position(|b| (0x40..=0x7e).contains(b))
+                .map(|n| begin + n)
+                .unwrap_or(input.len());
+            if input.get(end) == Some(&b'm') {
+                style.apply(&input[begin..end]);
+            }
+            i = end + 1;
+            continue;
+        }
+
+        let end = input[i..]
+            .iter()
+            .position(|&b| b == 0x1b)
+            .map(|n| i + n)
+            .unwrap_or(input.len());
+
+        let text = &input[i..end];
+        let ws = text.iter().all(|&b| b == b' ' || b == b'\n');
+        if !ws || style.has_background() {
+            let wanted = style.css();
+            if open != wanted {
+                if open.is_some() {
+                    buf.extend(b"</span>");
+                }
+                if let Some(css) = &wanted {
+                    write!(buf, "<span style=\"{css}\">").ok();
+                }
+                open = wanted;
+            }
+        }
+        escape(text, buf);
+        i = end;
+    }
+
+    if open.is_some() {
+        buf.extend(b"</span>");
+    }
+}
+
+fn escape(text: &[u8], buf: &mut Buf) {
+    for &b in text {
+        match b {
+            b'&' => buf.extend(b"&amp;"),
+            b'<' => buf.extend(b"&lt;"),
+            b'>' => buf.extend(b"&gt;"),
+            b'"' => buf.extend(b"&quot;"),
+            b'\'' => buf.extend(b"&#39;"),
+            _ => buf.push(b),
+        }
+    }
+}
+
+// ---
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    fg: Option<Rgb>,
+    bg: Option<Rgb>,
+    bold: bool,
+    faint: bool,
+    italic: bool,
+    underline: bool,
+    reverse: bool,
+    conceal: bool,
+    crossed_out: bool,
+}
+
+impl Style {
+    fn apply(&mut self, params: &[u8]) {
+        let mut codes = params.split(|&b| b == b';').map(|p| {
+            std::str::from_utf8(p)
+                .ok()
+                .and_then(|p| p.parse::<u8>().ok())
+                .unwrap_or(0)
+        });
+
+        while let Some(code) = codes.next() {
+            match code {
+                0 => *self = Self::default(),
+                1 => self.bold = true,
+                2 => self.faint = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                7 => self.reverse = true,
+                8 => self.conceal = true,
+                9 => self.crossed_out = true,
+                22 => (self.bold, self.faint) = (false, false),
+                23 => self.italic = false,
+                24 => self.underline = false,
+                27 => self.reverse = false,
+                28 => self.conceal = false,
+                29 => self.crossed_out = false,
+                30..=37 => self.fg = Some(Rgb::palette(code - 30)),
+                38 => self.fg = Rgb::extended(&mut codes),
+                39 => self.fg = None,
+                40..=47 => self.bg = Some(Rgb::palette(code - 40)),
+                48 => self.bg = Rgb::extended(&mut codes),
+                49 => self.bg = None,
+                90..=97 => self.fg = Some(Rgb::palette(code - 90 + 8)),
+                100..=107 => self.bg = Some(Rgb::palette(code - 100 + 8)),
+                _ => {}
+            }
+        }
+    }
+
+    fn has_background(&self) -> bool {
+        if self.reverse {
+            self.fg.is_some()
+        } else {
+            self.bg.is_some()
+        }
+    }
+
+    fn css(&self) -> Option<String> {
+        let (fg, bg) = if self.reverse {
+            (self.bg, self.fg)
+        } else {
+            (self.fg, self.bg)
+        };
+
+        let mut props = Vec::new();
+        if let Some(fg) = fg {
+            props.push(format!("color:{fg}"));
+        }
+        if let Some(bg) = bg {
+            props.push(format!("background-color:{bg}"));
+        }
+        if self.bold {
+            props.push("font-weight:bold".into());
+        }
+        if self.faint {
+            props.push("opacity:0.7".into());
+        }
+        if self.italic {
+            props.push("font-style:italic".into());
+        }
+        match (self.underline, self.crossed_out) {
+            (true, true) => props.push("text-decoration:underline line-through".into()),
+            (true, false) => props.push("text-decoration:underline".into()),
+            (false, true) => props.push("text-decoration:line-through".into()),
+            (false, false) => {}
+        }
+        if self.conceal {
+            props.push("visibility:hidden".into());
+        }
+
+        (!props.is_empty()).then(|| props.join(";"))
+    }
+}
+
+// ---
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Rgb(u8, u8, u8);
+
+impl Rgb {
+    /// Resolves a color of the 256-color palette using the xterm defaults.
+    fn palette(code: u8) -> Self {
+        const BASIC: [Rgb; 16] = [
+            Rgb(0x00, 0x00, 0x00),
+            Rgb(0xcd, 0x00, 0x00),
+            Rgb(0x00, 0xcd, 0x00),
+            Rgb(0xcd, 0xcd, 0x00),
+            Rgb(0x00, 0x00, 0xee),
+            Rgb(0xcd, 0x00, 0xcd),
+            Rgb(0x00, 0xcd, 0xcd),
+            Rgb(0xe5, 0xe5, 0xe5),
+            Rgb(0x7f, 0x7f, 0x7f),
+            Rgb(0xff, 0x00, 0x00),
+            Rgb(0x00, 0xff, 0x00),
+            Rgb(0xff, 0xff, 0x00),
+            Rgb(0x5c, 0x5c, 0xff),
+            Rgb(0xff, 0x00, 0xff),
+            Rgb(0x00, 0xff, 0xff),
+            Rgb(0xff, 0xff, 0xff),
+        ];
+        const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        match code {
+            0..=15 => BASIC[code as usize],
+            16..=231 => {
+                let n = code - 16;
+                Rgb(
+                    CUBE[(n / 36) as usize],
+                    CUBE[(n / 6 % 6) as usize],
+                    CUBE[(n % 6) as usize],
+                )
+            }
+            _ => {
+                let v = 8 + (code - 232) * 10;
+                Rgb(v, v, v)
+            }
+        }
+    }
+
+    /// Parses the remainder of an extended color specification, i.e. `5;n` or `2;r;g;b`.
+    fn extended<I: Iterator<Item = u8>>(codes: &mut I) -> Option<Self> {
+        match codes.next()? {
+            5 => Some(Self::palette(codes.next()?)),
+            2 => Some(Rgb(codes.next()?, codes.next()?, codes.next()?)),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Rgb {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use super::*;
+
+fn html(input: &str) -> String {
+    let mut buf = Vec::new();
+    render(input.as_bytes(), &mut buf);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_render_plain() {
+    assert_eq!(html(r#"a<b> & "c" 'd'"#), "a&lt;b&gt; &amp; &quot;c&quot; &#39;d&#39;");
+}
+
+#[test]
+fn test_render_styles() {
+    assert_eq!(
+        html("\x1b[0;1;31mERR\x1b[0m x=\x1b[0;38;5;39m<1>\x1b[0m"),
+        r#"<span style="color:#cd0000;font-weight:bold">ERR</span> x=<span style="color:#00afff">&lt;1&gt;</span>"#,
+    );
+}
+
+#[test]
+fn test_render_merges_equal_styles() {
+    assert_eq!(
+        html("\x1b[0;32ma\x1b[0m \x1b[0;32mb\x1b[0m"),
+        r#"<span style="color:#00cd00">a</span> <span style="color:#00cd00">b</span>"#,
+    );
+    assert_eq!(
+        html("\x1b[0;32ma\x1b[0;32mb\x1b[0m"),
+        r#"<span style="color:#00cd00">ab</span>"#
+    );
+}
+
+#[test]
+fn test_render_extended() {
+    assert_eq!(
+        html("\x1b[0;7;38;2;1;2;3;103mx\x1b[0m"),
+        r#"<span style="color:#ffff00;background-color:#010203">x</span>"#,
+    );
+    assert_eq!(html("\x1b[2Kx"), "x");
+}
+
+#[test]
+fn test_prologue_epilogue() {
+    let mut buf = Vec::new();
+    prologue(&mut buf, false);
+    epilogue(&mut buf, false);
+    assert_eq!(buf, b"<pre></pre>\n");
+
+    let mut buf = Vec::new();
+    prologue(&mut buf, true);
+    epilogue(&mut buf, true);
+    let html = String::from_utf8(buf).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"));
+    assert!(html.ends_with("</pre>\n</body>\n</html>\n"));
+}
@@ -0,0 +1,210 @@
+// std imports
+use std::sync::Arc;
+
+// third-party imports
+use chrono::{SecondsFormat, Utc};
+use serde_json as json;
+
+// workspace imports
+use encstr::{AnyEncodedString, Builder, EncodedString};
+
+// local imports
+use crate::{
+    IncludeExcludeKeyFilter,
+    filtering::IncludeExcludeSetting,
+    model::{self, RawObject, RawRecordParser, RawValue},
+    settings::FieldAliases,
+};
+
+// relative imports
+use super::{Buf, RecordWithSourceFormatter};
+
+// ---
+
+const NORMALIZED_TIME_KEY: &str = "ts";
+const NORMALIZED_LEVEL_KEY: &str = "level";
+
+/// Formats records as JSON lines.
+///
+/// Fields are emitted in their original order, and values keep their original types and representation.
+/// Normalization adds `ts` and `level` fields in front of the others,
+/// replacing the original fields having the same keys.
+#[derive(Default)]
+pub struct JsonRecordFormatter {
+    hide_empty_fields: bool,
+    fields: Arc<IncludeExcludeKeyFilter>,
+    parser: RawRecordParser,
+    normalize: bool,
+    aliases: FieldAliases,
+}
+
+impl JsonRecordFormatter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_empty_fields_hiding(self, enabled: bool) -> Self {
+        Self {
+            hide_empty_fields: enabled,
+            ..self
+        }
+    }
+
+    pub fn with_field_filter(self, value: Arc<IncludeExcludeKeyFilter>) -> Self {
+        Self { fields: value, ..self }
+    }
+
+    /// Sets the parser used to read the original fields back from the record source.
+    /// It is expected to be configured the same way as the parser used for the input.
+    pub fn with_source_parser(self, parser: RawRecordParser) -> Self {
+        Self { parser, ..self }
+    }
+
+    pub fn with_normalization(self, enabled: bool) -> Self {
+        Self {
+            normalize: enabled,
+            ..self
+        }
+    }
+
+    /// Sets the canonical keys replacing the original keys of aliased top-level fields.
+    pub fn with_aliases(self, aliases: FieldAliases) -> Self {
+        Self { aliases, ..self }
+    }
+
+    pub fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        buf.push(b'{');
+        let begin = buf.len();
+
+        if self.normalize {
+            if let Some(ts) = rec.record.ts.as_ref().and_then(|ts| ts.parse()) {
+                let ts = ts.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::AutoSi, true);
+                self.format_key(buf, begin, NORMALIZED_TIME_KEY);
+                json::to_writer(&mut *buf, &ts).ok();
+            }
+            if let Some(level) = rec.record.level {
+                self.format_key(buf, begin, NORMALIZED_LEVEL_KEY);
+                json::to_writer(&mut *buf, level.as_ref()).ok();
+            }
+        }
+
+        // Fields are read back from the source to keep predefined fields in their original places.
+        let source = self.parser.parse(rec.source).next();
+        match source {
+            Some(Ok(source)) => self.format_fields(buf, begin, source.record.fields()),
+            _ => self.format_fields(buf, begin, rec.record.fields_for_search()),
+        }
+
+        buf.push(b'}');
+    }
+
+    fn format_fields<'a, 'i, I>(&self, buf: &mut Buf, begin: usize, fields: I)
+    where
+        I: IntoIterator<Item = &'i (&'a str, RawValue<'a>)>,
+        'a: 'i,
+    {
+        for (k, v) in fields {
+            let k = self.aliases.get(*k).map(String::as_str).unwrap_or(k);
+            if self.normalize && (k == NORMALIZED_TIME_KEY || k == NORMALIZED_LEVEL_KEY) {
+                continue;
+            }
+            if !self.hide_empty_fields || !v.is_empty() {
+                self.format_field(
+                    buf,
+                    begin,
+                    k,
+                    *v,
+                    Some(&self.fields),
+                    IncludeExcludeSetting::Unspecified,
+                );
+            }
+        }
+    }
+
+    fn format_field(
+        &self,
+        buf: &mut Buf,
+        begin: usize,
+        key: &str,
+        value: RawValue,
+        filter: Option<&IncludeExcludeKeyFilter>,
+        setting: IncludeExcludeSetting,
+    ) {
+        let (filter, setting, leaf) = match filter {
+            Some(filter) => {
+                let setting = setting.apply(filter.setting());
+                match filter.get(key) {
+                    Some(filter) => (Some(filter), setting.apply(filter.setting()), filter.leaf()),
+                    None => (None, setting, true),
+                }
+            }
+            None => (None, setting, true),
+        };
+        if setting == IncludeExcludeSetting::Exclude && leaf {
+            return;
+        }
+
+        self.format_key(buf, begin, key);
+
+        match value {
+            RawValue::Object(RawObject::Json(object)) if leaf && !self.hide_empty_fields => {
+                buf.extend(object.get().as_bytes())
+            }
+            RawValue::Object(object) => {
+                let mut item = model::Object::default();
+                object.parse_into(&mut item).ok();
+                buf.push(b'{');
+                let begin = buf.len();
+                for (k, v) in item.fields.iter() {
+                    if !self.hide_empty_fields || !v.is_empty() {
+                        self.format_field(buf, begin, k, *v, filter, setting);
+                    }
+                }
+                buf.push(b'}');
+            }
+            _ => format_value(buf, value),
+        }
+    }
+
+    fn format_key(&self, buf: &mut Buf, begin: usize, key: &str) {
+        if begin != buf.len() {
+            buf.push(b',');
+        }
+        json::to_writer(&mut *buf, key).ok();
+        buf.push(b':');
+    }
+}
+
+impl RecordWithSourceFormatter for JsonRecordFormatter {
+    #[inline]
+    fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        JsonRecordFormatter::format_record(self, buf, rec)
+    }
+}
+
+// ---
+
+fn format_value(buf: &mut Buf, value: RawValue) {
+    match value {
+        RawValue::String(value) => match value {
+            EncodedString::Json(_) => buf.extend(value.source().as_bytes()),
+            EncodedString::Raw(_) => {
+                json::to_writer(&mut *buf, value.source()).ok();
+            }
+            EncodedString::SdParam(_) => {
+                let mut decoded = Builder::new();
+                value.decode(&mut decoded).ok();
+                json::to_writer(&mut *buf, decoded.as_str()).ok();
+            }
+        },
+        RawValue::Number(value) => buf.extend(value.as_bytes()),
+        RawValue::Boolean(true) => buf.extend(b"true"),
+        RawValue::Boolean(false) => buf.extend(b"false"),
+        RawValue::Null => buf.extend(b"null"),
+        RawValue::Object(value) => buf.extend(value.get().as_bytes()),
+        RawValue::Array(value) => buf.extend(value.get().as_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests;
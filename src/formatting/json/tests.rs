@@ -0,0 +1,80 @@
+use super::*;
+use crate::{
+    filtering::MatchOptions,
+    model::{Parser, ParserSettings},
+};
+
+fn format(formatter: &JsonRecordFormatter, source: &str) -> String {
+    format_with(formatter, RawRecordParser::new(), source)
+}
+
+fn format_with(formatter: &JsonRecordFormatter, parser: RawRecordParser, source: &str) -> String {
+    let raw = parser.parse(source.as_bytes()).next().unwrap().unwrap().record;
+    let rec = Parser::new(ParserSettings::default()).parse(&raw);
+    let mut buf = Vec::new();
+    formatter.format_record(&mut buf, model::RecordWithSource::new(&rec, source.as_bytes()));
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_preserves_order_and_types() {
+    let source =
+        r#"{"a":1,"ts":"2000-01-02T03:04:05Z","b":true,"level":"info","c":null,"msg":"m","d":{"x":[1, 2]},"e":1.50}"#;
+    assert_eq!(format(&JsonRecordFormatter::new(), source), source);
+}
+
+#[test]
+fn test_logfmt_source() {
+    let source = r#"level=info msg="some message" k=42 path=a\b"#;
+    assert_eq!(
+        format(&JsonRecordFormatter::new(), source),
+        r#"{"level":"info","msg":"some message","k":"42","path":"a\\b"}"#
+    );
+}
+
+#[test]
+fn test_source_parser() {
+    let source = r#"logfmt prefix {"b":1,"ts":"2000-01-02T03:04:05Z","a":2,"msg":"m"}"#;
+    let parser = || RawRecordParser::new().allow_prefix(true);
+    let formatter = JsonRecordFormatter::new().with_source_parser(parser());
+    assert_eq!(
+        format_with(&formatter, parser(), source),
+        r#"{"b":1,"ts":"2000-01-02T03:04:05Z","a":2,"msg":"m"}"#
+    );
+}
+
+#[test]
+fn test_normalize() {
+    let source = r#"{"time":"2000-01-02T06:04:05.123+03:00","level":"WARN","ts":"x","msg":"m"}"#;
+    assert_eq!(
+        format(&JsonRecordFormatter::new().with_normalization(true), source),
+        r#"{"ts":"2000-01-02T03:04:05.123Z","level":"warning","time":"2000-01-02T06:04:05.123+03:00","msg":"m"}"#
+    );
+}
+
+#[test]
+fn test_field_filter() {
+    let mut filter = IncludeExcludeKeyFilter::new(MatchOptions::default());
+    filter.entry("a.b").exclude();
+    filter.entry("c").exclude();
+    let formatter = JsonRecordFormatter::new()
+        .with_field_filter(Arc::new(filter))
+        .with_empty_fields_hiding(true);
+
+    let source = r#"{"a":{"b":1,"e":""},"c":2,"d":{"x":3},"f":""}"#;
+    assert_eq!(format(&formatter, source), r#"{"a":{},"d":{"x":3}}"#);
+}
+
+#[test]
+fn test_aliases() {
+    let aliases = FieldAliases::from(std::collections::BTreeMap::from([(
+        "svc".to_string(),
+        "service".to_string(),
+    )]));
+    let source = r#"{"svc":"api","msg":"m","x":{"svc":1}}"#;
+    assert_eq!(
+        format(&JsonRecordFormatter::new().with_aliases(aliases), source),
+        r#"{"service":"api","msg":"m","x":{"svc":1}}"#
+    );
+    assert_eq!(format(&JsonRecordFormatter::new(), source), source);
+}
@@ -0,0 +1,229 @@
+// std imports
+use std::sync::Arc;
+
+// workspace imports
+use encstr::EncodedString;
+
+// local imports
+use crate::{
+    IncludeExcludeKeyFilter,
+    filtering::IncludeExcludeSetting,
+    model::{self, RawValue},
+};
+
+// relative imports
+use super::{
+    Buf, FieldQuote, RecordWithSourceFormatter,
+    string::{Format, ValueFormatDoubleQuoted, ValueFormatRaw},
+};
+
+// ---
+
+/// Formats records as logfmt lines.
+///
+/// Predefined fields are emitted first using `time`, `level`, `logger`, `msg` and `caller` keys,
+/// followed by the rest of the fields in their original order.
+/// Nested objects are flattened using dotted keys, e.g. `a.b=1`.
+#[derive(Default)]
+pub struct LogfmtRecordFormatter {
+    hide_empty_fields: bool,
+    fields: Arc<IncludeExcludeKeyFilter>,
+    field_quote: FieldQuote,
+}
+
+impl LogfmtRecordFormatter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_empty_fields_hiding(self, enabled: bool) -> Self {
+        Self {
+            hide_empty_fields: enabled,
+            ..self
+        }
+    }
+
+    pub fn with_field_filter(self, value: Arc<IncludeExcludeKeyFilter>) -> Self {
+        Self { fields: value, ..self }
+    }
+
+    /// Sets when field values are quoted, with [`FieldQuote::Auto`] quoting only the values that need it to be parsed back.
+    pub fn with_field_quote(self, value: FieldQuote) -> Self {
+        Self {
+            field_quote: value,
+            ..self
+        }
+    }
+
+    pub fn format_record(&self, buf: &mut Buf, rec: &model::Record) {
+        let begin = buf.len();
+        let mut prefix = String::new();
+
+        if let Some(ts) = &rec.ts {
+            pair(buf, begin, "time", |buf| format_str(buf, EncodedString::raw(ts.raw())));
+        }
+
+        if let Some(level) = rec.level {
+            pair(buf, begin, "level", |buf| buf.extend(level.as_ref().as_bytes()));
+        }
+
+        if let Some(logger) = rec.logger {
+            pair(buf, begin, "logger", |buf| format_str(buf, EncodedString::raw(logger)));
+        }
+
+        if let Some(value) = rec.message {
+            self.format_field(
+                buf,
+                begin,
+                &mut prefix,
+                "msg",
+                value,
+                None,
+                IncludeExcludeSetting::Unspecified,
+            );
+        }
+
+        for (k, v) in rec.fields() {
+            if !self.hide_empty_fields || !v.is_empty() {
+                self.format_field(
+                    buf,
+                    begin,
+                    &mut prefix,
+                    k,
+                    *v,
+                    Some(&self.fields),
+                    IncludeExcludeSetting::Unspecified,
+                );
+            }
+        }
+
+        let caller = &rec.caller;
+        if !caller.is_empty() {
+            pair(buf, begin, "caller", |buf| {
+                let mut caller_buf = String::new();
+                caller_buf.push_str(caller.name);
+                if !caller.file.is_empty() || !caller.line.is_empty() {
+                    if !caller.name.is_empty() {
+                        caller_buf.push(' ');
+                    }
+                    caller_buf.push_str(caller.file);
+                    if !caller.line.is_empty() {
+                        caller_buf.push(':');
+                        caller_buf.push_str(caller.line);
+                    }
+                }
+                format_str(buf, EncodedString::raw(&caller_buf))
+            });
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn format_field(
+        &self,
+        buf: &mut Buf,
+        begin: usize,
+        prefix: &mut String,
+        key: &str,
+        value: RawValue,
+        filter: Option<&IncludeExcludeKeyFilter>,
+        setting: IncludeExcludeSetting,
+    ) {
+        let (filter, setting, leaf) = match filter {
+            Some(filter) => {
+                let setting = setting.apply(filter.setting());
+                match filter.get(key) {
+                    Some(filter) => (Some(filter), setting.apply(filter.setting()), filter.leaf()),
+                    None => (None, setting, true),
+                }
+            }
+            None => (None, setting, true),
+        };
+        if setting == IncludeExcludeSetting::Exclude && leaf {
+            return;
+        }
+
+        if let RawValue::Object(value) = value {
+            let mut item = model::Object::default();
+            value.parse_into(&mut item).ok();
+            let n = prefix.len();
+            prefix.push_str(key);
+            prefix.push('.');
+            for (k, v) in item.fields.iter() {
+                if !self.hide_empty_fields || !v.is_empty() {
+                    self.format_field(buf, begin, prefix, k, *v, filter, setting);
+                }
+            }
+            prefix.truncate(n);
+            return;
+        }
+
+        if begin != buf.len() {
+            buf.push(b' ');
+        }
+        buf.extend(prefix.as_bytes());
+        buf.extend(key.as_bytes());
+        buf.push(b'=');
+        format_value(buf, value, self.field_quote);
+    }
+}
+
+impl RecordWithSourceFormatter for LogfmtRecordFormatter {
+    #[inline]
+    fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        LogfmtRecordFormatter::format_record(self, buf, rec.record)
+    }
+}
+
+// ---
+
+#[inline]
+fn pair(buf: &mut Buf, begin: usize, key: &str, value: impl FnOnce(&mut Buf)) {
+    if begin != buf.len() {
+        buf.push(b' ');
+    }
+    buf.extend(key.as_bytes());
+    buf.push(b'=');
+    value(buf);
+}
+
+fn format_value(buf: &mut Buf, value: RawValue, quote: FieldQuote) {
+    match value {
+        RawValue::String(value) => format_str_quoted(buf, value, quote),
+        RawValue::Number(value) => buf.extend(value.as_bytes()),
+        RawValue::Boolean(true) => buf.extend(b"true"),
+        RawValue::Boolean(false) => buf.extend(b"false"),
+        RawValue::Null => buf.extend(b"null"),
+        RawValue::Object(value) => format_str_quoted(buf, EncodedString::raw(value.get()), quote),
+        RawValue::Array(value) => format_str_quoted(buf, EncodedString::raw(value.get()), quote),
+    }
+}
+
+fn format_str(buf: &mut Buf, value: EncodedString) {
+    format_str_quoted(buf, value, FieldQuote::Auto)
+}
+
+fn format_str_quoted(buf: &mut Buf, value: EncodedString, quote: FieldQuote) {
+    let begin = buf.len();
+    let quote = match quote {
+        FieldQuote::Auto => ValueFormatRaw.format(value, buf).is_err() || needs_quoting(&buf[begin..]),
+        FieldQuote::Always => true,
+        FieldQuote::Never => ValueFormatRaw.format(value, buf).is_err(),
+    };
+    if !quote {
+        return;
+    }
+
+    buf.truncate(begin);
+    if ValueFormatDoubleQuoted.format(value, buf).is_err() {
+        buf.truncate(begin);
+        buf.extend(br#""""#);
+    }
+}
+
+#[inline]
+fn needs_quoting(value: &[u8]) -> bool {
+    value.is_empty() || value.iter().any(|&b| b <= b' ' || b == b'=' || b == b'"' || b == 0x7f)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+use super::*;
+use crate::{
+    filtering::MatchOptions,
+    model::{Caller, Level, RawArray, RawObject, Record, RecordFields},
+    timestamp::Timestamp,
+};
+use serde_json as json;
+
+fn json_raw_value(s: &str) -> Box<json::value::RawValue> {
+    json::value::RawValue::from_string(s.into()).unwrap()
+}
+
+fn format(formatter: &LogfmtRecordFormatter, rec: &Record) -> String {
+    let mut buf = Vec::new();
+    formatter.format_record(&mut buf, rec);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_predefined_fields() {
+    let rec = Record {
+        ts: Some(Timestamp::new("2000-01-02T03:04:05.123Z")),
+        message: Some(RawValue::String(EncodedString::json(r#""some message""#))),
+        level: Some(Level::Warning),
+        logger: Some("tl"),
+        caller: Caller::with_name("tc"),
+        fields: RecordFields::from_slice(&[("k", RawValue::Number("42"))]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        format(&LogfmtRecordFormatter::new(), &rec),
+        r#"time=2000-01-02T03:04:05.123Z level=warning logger=tl msg="some message" k=42 caller=tc"#
+    );
+}
+
+#[test]
+fn test_quoting() {
+    let rec = Record {
+        fields: RecordFields::from_slice(&[
+            ("a", RawValue::String(EncodedString::json(r#""x=y""#))),
+            ("b", RawValue::String(EncodedString::json(r#""say \"hi\"""#))),
+            ("c", RawValue::String(EncodedString::json(r#""""#))),
+            ("d", RawValue::String(EncodedString::raw("plain"))),
+            ("e", RawValue::String(EncodedString::json(r#""line\nbreak""#))),
+            ("f", RawValue::Boolean(true)),
+            ("g", RawValue::Null),
+        ]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        format(&LogfmtRecordFormatter::new(), &rec),
+        r#"a="x=y" b="say \"hi\"" c="" d=plain e="line\nbreak" f=true g=null"#
+    );
+}
+
+#[test]
+fn test_field_quote() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::raw("done"))),
+        fields: RecordFields::from_slice(&[
+            ("a", RawValue::String(EncodedString::raw("x y"))),
+            ("b", RawValue::Number("42")),
+        ]),
+        ..Default::default()
+    };
+
+    let formatter = LogfmtRecordFormatter::new().with_field_quote(FieldQuote::Always);
+    assert_eq!(format(&formatter, &rec), r#"msg="done" a="x y" b=42"#);
+
+    let formatter = LogfmtRecordFormatter::new().with_field_quote(FieldQuote::Never);
+    assert_eq!(format(&formatter, &rec), r#"msg=done a=x y b=42"#);
+}
+
+#[test]
+fn test_nested_objects() {
+    let obj = json_raw_value(r#"{"b":{"c":1,"d":"x y"}}"#);
+    let arr = json_raw_value(r#"[1, 2]"#);
+    let rec = Record {
+        fields: RecordFields::from_slice(&[
+            ("a", RawValue::from(RawObject::Json(&obj))),
+            ("e", RawValue::Array(RawArray::Json(&arr))),
+        ]),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        format(&LogfmtRecordFormatter::new(), &rec),
+        r#"a.b.c=1 a.b.d="x y" e="[1, 2]""#
+    );
+}
+
+#[test]
+fn test_hidden_fields() {
+    let obj = json_raw_value(r#"{"b":1,"c":2}"#);
+    let rec = Record {
+        fields: RecordFields::from_slice(&[
+            ("a", RawValue::from(RawObject::Json(&obj))),
+            ("d", RawValue::String(EncodedString::raw(""))),
+            ("e", RawValue::Number("3")),
+        ]),
+        ..Default::default()
+    };
+
+    let mut filter = IncludeExcludeKeyFilter::new(MatchOptions::default());
+    filter.entry("a.c").exclude();
+    filter.entry("e").exclude();
+
+    let formatter = LogfmtRecordFormatter::new()
+        .with_field_filter(Arc::new(filter))
+        .with_empty_fields_hiding(true);
+
+    assert_eq!(format(&formatter, &rec), "a.b=1");
+}
@@ -0,0 +1,81 @@
+/// Part of a message template, e.g. `User {UserId} logged in`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// Literal text, with `{{` and `}}` already unescaped.
+    Text(&'a str),
+    /// Named placeholder, `source` is the whole placeholder including braces, format and alignment,
+    /// e.g. `{@Elapsed,8:0.00}` has the name `Elapsed`.
+    Placeholder { source: &'a str, name: &'a str },
+}
+
+/// Splits a [message template](https://messagetemplates.org/) into literal text and placeholders.
+///
+/// Text that looks like a placeholder but is not a valid one is kept as literal text.
+pub fn tokens(template: &str) -> Tokens<'_> {
+    Tokens { rest: template }
+}
+
+/// Returns true if the text contains at least one placeholder.
+pub fn has_placeholders(template: &str) -> bool {
+    template.contains('{') && tokens(template).any(|token| matches!(token, Token::Placeholder { .. }))
+}
+
+// ---
+
+pub struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest;
+        if rest.is_empty() {
+            return None;
+        }
+
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            self.rest = &rest[2..];
+            return Some(Token::Text(&rest[..1]));
+        }
+
+        if rest.starts_with('{') {
+            if let Some(end) = rest.find('}') {
+                if let Some(name) = placeholder_name(&rest[1..end]) {
+                    self.rest = &rest[end + 1..];
+                    return Some(Token::Placeholder {
+                        source: &rest[..end + 1],
+                        name,
+                    });
+                }
+            }
+        }
+
+        // literal text up to the next brace, a brace that does not start a placeholder is literal text as well
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let end = rest[first..].find(['{', '}']).map_or(rest.len(), |i| i + first);
+        self.rest = &rest[end..];
+        Some(Token::Text(&rest[..end]))
+    }
+}
+
+/// Returns the name of the placeholder given its content between braces,
+/// skipping the `@` and `$` capturing hints and the alignment and format specifiers.
+fn placeholder_name(content: &str) -> Option<&str> {
+    let content = content.strip_prefix(['@', '$']).unwrap_or(content);
+    let name = match content.find([',', ':']) {
+        Some(i) => &content[..i],
+        None => content,
+    };
+
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '-');
+
+    valid.then_some(name)
+}
+
+#[cfg(test)]
+mod tests;
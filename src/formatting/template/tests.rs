@@ -0,0 +1,68 @@
+use super::*;
+
+fn collect(template: &str) -> Vec<Token<'_>> {
+    tokens(template).collect()
+}
+
+fn placeholder<'a>(source: &'a str, name: &'a str) -> Token<'a> {
+    Token::Placeholder { source, name }
+}
+
+#[test]
+fn test_tokens() {
+    assert_eq!(
+        collect("User {UserId} did {Action}"),
+        vec![
+            Token::Text("User "),
+            placeholder("{UserId}", "UserId"),
+            Token::Text(" did "),
+            placeholder("{Action}", "Action"),
+        ]
+    );
+    assert_eq!(collect("no placeholders"), vec![Token::Text("no placeholders")]);
+    assert_eq!(
+        collect("привет {Имя}"),
+        vec![Token::Text("привет "), placeholder("{Имя}", "Имя")]
+    );
+    assert_eq!(collect(""), vec![]);
+}
+
+#[test]
+fn test_tokens_specifiers() {
+    assert_eq!(
+        collect("{@Position} in {Elapsed:000} ms, {$Kind,-8}"),
+        vec![
+            placeholder("{@Position}", "Position"),
+            Token::Text(" in "),
+            placeholder("{Elapsed:000}", "Elapsed"),
+            Token::Text(" ms, "),
+            placeholder("{$Kind,-8}", "Kind"),
+        ]
+    );
+}
+
+#[test]
+fn test_tokens_escaped_and_invalid() {
+    assert_eq!(
+        collect("{{literal}} {not valid} {} {a"),
+        vec![
+            Token::Text("{"),
+            Token::Text("literal"),
+            Token::Text("}"),
+            Token::Text(" "),
+            Token::Text("{not valid"),
+            Token::Text("} "),
+            Token::Text("{"),
+            Token::Text("} "),
+            Token::Text("{a"),
+        ]
+    );
+}
+
+#[test]
+fn test_has_placeholders() {
+    assert!(has_placeholders("a {b} c"));
+    assert!(!has_placeholders("a {{b}} c"));
+    assert!(!has_placeholders("a { b } c"));
+    assert!(!has_placeholders("plain"));
+}
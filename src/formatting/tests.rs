@@ -1,7 +1,7 @@
 use super::{string::new_message_format, *};
 use crate::{
     datefmt::LinuxDateFormat,
-    model::{Caller, RawObject, Record, RecordFields, RecordWithSourceConstructor},
+    model::{Caller, Parser, ParserSettings, RawObject, RawRecord, Record, RecordFields, RecordWithSourceConstructor},
     settings::{AsciiMode, MessageFormat, MessageFormatting},
     testing::Sample,
     timestamp::Timestamp,
@@ -9,6 +9,7 @@ use crate::{
 };
 use chrono::{Offset, Utc};
 use encstr::EncodedString;
+use rstest::rstest;
 use serde_json as json;
 
 trait FormatToVec {
@@ -98,6 +99,306 @@ fn test_nested_objects() {
     );
 }
 
+#[test]
+fn test_format_to_writer() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","msg":"tm","k":1}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let mut out = Vec::new();
+    formatter()
+        .with_theme(Default::default())
+        .build()
+        .format(&rec, &mut out)
+        .unwrap();
+
+    assert_eq!(std::str::from_utf8(&out).unwrap(), "00-01-02 03:04:05.123 |INF| tm k=1");
+}
+
+#[test]
+fn test_max_widths() {
+    let parser = Parser::new(ParserSettings::default());
+    let input =
+        br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","msg":"helloworld","a":"abcdefgh","b":"abc","c":12345678}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_max_message_width(Some(5))
+        .with_max_field_width(Some(3))
+        .build();
+
+    assert_eq!(
+        formatter.format_to_string(&rec),
+        "00-01-02 03:04:05.123 |INF| hello... a=abc... b=abc c=12345678"
+    );
+}
+
+#[test]
+fn test_error_key() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"error","msg":"failed","error":{"message":"boom","stack":"at a (a.js:1)\n  at b (b.js:2)\n","cause":{"msg":"io","stack":["at c (c.js:3)"],"cause":"eof"}},"x":1}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let structured = formatter()
+        .with_theme(Default::default())
+        .with_error_key(Some("error".into()))
+        .build();
+
+    assert_eq!(
+        structured.format_to_string(&rec),
+        concat!(
+            "00-01-02 03:04:05.123 |ERR| failed error=boom x=1\n",
+            "    at a (a.js:1)\n",
+            "    at b (b.js:2)\n",
+            "  caused by: io\n",
+            "    at c (c.js:3)\n",
+            "  caused by: eof",
+        )
+    );
+
+    let text = format_no_color(&rec);
+    assert!(!text.contains('\n') && text.contains("boom"), "{text}");
+}
+
+#[test]
+fn test_json_expansion() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","msg":"{\"event\":\"x\"}","data":"{\"a\":\"{\\\"b\\\":1}\"}","q":"plain"}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let expanding = formatter()
+        .with_theme(Default::default())
+        .with_json_expansion(true)
+        .build();
+
+    assert_eq!(
+        expanding.format_to_string(&rec),
+        "00-01-02 03:04:05.123 |INF| msg={ event=x } data={ a={ b=1 } } q=plain"
+    );
+
+    let text = format_no_color(&rec);
+    assert!(!text.contains("event=x") && !text.contains("b=1"), "{text}");
+}
+
+#[test]
+fn test_json_expansion_message_key() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","message":"{\"event\":\"x\"}"}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let expanding = formatter()
+        .with_theme(Default::default())
+        .with_json_expansion(true)
+        .with_message_keys(vec!["msg".into(), "message".into()])
+        .build();
+
+    assert_eq!(
+        expanding.format_to_string(&rec),
+        "00-01-02 03:04:05.123 |INF| message={ event=x }"
+    );
+}
+
+#[test]
+fn test_template_rendering() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","msg":"User {UserId} did {@Action} in {Elapsed:0.0} ms {{ok}} {Unknown}","UserId":42,"Action":"log \"in\"","Elapsed":1.5,"x":"y"}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let rendering = formatter().with_theme(Default::default()).with_template_rendering(true);
+
+    assert_eq!(
+        rendering.clone().build().format_to_string(&rec),
+        concat!(
+            r#"00-01-02 03:04:05.123 |INF| User 42 did log "in" in 1.5 ms {ok} {Unknown} "#,
+            r#"UserId=42 Action='log "in"' Elapsed=1.5 x=y"#,
+        )
+    );
+    assert_eq!(
+        rendering
+            .with_template_fields_dropping(true)
+            .build()
+            .format_to_string(&rec),
+        r#"00-01-02 03:04:05.123 |INF| User 42 did log "in" in 1.5 ms {ok} {Unknown} x=y"#
+    );
+
+    let text = format_no_color(&rec);
+    assert!(text.contains("{UserId}"), "{text}");
+}
+
+#[test]
+fn test_template_rendering_styles() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(r#""n={n} s={s}""#))),
+        fields: RecordFields::from_slice(&[
+            ("n", RawValue::Number("1")),
+            ("s", RawValue::String(EncodedString::json(r#""v""#))),
+        ]),
+        ..Default::default()
+    };
+
+    let formatter = formatter().with_template_rendering(true).build();
+    assert_eq!(
+        formatter.format_to_string(&rec),
+        "\u{1b}[0;1;39mn=\u{1b}[0;94m1\u{1b}[0;1;39m s=\u{1b}[0;39mv \u{1b}[0;32mn\u{1b}[0;2m=\u{1b}[0;94m1 \u{1b}[0;32ms\u{1b}[0;2m=\u{1b}[0;39mv\u{1b}[0m",
+    );
+}
+
+#[test]
+fn test_ansi_stripping() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(
+            r#""\u001b[31merror\u001b[0m occurred""#,
+        ))),
+        fields: RecordFields::from_slice(&[
+            (
+                "a",
+                RawValue::String(EncodedString::raw("\x1b]8;;x\x07link\x1b]8;;\x07")),
+            ),
+            ("b", RawValue::String(EncodedString::json(r#""\u001b[1m""#))),
+        ]),
+        ..Default::default()
+    };
+
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_ansi_stripping(true)
+        .build();
+    assert_eq!(formatter.format_to_string(&rec), r#"error occurred a=link b="""#);
+
+    let text = format_no_color(&rec);
+    assert!(text.contains("31m"), "{text}");
+}
+
+#[test]
+fn test_pretty_layout() {
+    let ka = json_raw_value(r#"{"va":{"kb":42,"kc":[43]},"vd":{}}"#);
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(r#""tm""#))),
+        caller: Caller::with_name("tc"),
+        fields: RecordFields::from_slice(&[
+            ("k_a", RawValue::from(RawObject::Json(&ka))),
+            ("k_b", RawValue::Number("1")),
+        ]),
+        ..Default::default()
+    };
+
+    let formatter = || formatter().with_theme(Default::default()).with_pretty_layout(true);
+
+    assert_eq!(
+        formatter().build().format_to_string(&rec),
+        "tm -> tc\n  k-a:\n    va:\n      kb=42\n      kc=[43]\n    vd={}\n  k-b=1",
+    );
+    assert_eq!(
+        formatter().with_flatten(true).build().format_to_string(&rec),
+        "tm -> tc\n  k-a.va.kb=42\n  k-a.va.kc=[43]\n  k-b=1",
+    );
+
+    let mut punctuation = crate::settings::Punctuation::sample().resolve(AsciiMode::On);
+    punctuation.field_key_value_separator = ": ".into();
+    assert_eq!(
+        formatter()
+            .with_punctuation(Arc::new(punctuation))
+            .build()
+            .format_to_string(&rec),
+        "tm -> tc\n  k-a:\n    va:\n      kb: 42\n      kc: [43]\n    vd: {}\n  k-b: 1",
+    );
+}
+
+#[test]
+fn test_message_column_alignment() {
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_always_show_level(true)
+        .with_timestamp_formatter(DateTimeFormatter::new(
+            LinuxDateFormat::new("%y年%m月%d日 %T").compile(),
+            Tz::FixedOffset(Utc.fix()),
+        ))
+        .build();
+
+    let levels = [
+        Some(Level::Error),
+        Some(Level::Warning),
+        Some(Level::Info),
+        Some(Level::Debug),
+        Some(Level::Trace),
+        None,
+    ];
+    let columns = levels
+        .iter()
+        .flat_map(|&level| ["2000-01-02T03:04:05.123Z", "昨日"].map(move |ts| (level, ts)))
+        .map(|(level, ts)| {
+            let rec = Record {
+                ts: Some(Timestamp::new(ts)),
+                level,
+                message: Some(RawValue::String(EncodedString::raw("MSG"))),
+                ..Default::default()
+            };
+            let text = formatter.format_to_string(&rec);
+            let at = text.find("MSG").unwrap();
+            wrap::display_width(&text.as_bytes()[..at])
+        })
+        .collect::<Vec<_>>();
+
+    assert!(columns.iter().all(|&column| column == columns[0]), "{columns:?}");
+}
+
+#[test]
+fn test_wrap() {
+    let parser = Parser::new(ParserSettings::default());
+    let input = br#"{"ts":"2000-01-02T03:04:05.123Z","level":"info","msg":"hello","a":"first","b":"second","c":"third","d":"fourth"}"#;
+    let raw = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let rec = parser.parse(&raw.record);
+
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_wrapper(wrap::Wrapper::new(wrap::WrapMode::Word, 60))
+        .build();
+
+    assert_eq!(
+        formatter.format_to_string(&rec),
+        concat!(
+            "00-01-02 03:04:05.123 |INF| hello a=first b=second c=third\n",
+            "                            d=fourth",
+        )
+    );
+}
+
+#[test]
+fn test_highlight() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(r#""timeout-m""#))),
+        fields: RecordFields::from_slice(&[("k", RawValue::String(EncodedString::json(r#""x-timeout-y""#)))]),
+        ..Default::default()
+    };
+    let highlighter = Highlighter::new(["timeout"]).unwrap().map(Arc::new);
+
+    assert_eq!(
+        &formatter().with_highlighter(highlighter).build().format_to_string(&rec),
+        "\u{1b}[0;1;39m\u{1b}[0;7mtimeout\u{1b}[0;1;39m-m \u{1b}[0;32mk\u{1b}[0;2m=\u{1b}[0;39mx-\u{1b}[0;7mtimeout\u{1b}[0;39m-y\u{1b}[0m",
+    );
+}
+
+#[test]
+fn test_highlight_no_match() {
+    let rec = Record {
+        message: Some(RawValue::String(EncodedString::json(r#""tm""#))),
+        ..Default::default()
+    };
+    let highlighter = Highlighter::new(["timeout"]).unwrap().map(Arc::new);
+
+    assert_eq!(
+        &formatter().with_highlighter(highlighter).build().format_to_string(&rec),
+        "\u{1b}[0;1;39mtm\u{1b}[0m",
+    );
+}
+
 #[test]
 fn test_timestamp_none() {
     let rec = Record {
@@ -477,6 +778,99 @@ fn test_message_spaces_only() {
     assert_eq!(&result, r#""#, "{}", result);
 }
 
+#[test]
+fn test_pinned_fields() {
+    let rec = Record::from_fields(&[
+        ("a", RawValue::Number("1")),
+        ("request_id", RawValue::Number("2")),
+        ("b", RawValue::Number("3")),
+        ("user_id", RawValue::Number("4")),
+    ]);
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_pinned_fields(vec!["user_id".into(), "missing".into(), "request_id".into()])
+        .build();
+
+    assert_eq!(&formatter.format_to_string(&rec), "user-id=4 request-id=2 a=1 b=3");
+}
+
+#[test]
+fn test_color_by() {
+    let rec = |service| {
+        Record::from_fields(&[
+            ("service", RawValue::String(EncodedString::json(service))),
+            ("a", RawValue::Number("1")),
+        ])
+    };
+    let api = rec(r#""api""#);
+    let db = rec(r#""db""#);
+    let none = Record::from_fields(&[("a", RawValue::Number("1"))]);
+
+    let formatter = formatter().with_color_by(Some("service".into()));
+    let colored = formatter.clone().build();
+    let plain = formatter.with_theme(Default::default()).build();
+
+    assert_eq!(&plain.format_to_string(&api), "| service=api a=1");
+    assert_eq!(&plain.format_to_string(&none), "  a=1");
+    assert!(
+        colored
+            .format_to_string(&api)
+            .starts_with("\u{1b}[0;38;5;29m| \u{1b}[0m")
+    );
+    assert!(
+        colored
+            .format_to_string(&db)
+            .starts_with("\u{1b}[0;38;5;107m| \u{1b}[0m")
+    );
+    assert_eq!(colored.format_to_string(&api), colored.format_to_string(&api));
+}
+
+#[test]
+fn test_rules() {
+    let style = |background, foreground| themecfg::Style {
+        background,
+        foreground,
+        ..Default::default()
+    };
+    let rules = vec![
+        StyleRule {
+            query: Query::parse(".status>=500").unwrap(),
+            field: None,
+            style: style(Some(themecfg::Color::Plain(themecfg::PlainColor::Red)), None),
+        },
+        StyleRule {
+            query: Query::parse(".user=admin").unwrap(),
+            field: Some("user".into()),
+            style: style(None, Some(themecfg::Color::Palette(214))),
+        },
+    ];
+    let rec = |status, user| {
+        Record::from_fields(&[
+            ("status", RawValue::Number(status)),
+            ("user", EncodedString::raw(user).into()),
+        ])
+    };
+
+    let plain = formatter().build();
+    let styled = formatter().with_rules(rules.clone()).build();
+
+    let ok = rec("200", "guest");
+    assert_eq!(styled.format_to_string(&ok), plain.format_to_string(&ok));
+
+    let failed = styled.format_to_string(&rec("503", "admin"));
+    assert!(failed.starts_with("\u{1b}[0;41"));
+    assert!(failed.ends_with("\u{1b}[0m"));
+    assert!(!failed.contains("38;5;214"));
+
+    let admin = styled.format_to_string(&rec("200", "admin"));
+    assert_ne!(admin, plain.format_to_string(&rec("200", "admin")));
+    assert!(admin.contains("38;5;214"));
+    assert!(!admin.contains(";41"));
+
+    let no_color = formatter().with_rules(rules).with_theme(Default::default()).build();
+    assert_eq!(no_color.format_to_string(&rec("503", "admin")), "status=503 user=admin");
+}
+
 #[test]
 fn test_nested_hidden_fields_flatten() {
     let val = json_raw_value(r#"{"b":{"c":{"d":1,"e":2},"f":3}}"#);
@@ -496,6 +890,61 @@ fn test_nested_hidden_fields_flatten() {
     assert_eq!(&formatter.format_to_string(&rec), "a.b.c.d=1 ...");
 }
 
+#[test]
+fn test_allowlist_with_exclusions() {
+    let val = json_raw_value(r#"{"method":"GET","headers":{"accept":"*/*"}}"#);
+    let rec = Record::from_fields(&[
+        ("req", RawValue::Number("1")),
+        ("request", RawObject::Json(&val).into()),
+        ("user", RawValue::String(EncodedString::raw("x"))),
+    ]);
+    let mut fields = IncludeExcludeKeyFilter::default().excluded();
+    fields.entry("req*").include();
+    fields.entry("request.headers").exclude();
+    let formatter = RecordFormatterBuilder {
+        flatten: true,
+        theme: Some(Default::default()), // No theme for consistent test output
+        fields: Some(fields.into()),
+        ..formatter()
+    }
+    .build();
+
+    assert_eq!(&formatter.format_to_string(&rec), "req=1 request.method=GET ...");
+}
+
+#[rstest]
+#[case(None, "a.b.c.d=1 a.b.c.e=2 a.f=3")]
+#[case(Some(0), r#"a={"b":{"c":{"d":1,"e":2}},"f":3}"#)]
+#[case(Some(1), r#"a.b={"c":{"d":1,"e":2}} a.f=3"#)]
+#[case(Some(2), r#"a.b.c={"d":1,"e":2} a.f=3"#)]
+#[case(Some(3), "a.b.c.d=1 a.b.c.e=2 a.f=3")]
+fn test_flatten_depth(#[case] depth: Option<usize>, #[case] expected: &str) {
+    let val = json_raw_value(r#"{"b": {"c": {"d": 1, "e": 2}}, "f": 3}"#);
+    let rec = Record::from_fields(&[("a", RawObject::Json(&val).into())]);
+    let formatter = RecordFormatterBuilder {
+        flatten: true,
+        flatten_depth: depth,
+        theme: Some(Default::default()), // No theme for consistent test output
+        ..formatter()
+    }
+    .build();
+
+    assert_eq!(&formatter.format_to_string(&rec), expected);
+}
+
+#[test]
+fn test_flatten_depth_strings() {
+    let val = json_raw_value(r#"{"b": {"c": "x y", "d": "\" }"}}"#);
+    let rec = Record::from_fields(&[("a", RawObject::Json(&val).into())]);
+    let formatter = formatter()
+        .with_theme(Default::default())
+        .with_flatten(true)
+        .with_flatten_depth(Some(1))
+        .build();
+
+    assert_eq!(&formatter.format_to_string(&rec), r#"a.b={"c":"x y","d":"\" }"}"#);
+}
+
 #[test]
 fn test_nested_hidden_fields_group_unhide() {
     let val = json_raw_value(r#"{"b":{"c":{"d":1,"e":2},"f":3}}"#);
@@ -680,6 +1129,29 @@ fn test_raw_message() {
     assert_eq!(formatter.format_to_string(&rec), r#"m 1"#);
 }
 
+#[test]
+fn test_field_quote() {
+    let rec = Record {
+        fields: RecordFields::from_slice(&[
+            ("a", RawValue::String(EncodedString::raw("x y"))),
+            ("b", RawValue::String(EncodedString::raw("plain"))),
+            ("c", RawValue::Number("42")),
+        ]),
+        ..Default::default()
+    };
+    let format = |quote| {
+        formatter()
+            .with_theme(Default::default())
+            .with_field_quote(quote)
+            .build()
+            .format_to_string(&rec)
+    };
+
+    assert_eq!(format(FieldQuote::Auto), r#"a="x y" b=plain c=42"#);
+    assert_eq!(format(FieldQuote::Always), r#"a="x y" b="plain" c=42"#);
+    assert_eq!(format(FieldQuote::Never), r#"a=x y b=plain c=42"#);
+}
+
 #[test]
 fn test_delimited_message() {
     let formatter = formatter()
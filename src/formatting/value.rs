@@ -0,0 +1,80 @@
+// std imports
+use std::sync::Arc;
+
+// workspace imports
+use encstr::AnyEncodedString;
+
+// local imports
+use crate::{
+    model::{self, RawValue},
+    theme::{Element, StylingPush, Theme},
+};
+
+// relative imports
+use super::{Buf, RecordWithSourceFormatter};
+
+// ---
+
+/// Formats records as the bare value of a single field, e.g. the message.
+///
+/// Strings are decoded and written without quotes, other values are written as they appear in the input.
+/// A record without the field produces an empty line.
+pub struct ValueRecordFormatter {
+    key: String,
+    theme: Arc<Theme>,
+}
+
+impl ValueRecordFormatter {
+    pub fn new(key: String) -> Self {
+        Self {
+            key,
+            theme: Arc::new(Theme::none()),
+        }
+    }
+
+    /// Sets the theme used to style the value, which is written without styles by default.
+    pub fn with_theme(self, theme: Arc<Theme>) -> Self {
+        Self { theme, ..self }
+    }
+
+    pub fn format_record(&self, buf: &mut Buf, rec: &model::Record) {
+        let Some(value) = rec.get(&self.key) else {
+            return;
+        };
+
+        let element = match value {
+            _ if rec.message == Some(value) => Element::Message,
+            RawValue::String(_) => Element::String,
+            RawValue::Number(_) => Element::Number,
+            RawValue::Boolean(_) => Element::Boolean,
+            RawValue::Null => Element::Null,
+            RawValue::Object(_) => Element::Object,
+            RawValue::Array(_) => Element::Array,
+        };
+
+        self.theme.apply(buf, &rec.level, |s| {
+            s.element(element, |s| {
+                s.batch(|buf| match value {
+                    RawValue::String(value) => {
+                        let begin = buf.len();
+                        if value.decode(&mut *buf).is_err() {
+                            buf.truncate(begin);
+                            buf.extend(value.source().as_bytes());
+                        }
+                    }
+                    _ => buf.extend(value.raw_str().as_bytes()),
+                })
+            })
+        });
+    }
+}
+
+impl RecordWithSourceFormatter for ValueRecordFormatter {
+    #[inline]
+    fn format_record(&self, buf: &mut Buf, rec: model::RecordWithSource) {
+        ValueRecordFormatter::format_record(self, buf, rec.record)
+    }
+}
+
+#[cfg(test)]
+mod tests;
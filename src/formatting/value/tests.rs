@@ -0,0 +1,42 @@
+use super::*;
+use crate::model::{RawObject, Record, RecordFields};
+use encstr::EncodedString;
+use serde_json as json;
+
+fn format(key: &str, rec: &Record) -> String {
+    let mut buf = Vec::new();
+    ValueRecordFormatter::new(key.into()).format_record(&mut buf, rec);
+    String::from_utf8(buf).unwrap()
+}
+
+fn record() -> Record<'static> {
+    Record {
+        message: Some(RawValue::String(EncodedString::json(r#""hello \"world\"""#))),
+        fields: RecordFields::from_slice(&[
+            ("n", RawValue::Number("42")),
+            (
+                "ctx",
+                RawValue::Object(RawObject::Json(json::from_str(r#"{"a":1}"#).unwrap())),
+            ),
+        ]),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_message() {
+    assert_eq!(format("msg", &record()), r#"hello "world""#);
+    assert_eq!(format("message", &record()), r#"hello "world""#);
+}
+
+#[test]
+fn test_field() {
+    assert_eq!(format("n", &record()), "42");
+    assert_eq!(format("ctx", &record()), r#"{"a":1}"#);
+    assert_eq!(format("ctx.a", &record()), "1");
+}
+
+#[test]
+fn test_missing() {
+    assert_eq!(format("other", &record()), "");
+}
@@ -0,0 +1,250 @@
+// std imports
+use std::ops::Range;
+
+// third-party imports
+use unicode_width::UnicodeWidthChar;
+
+// ---
+
+/// Defines how long formatted lines are wrapped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Lines are never wrapped.
+    #[default]
+    Never,
+    /// Lines are wrapped at word boundaries, words longer than the width are wrapped hard.
+    Word,
+    /// Lines are wrapped hard at the width.
+    Char,
+}
+
+/// Wraps formatted lines at the given width.
+///
+/// ANSI escape sequences do not take up space, and the active style is restored on each continuation line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wrapper {
+    mode: WrapMode,
+    width: usize,
+}
+
+impl Wrapper {
+    /// Returns `None` if wrapping is disabled by the mode or the width is zero.
+    pub fn new(mode: WrapMode, width: usize) -> Option<Self> {
+        (mode != WrapMode::Never && width != 0).then_some(Self { mode, width })
+    }
+
+    /// Appends the wrapped text to the output, indenting continuation lines by the given number of columns.
+    pub fn wrap(&self, text: &[u8], indent: usize, out: &mut Vec<u8>) {
+        // the indent is dropped if it leaves too little room for the text
+        let indent = if indent * 2 > self.width { 0 } else { indent };
+
+        let units = units(text);
+        let breaks = self.breaks(text, &units, indent);
+        if breaks.is_empty() {
+            out.extend(text);
+            return;
+        }
+
+        let mut style = Vec::new();
+        let mut breaks = breaks.into_iter().peekable();
+        let mut skip = false;
+        for (i, unit) in units.iter().enumerate() {
+            if let Some(&(at, skip_unit)) = breaks.peek() {
+                if at == i {
+                    breaks.next();
+                    if !style.is_empty() {
+                        out.extend(RESET);
+                    }
+                    out.push(b'\n');
+                    out.extend(std::iter::repeat_n(b' ', indent));
+                    out.extend(&style);
+                    skip = skip_unit;
+                }
+            }
+            let data = &text[unit.range.clone()];
+            match unit.kind {
+                UnitKind::Style => {
+                    update_style(&mut style, data);
+                    out.extend(data);
+                }
+                _ if skip => skip = false,
+                _ => out.extend(data),
+            }
+        }
+    }
+
+    /// Returns indexes of units to insert line breaks before, each with a flag telling whether the unit is skipped.
+    fn breaks(&self, text: &[u8], units: &[Unit], indent: usize) -> Vec<(usize, bool)> {
+        let word = self.mode == WrapMode::Word;
+        let mut result = Vec::new();
+        let mut col = 0;
+        // last space in the current line suitable for a break, with the number of columns taken after it
+        let mut space: Option<(usize, usize)> = None;
+
+        for (i, unit) in units.iter().enumerate() {
+            match unit.kind {
+                UnitKind::Style => continue,
+                UnitKind::Newline => {
+                    col = 0;
+                    space = None;
+                    continue;
+                }
+                UnitKind::Space | UnitKind::Char => {}
+            }
+
+            let width = unit.width(text);
+            if col + width > self.width {
+                if word && unit.kind == UnitKind::Space {
+                    result.push((i, true));
+                    col = indent;
+                    space = None;
+                    continue;
+                }
+                if let Some((at, after)) = space.take() {
+                    result.push((at, true));
+                    col = indent + after;
+                }
+                // a wide character not fitting even at the line start is left as is
+                if col > indent && col + width > self.width {
+                    result.push((i, false));
+                    col = indent;
+                }
+            }
+
+            if word && unit.kind == UnitKind::Space && col > indent {
+                space = Some((i, 0));
+            } else if let Some((_, after)) = &mut space {
+                *after += width;
+            }
+            col += width;
+        }
+
+        result
+    }
+}
+
+/// Returns the number of columns taken by the text, not counting escape sequences.
+///
+/// Wide characters take two columns and combining characters take none.
+pub fn display_width(text: &[u8]) -> usize {
+    units(text).iter().map(|unit| unit.width(text)).sum()
+}
+
+/// Truncates the text following `begin` to the given number of columns, keeping escape sequences.
+///
+/// A wide character that does not fit entirely is removed.
+/// Returns true if any characters were removed.
+pub fn truncate(buf: &mut Vec<u8>, begin: usize, width: usize) -> bool {
+    let mut col = 0;
+    let mut cut = None;
+    for unit in units(&buf[begin..]) {
+        let n = match unit.kind {
+            UnitKind::Style => continue,
+            UnitKind::Newline => 1,
+            UnitKind::Char | UnitKind::Space => unit.width(&buf[begin..]),
+        };
+        if col + n > width {
+            cut = Some(begin + unit.range.start);
+            break;
+        }
+        col += n;
+    }
+
+    match cut {
+        Some(end) => {
+            let tail = buf.split_off(end);
+            // keep escape sequences so the styles are still reset properly
+            for unit in units(&tail).into_iter().filter(|unit| unit.kind == UnitKind::Style) {
+                buf.extend_from_slice(&tail[unit.range]);
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+// ---
+
+const RESET: &[u8] = b"\x1b[0m";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UnitKind {
+    Char,
+    Space,
+    Newline,
+    Style,
+}
+
+#[derive(Debug)]
+struct Unit {
+    kind: UnitKind,
+    range: Range<usize>,
+}
+
+impl Unit {
+    /// Returns the number of columns taken by the unit of the given text.
+    ///
+    /// Wide characters take two columns, combining and control characters take none.
+    fn width(&self, text: &[u8]) -> usize {
+        match self.kind {
+            UnitKind::Char => match std::str::from_utf8(&text[self.range.clone()]) {
+                Ok(s) => s.chars().next().map_or(0, |c| c.width().unwrap_or(0)),
+                Err(_) => 1,
+            },
+            UnitKind::Space => 1,
+            UnitKind::Newline | UnitKind::Style => 0,
+        }
+    }
+}
+
+/// Splits the text into characters and escape sequences.
+fn units(text: &[u8]) -> Vec<Unit> {
+    let mut result = Vec::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        let (kind, n) = match text[i] {
+            b'\x1b' if text.get(i + 1) == Some(&b'[') => {
+                let n = text[i + 2..]
+                    .iter()
+                    .position(|b| (0x40..=0x7e).contains(b))
+                    .map_or(text.len() - i, |n| n + 3);
+                (UnitKind::Style, n)
+            }
+            b' ' => (UnitKind::Space, 1),
+            b'\n' => (UnitKind::Newline, 1),
+            b => (UnitKind::Char, utf8_len(b).min(text.len() - i)),
+        };
+        result.push(Unit { kind, range: i..i + n });
+        i += n;
+    }
+    result
+}
+
+/// Accounts the escape sequence in the style to be restored after a line break.
+fn update_style(style: &mut Vec<u8>, seq: &[u8]) {
+    if !seq.ends_with(b"m") {
+        return;
+    }
+
+    let params = &seq[2..seq.len() - 1];
+    if params.is_empty() || params == b"0" || params.starts_with(b"0;") {
+        style.clear();
+        if params.len() <= 1 {
+            return;
+        }
+    }
+    style.extend(seq);
+}
+
+#[inline]
+fn utf8_len(b: u8) -> usize {
+    match b {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests;
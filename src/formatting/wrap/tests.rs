@@ -0,0 +1,74 @@
+use super::*;
+
+use rstest::rstest;
+
+fn wrap(mode: WrapMode, width: usize, indent: usize, text: &str) -> String {
+    let mut out = Vec::new();
+    Wrapper::new(mode, width)
+        .unwrap()
+        .wrap(text.as_bytes(), indent, &mut out);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_wrapper_disabled() {
+    assert_eq!(Wrapper::new(WrapMode::Never, 80), None);
+    assert_eq!(Wrapper::new(WrapMode::Word, 0), None);
+}
+
+#[rstest]
+#[case(WrapMode::Char, 4, 0, "abcdefghij", "abcd\nefgh\nij")]
+#[case(WrapMode::Char, 10, 2, "aaa bbb ccc ddd", "aaa bbb cc\n  c ddd")]
+#[case(WrapMode::Word, 10, 2, "aaa bbb ccc ddd", "aaa bbb\n  ccc ddd")]
+#[case(WrapMode::Word, 7, 0, "aaa bbb ccc", "aaa bbb\nccc")]
+#[case(WrapMode::Word, 3, 0, "abcdefgh", "abc\ndef\ngh")]
+#[case(WrapMode::Word, 10, 0, "short", "short")]
+#[case(WrapMode::Word, 4, 0, "ab\ncdef gh", "ab\ncdef\ngh")]
+#[case(WrapMode::Word, 10, 6, "aaa bbb ccc", "aaa bbb\nccc")]
+#[case(WrapMode::Char, 2, 0, "ééé", "éé\né")]
+#[case(WrapMode::Char, 4, 0, "日本語テキ", "日本\n語テ\nキ")]
+#[case(WrapMode::Char, 5, 0, "日本語", "日本\n語")]
+#[case(WrapMode::Char, 1, 0, "日本", "日\n本")]
+#[case(WrapMode::Word, 6, 0, "日本 語テキ", "日本\n語テキ")]
+#[case(WrapMode::Char, 2, 0, "e\u{301}e\u{301}e\u{301}", "e\u{301}e\u{301}\ne\u{301}")]
+fn test_wrap(
+    #[case] mode: WrapMode,
+    #[case] width: usize,
+    #[case] indent: usize,
+    #[case] text: &str,
+    #[case] expected: &str,
+) {
+    assert_eq!(wrap(mode, width, indent, text), expected);
+}
+
+#[rstest]
+#[case("abcdef", 3, "abc", true)]
+#[case("abc", 3, "abc", false)]
+#[case("ab", 3, "ab", false)]
+#[case("a b c", 3, "a b", true)]
+#[case("äöüß", 2, "äö", true)]
+#[case("日本語", 4, "日本", true)]
+#[case("日本語", 3, "日", true)]
+#[case("日本", 4, "日本", false)]
+#[case("e\u{301}e\u{301}x", 2, "e\u{301}e\u{301}", true)]
+#[case("\x1b[0;1mabc\x1b[0mdef", 2, "\x1b[0;1mab\x1b[0m", true)]
+#[case("", 0, "", false)]
+#[case("a", 0, "", true)]
+fn test_truncate(#[case] text: &str, #[case] width: usize, #[case] expected: &str, #[case] truncated: bool) {
+    let mut buf = b"> ".to_vec();
+    buf.extend(text.as_bytes());
+    assert_eq!(truncate(&mut buf, 2, width), truncated);
+    assert_eq!(std::str::from_utf8(&buf[2..]).unwrap(), expected);
+}
+
+#[test]
+fn test_wrap_styles() {
+    assert_eq!(
+        wrap(WrapMode::Word, 6, 0, "\x1b[0;31mhello world\x1b[0m"),
+        "\x1b[0;31mhello\x1b[0m\n\x1b[0;31mworld\x1b[0m"
+    );
+    assert_eq!(
+        wrap(WrapMode::Char, 3, 1, "\x1b[0;1mab\x1b[4mcd\x1b[0mef"),
+        "\x1b[0;1mab\x1b[4mc\x1b[0m\n \x1b[0;1m\x1b[4md\x1b[0me\n f"
+    );
+}
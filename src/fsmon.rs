@@ -54,6 +54,14 @@ where
     })
 }
 
+/// Watches the directory itself, calling the handler on any change of its entries.
+pub fn run_dir<H>(dir: PathBuf, handle: H) -> Result<()>
+where
+    H: FnMut(Event) -> Result<()>,
+{
+    imp::run(vec![dir], handle)
+}
+
 #[cfg(not(target_os = "macos"))]
 mod imp {
     use std::sync::mpsc::{self};
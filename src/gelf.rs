@@ -0,0 +1,63 @@
+//! Mapping of [GELF](https://go2docs.graylog.org/current/getting_in_log_data/gelf.html) messages onto plain fields.
+
+// local imports
+use crate::{
+    level::Level,
+    model::RawRecordFields,
+    settings::{PredefinedFields, RawLevelFieldVariant},
+};
+
+// ---
+
+const TIME: &str = "timestamp";
+const LEVEL: &str = "level";
+const MESSAGE: &[&str] = &["short_message", "full_message"];
+const EXTRA_FIELD_PREFIX: &str = "_";
+
+/// Returns the predefined fields extended with the GELF field names,
+/// which take precedence over the configured ones.
+///
+/// The level is a numeric syslog severity.
+pub fn predefined_fields(base: &PredefinedFields) -> PredefinedFields {
+    let mut result = base.clone();
+
+    result.time.0.names.insert(0, TIME.into());
+    result
+        .message
+        .0
+        .names
+        .splice(0..0, MESSAGE.iter().map(|&name| name.to_owned()));
+
+    let values = [
+        (Level::Error, vec!["0", "1", "2", "3"]),
+        (Level::Warning, vec!["4", "5"]),
+        (Level::Info, vec!["6"]),
+        (Level::Debug, vec!["7"]),
+    ];
+    let variant = RawLevelFieldVariant {
+        names: vec![LEVEL.into()],
+        values: values
+            .into_iter()
+            .map(|(level, names)| (level.into(), names.into_iter().map(Into::into).collect()))
+            .collect(),
+        level: None,
+    };
+    result.level.variants.insert(0, variant);
+
+    result
+}
+
+/// Removes the leading underscore from the keys of additional fields, e.g. `_request_id` becomes `request_id`.
+pub fn strip_extra_field_prefixes<'a>(fields: &mut RawRecordFields<'a>) {
+    for (key, _) in fields.iter_mut() {
+        if let Some(stripped) = key
+            .strip_prefix(EXTRA_FIELD_PREFIX)
+            .filter(|stripped| !stripped.is_empty())
+        {
+            *key = stripped;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+use crate::{
+    app::InputFormat,
+    model::{Parser, ParserSettings, RawRecord, RawRecordParser},
+};
+
+const RECORD: &str = concat!(
+    r#"{"version":"1.1","host":"node-1","short_message":"disk almost full","#,
+    r#""full_message":"disk almost full\n/dev/sda1 95%","timestamp":1700000000.123,"level":4,"#,
+    r#""_free":42,"_":"x"}"#,
+);
+
+fn raw(line: &str) -> RawRecord<'_> {
+    RawRecordParser::new()
+        .format(Some(InputFormat::Gelf))
+        .parse(line.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+        .record
+}
+
+#[test]
+fn test_strip_extra_field_prefixes() {
+    let record = raw(RECORD);
+    let keys: Vec<_> = record.fields().map(|(k, _)| *k).collect();
+    assert_eq!(
+        keys,
+        vec![
+            "version",
+            "host",
+            "short_message",
+            "full_message",
+            "timestamp",
+            "level",
+            "free",
+            "_"
+        ]
+    );
+}
+
+#[test]
+fn test_predefined_fields() {
+    let predefined = predefined_fields(&PredefinedFields::default());
+    let parser = Parser::new(ParserSettings::new(&predefined, Vec::new(), None));
+    let raw = raw(RECORD);
+    let record = parser.parse(&raw);
+
+    assert_eq!(record.level, Some(Level::Warning));
+    assert_eq!(record.message.map(|v| v.raw_str()), Some(r#""disk almost full""#));
+    assert_eq!(
+        record.ts.and_then(|ts| ts.parse()).map(|ts| ts.timestamp_millis()),
+        Some(1700000000123)
+    );
+    assert_eq!(record.get("free").map(|v| v.raw_str()), Some("42"));
+}
+
+#[test]
+fn test_severity() {
+    let predefined = predefined_fields(&PredefinedFields::default());
+    let parser = Parser::new(ParserSettings::new(&predefined, Vec::new(), None));
+    let level = |severity: u8| {
+        let line = format!(r#"{{"version":"1.1","short_message":"x","level":{severity}}}"#);
+        parser.parse(&raw(&line)).level
+    };
+
+    assert_eq!(level(3), Some(Level::Error));
+    assert_eq!(level(4), Some(Level::Warning));
+    assert_eq!(level(5), Some(Level::Warning));
+    assert_eq!(level(6), Some(Level::Info));
+    assert_eq!(level(7), Some(Level::Debug));
+}
@@ -0,0 +1,121 @@
+// std imports
+use std::{
+    collections::HashMap,
+    io::{Result, Write},
+};
+
+// local imports
+use crate::{
+    model::{RawValue, Record},
+    theme::{Element, StylingPush, Theme},
+};
+
+// ---
+
+/// Returns the text of the value of the field with the given key, decoding strings.
+pub fn key(record: &Record, key: &str) -> Option<String> {
+    let value = record.get(key)?;
+    let text = match value {
+        RawValue::String(value) => {
+            let mut buf = Vec::new();
+            match value.decode(&mut buf) {
+                Ok(()) => String::from_utf8_lossy(&buf).into_owned(),
+                Err(_) => value.source().to_owned(),
+            }
+        }
+        _ => value.raw_str().to_owned(),
+    };
+    Some(text)
+}
+
+// ---
+
+/// Formatted records grouped by the value of a field.
+///
+/// Groups keep the order of their first records, records without the field form a group of their own.
+#[derive(Default)]
+pub struct Groups {
+    groups: Vec<Group>,
+    index: HashMap<Option<String>, usize>,
+}
+
+impl Groups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the formatted record, including its line break, to the group with the given key.
+    pub fn add(&mut self, key: Option<String>, ts: Option<(i64, u32)>, text: Vec<u8>) {
+        let i = *self.index.entry(key.clone()).or_insert_with(|| {
+            self.groups.push(Group {
+                key,
+                records: Vec::new(),
+            });
+            self.groups.len() - 1
+        });
+        self.groups[i].records.push((ts, text));
+    }
+
+    /// Writes each group under a header showing the key and the number of records,
+    /// with the records of the group ordered by timestamp.
+    ///
+    /// Records without a timestamp keep their position relative to the preceding record.
+    pub fn write<W: Write + ?Sized>(mut self, output: &mut W, theme: &Theme, name: &str) -> Result<()> {
+        let mut buf = Vec::new();
+        for (i, group) in self.groups.iter_mut().enumerate() {
+            let mut last = None;
+            for (ts, _) in group.records.iter_mut() {
+                match ts {
+                    Some(ts) => last = Some(*ts),
+                    None => *ts = last,
+                }
+            }
+            group.records.sort_by_key(|(ts, _)| *ts);
+
+            buf.clear();
+            if i != 0 {
+                buf.push(b'\n');
+            }
+            header(&mut buf, theme, name, group.key.as_deref(), group.records.len());
+            buf.push(b'\n');
+            output.write_all(&buf)?;
+            for (_, text) in &group.records {
+                output.write_all(text)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Group {
+    key: Option<String>,
+    records: Vec<(Option<(i64, u32)>, Vec<u8>)>,
+}
+
+/// Formats a header like `==> request_id=42 (3 records) <==`.
+fn header(buf: &mut Vec<u8>, theme: &Theme, name: &str, key: Option<&str>, count: usize) {
+    theme.apply(buf, &None, |s| {
+        s.element(Element::Ellipsis, |s| s.batch(|buf| buf.extend(b"==> ")));
+        match key {
+            Some(key) => {
+                s.element(Element::Key, |s| s.batch(|buf| buf.extend(name.as_bytes())));
+                s.element(Element::KeyValueSeparator, |s| s.batch(|buf| buf.push(b'=')));
+                s.element(Element::String, |s| s.batch(|buf| buf.extend(key.as_bytes())));
+            }
+            None => {
+                s.element(Element::Ellipsis, |s| s.batch(|buf| buf.extend(b"no ")));
+                s.element(Element::Key, |s| s.batch(|buf| buf.extend(name.as_bytes())));
+            }
+        }
+        s.element(Element::Ellipsis, |s| {
+            s.batch(|buf| {
+                let unit = if count == 1 { "record" } else { "records" };
+                write!(buf, " ({count} {unit}) <==").ok();
+            })
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,57 @@
+use super::*;
+
+use encstr::EncodedString;
+
+use crate::{model::RecordFields, timestamp::Timestamp};
+
+fn write(groups: Groups) -> String {
+    let mut buf = Vec::new();
+    groups.write(&mut buf, &Theme::none(), "req").unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_key() {
+    let record = Record {
+        ts: Some(Timestamp::new("2000-01-02T03:04:05Z")),
+        fields: RecordFields::from_slice(&[
+            ("req", RawValue::String(EncodedString::json(r#""a\"b""#))),
+            ("n", RawValue::Number("42")),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(key(&record, "req").as_deref(), Some(r#"a"b"#));
+    assert_eq!(key(&record, "n").as_deref(), Some("42"));
+    assert_eq!(key(&record, "x"), None);
+}
+
+#[test]
+fn test_groups() {
+    let mut groups = Groups::new();
+    groups.add(Some("b".into()), Some((2, 0)), b"b2\n".to_vec());
+    groups.add(Some("a".into()), Some((3, 0)), b"a3\n".to_vec());
+    groups.add(None, None, b"x\n".to_vec());
+    groups.add(Some("b".into()), Some((1, 0)), b"b1\n".to_vec());
+    groups.add(Some("b".into()), None, b"b1+\n".to_vec());
+
+    assert_eq!(
+        write(groups),
+        concat!(
+            "==> req=b (3 records) <==\n",
+            "b1\n",
+            "b1+\n",
+            "b2\n",
+            "\n",
+            "==> req=a (1 record) <==\n",
+            "a3\n",
+            "\n",
+            "==> no req (1 record) <==\n",
+            "x\n",
+        )
+    );
+}
+
+#[test]
+fn test_groups_empty() {
+    assert_eq!(write(Groups::new()), "");
+}
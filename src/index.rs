@@ -47,8 +47,9 @@ use crate::{
     index_capnp as schema,
     level::Level,
     model::{Parser, ParserSettings, RawRecord},
+    progress::Progress,
     scanning::{Delimiter, Scanner, Segment, SegmentBuf, SegmentBufFactory},
-    settings::PredefinedFields,
+    settings::{FieldAliases, PredefinedFields},
     vfs::{FileRead, FileSystem, LocalFileSystem},
 };
 
@@ -163,10 +164,12 @@ pub struct IndexerSettings<'a, FS: FileSystem> {
     pub buffer_size: BufferSize,
     pub max_message_size: MessageSize,
     pub fields: &'a PredefinedFields,
+    pub aliases: &'a FieldAliases,
     pub delimiter: Delimiter,
     pub allow_prefix: bool,
     pub unix_ts_unit: Option<UnixTimestampUnit>,
     pub format: Option<InputFormat>,
+    pub mode: IndexMode,
 }
 
 impl<'a, FS: FileSystem + Default> Default for IndexerSettings<'a, FS> {
@@ -182,10 +185,12 @@ impl<'a, FS: FileSystem> IndexerSettings<'a, FS> {
             buffer_size: BufferSize::default(),
             max_message_size: MessageSize::default(),
             fields: Default::default(),
+            aliases: Default::default(),
             delimiter: Delimiter::default(),
             allow_prefix: false,
             unix_ts_unit: None,
             format: None,
+            mode: IndexMode::default(),
         }
     }
 
@@ -197,6 +202,7 @@ impl<'a, FS: FileSystem> IndexerSettings<'a, FS> {
                 &self.buffer_size,
                 &self.max_message_size,
                 &self.fields,
+                &self.aliases,
                 &self.delimiter,
                 &self.allow_prefix,
                 &self.unix_ts_unit,
@@ -277,6 +283,20 @@ impl From<MessageSize> for u32 {
 
 // ---
 
+/// Controls how index files stored on disk are used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IndexMode {
+    /// Reuse an existing index if it matches the source file size and modification time, rebuild it otherwise.
+    #[default]
+    Auto,
+    /// Ignore an existing index and build a new one.
+    Rebuild,
+    /// Build the index in memory only, never reading or writing index files.
+    Disabled,
+}
+
+// ---
+
 /// Allows log files indexing to enable message sorting.
 pub struct Indexer<FS = LocalFileSystem> {
     fs: FS,
@@ -288,6 +308,8 @@ pub struct Indexer<FS = LocalFileSystem> {
     delimiter: Delimiter,
     allow_prefix: bool,
     format: Option<InputFormat>,
+    mode: IndexMode,
+    progress: Option<Arc<Progress>>,
 }
 
 impl<FS: FileSystem + Sync> Indexer<FS>
@@ -302,19 +324,32 @@ where
             buffer_size: settings.buffer_size.into(),
             max_message_size: settings.max_message_size.into(),
             dir,
-            parser: Parser::new(ParserSettings::new(settings.fields, empty(), settings.unix_ts_unit)),
+            parser: Parser::new(
+                ParserSettings::new(settings.fields, empty(), settings.unix_ts_unit).with_aliases(settings.aliases),
+            ),
             delimiter: settings.delimiter,
             allow_prefix: settings.allow_prefix,
             format: settings.format,
+            mode: settings.mode,
+            progress: None,
         }
     }
 
+    /// Reports the progress of building indexes.
+    pub fn with_progress(self, progress: Option<Arc<Progress>>) -> Self {
+        Self { progress, ..self }
+    }
+
     /// Builds index for the given file.
     ///
     /// Builds the index, saves it to disk and returns it.
     pub fn index(&self, source_path: &Path) -> Result<Index> {
         let (source_path, mut stream) = self.open_source(source_path)?;
         let meta = Metadata::from(&stream.metadata()?)?;
+        if self.mode == IndexMode::Disabled {
+            return self.process_file(&source_path, &meta, &mut stream, &mut io::sink(), None);
+        }
+
         let (index_path, index, actual) = self.prepare(&source_path, &meta)?;
         if actual {
             return Ok(index.unwrap());
@@ -329,6 +364,10 @@ where
     /// The source_path parameter must be the canonical path of the file.
     pub fn index_stream(&self, stream: &mut Reader, source_path: &Path, meta: &fs::Metadata) -> Result<Index> {
         let meta = &meta.try_into()?;
+        if self.mode == IndexMode::Disabled {
+            return self.process_file(source_path, meta, stream, &mut io::sink(), None);
+        }
+
         let (index_path, index, actual) = self.prepare(source_path, meta)?;
         if actual {
             return Ok(index.unwrap());
@@ -363,7 +402,9 @@ where
         log::debug!("index file path: {}", index_path.display());
         log::debug!("source meta: size={} modified={:?}", meta.len, meta.modified);
 
-        if self.fs.exists(&index_path)? {
+        if self.mode == IndexMode::Rebuild {
+            log::debug!("ignoring existing index");
+        } else if self.fs.exists(&index_path)? {
             let mut file = match self.fs.open(&index_path) {
                 Ok(file) => file,
                 Err(err) => {
@@ -419,6 +460,10 @@ where
     ) -> Result<Index> {
         let n = self.concurrency;
         let sfi = Arc::new(SegmentBufFactory::new(self.buffer_size.try_into()?));
+        let progress = self.progress.as_deref();
+        if let Some(progress) = progress {
+            progress.expect(metadata.len);
+        }
         thread::scope(|scope| -> Result<Index> {
             // prepare receive/transmit channels for input data
             let (txi, rxi): (Vec<_>, Vec<_>) = (0..n).map(|_| channel::bounded(1)).unzip();
@@ -479,6 +524,9 @@ where
                 let mut offset: u64 = 0;
                 let mut sn = 0;
                 while let Ok((size, stat, chronology, hash)) = rxo[sn % n].recv() {
+                    if let Some(progress) = progress {
+                        progress.advance(size as u64, stat.lines_valid + stat.lines_invalid);
+                    }
                     index.source.stat.merge(&stat);
                     index.source.blocks.push(SourceBlock::new(
                         offset,
@@ -505,6 +553,8 @@ where
         let mut stat = Stat::new();
         let mut sorted = true;
         let mut prev_ts = None;
+        // last seen timestamp, used to keep lines without a timestamp next to their predecessors
+        let mut last_ts = None;
         let mut lines = Vec::<(Option<Timestamp>, u32, u32)>::with_capacity(segment.data().len() / 512);
         let mut offset = 0;
         let mut i = 0;
@@ -531,14 +581,15 @@ where
                                 sorted = false;
                             }
                             stat.add_valid(ts, flags);
-                            lines.push((ts.or(prev_ts), i as u32, offset + ar.offsets.start as u32));
+                            last_ts = ts.or(last_ts);
+                            lines.push((last_ts, i as u32, offset + ar.offsets.start as u32));
                             rel = ar.offsets.end;
                             i += 1;
                             prev_ts = ts;
                         }
                         _ => {
                             stat.add_invalid();
-                            lines.push((ts.or(prev_ts), i as u32, offset + rel as u32));
+                            lines.push((ts.or(last_ts), i as u32, offset + rel as u32));
                             i += 1;
                             break;
                         }
@@ -546,7 +597,7 @@ where
                 }
             } else {
                 stat.add_invalid();
-                lines.push((ts.or(prev_ts), i as u32, offset));
+                lines.push((ts.or(last_ts), i as u32, offset));
                 i += 1;
             }
             offset += data_len as u32 + 1;
@@ -125,6 +125,41 @@ fn test_indexer() {
     assert_eq!(index2.source.modified, index1.source.modified);
 }
 
+#[test]
+fn test_indexer_modes() {
+    let fs = vfs::mem::FileSystem::new();
+
+    let data = br#"ts=2024-01-02T03:04:05Z msg="some test message""#;
+    let mut file = fs.create(&PathBuf::from("test.log")).unwrap();
+    file.write_all(data).unwrap();
+
+    let source_path = fs.canonicalize(&PathBuf::from("test.log")).unwrap();
+    let index_path = PathBuf::from("/tmp/cache").join(hex::encode(sha256(source_path.to_string_lossy().as_bytes())));
+
+    let indexer = |mode| {
+        Indexer::new(
+            1,
+            PathBuf::from("/tmp/cache"),
+            IndexerSettings {
+                mode,
+                ..IndexerSettings::with_fs(&fs)
+            },
+        )
+    };
+
+    let index = indexer(IndexMode::Disabled).index(&source_path).unwrap();
+    assert_eq!(index.source.stat.lines_valid, 1);
+    assert!(!fs.exists(&index_path).unwrap());
+
+    let index = indexer(IndexMode::Rebuild).index(&source_path).unwrap();
+    assert_eq!(index.source.stat.lines_valid, 1);
+    assert!(fs.exists(&index_path).unwrap());
+
+    let index = indexer(IndexMode::Auto).index(&source_path).unwrap();
+    assert_eq!(index.source.stat.lines_valid, 1);
+    assert_eq!(index.source.blocks.len(), 1);
+}
+
 #[test]
 fn test_timestamp() {
     let ts = Timestamp::from((1701680467, 91243000));
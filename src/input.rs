@@ -12,12 +12,14 @@ use std::{
 
 // third-party imports
 use deko::{Format, bufread::AnyDecoder};
+use flate2::read::MultiGzDecoder;
 
 // local imports
 use crate::{
     error::Result,
     index::{Index, Indexer, SourceBlock, SourceMetadata},
-    iox::ReadFill,
+    iox::{LengthHeader, LengthPrefixed, ReadFill},
+    jsonarray::{self, JsonArrayReader},
     replay::{ReplayBufCreator, ReplayBufReader, ReplaySeekReader},
     tee::TeeReader,
     vfs::{FileSystem, LocalFileSystem},
@@ -80,6 +82,22 @@ impl TryFrom<PathBuf> for InputPath {
 
 // ---
 
+/// Decompression of the input data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Decompression {
+    /// The compression format is detected by the leading magic bytes, data in unknown formats is read as is.
+    #[default]
+    Auto,
+    /// The data is read as is.
+    None,
+    /// The data is decoded as gzip.
+    Gzip,
+    /// The data is decoded as zstd.
+    Zstd,
+}
+
+// ---
+
 /// A reference to an input file or stdin.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum InputReference {
@@ -124,6 +142,11 @@ impl InputReference {
         self.hold()?.open()
     }
 
+    /// Completely opens the input for reading, decompressing it as requested.
+    pub fn open_with(&self, decompression: Decompression) -> io::Result<Input> {
+        self.hold()?.open_with(decompression)
+    }
+
     /// Returns a description of the input reference.
     pub fn description(&self) -> String {
         match self {
@@ -211,6 +234,13 @@ impl InputHolder {
         Ok(Input::new(self.reference.clone(), self.stream()?))
     }
 
+    /// Opens the input file for reading, decompressing it as requested.
+    pub fn open_with(self, decompression: Decompression) -> io::Result<Input> {
+        let reference = self.reference.clone();
+        let stream = self.stream()?.verified().decompressed(decompression)?;
+        Ok(Input::from_decoded(reference, stream))
+    }
+
     /// Indexes the input file and returns IndexedInput that can be used to access the data in random order.
     pub fn index<FS>(self, indexer: &Indexer<FS>) -> Result<IndexedInput>
     where
@@ -248,9 +278,13 @@ pub struct Input {
 
 impl Input {
     fn new(reference: InputReference, stream: Stream) -> Self {
+        Self::from_decoded(reference, stream.verified().decoded())
+    }
+
+    fn from_decoded(reference: InputReference, stream: Stream) -> Self {
         Self {
             reference: reference.clone(),
-            stream: stream.verified().decoded().tagged(reference),
+            stream: stream.unrolled().tagged(reference),
         }
     }
 
@@ -274,6 +308,16 @@ impl Input {
         InputReference::Stdin.open()
     }
 
+    /// Decodes length-prefixed frames of the input into records terminated by the given byte.
+    /// The framed stream is always sequential and has no metadata, so it is never cached by the indexer.
+    pub fn framed(self, header: LengthHeader, terminator: u8) -> Self {
+        let stream = LengthPrefixed::new(self.stream.into_sequential(), header, terminator).with_metadata(None);
+        Self {
+            reference: self.reference,
+            stream: Stream::Sequential(Box::new(stream)),
+        }
+    }
+
     pub fn tail(mut self, lines: u64) -> io::Result<Self> {
         match &mut self.stream {
             Stream::Sequential(_) => (),
@@ -369,6 +413,56 @@ impl Stream {
         }
     }
 
+    /// Decodes the stream using the given decompression, bypassing format detection unless it is `Auto`.
+    pub fn decompressed(self, decompression: Decompression) -> io::Result<Self> {
+        let meta = self.metadata().ok().flatten();
+        Ok(match decompression {
+            Decompression::Auto => self.decoded(),
+            Decompression::None => self,
+            Decompression::Gzip => Self::Sequential(Box::new(
+                MultiGzDecoder::new(self.into_sequential()).with_metadata(meta),
+            )),
+            Decompression::Zstd => Self::Sequential(Box::new(
+                zstd::stream::read::Decoder::new(self.into_sequential())?.with_metadata(meta),
+            )),
+        })
+    }
+
+    /// Converts a top-level JSON array into a stream of newline-delimited elements if needed.
+    pub fn unrolled(self) -> Self {
+        const PEEK_SIZE: usize = 4096;
+
+        match self {
+            Self::Sequential(stream) => {
+                let meta = stream.metadata().ok().flatten();
+                Self::Sequential(Box::new(
+                    JsonArrayReader::auto(BufReader::new(stream)).with_metadata(meta),
+                ))
+            }
+            Self::RandomAccess(mut stream) => {
+                let Ok(pos) = stream.stream_position() else {
+                    return Self::Sequential(Box::new(stream));
+                };
+                let mut buf = [0; PEEK_SIZE];
+                let detected = stream
+                    .read_fill(&mut buf)
+                    .ok()
+                    .and_then(|n| jsonarray::detect(&buf[..n]))
+                    .unwrap_or(false);
+                stream.seek(SeekFrom::Start(pos)).ok();
+                if detected {
+                    log::debug!("detected top-level JSON array in random access stream");
+                    let meta = stream.metadata().ok().flatten();
+                    Self::Sequential(Box::new(
+                        JsonArrayReader::new(BufReader::new(stream)).with_metadata(meta),
+                    ))
+                } else {
+                    Self::RandomAccess(stream)
+                }
+            }
+        }
+    }
+
     /// Converts the stream to a sequential stream.
     pub fn as_sequential(&mut self) -> StreamOver<&mut (dyn ReadMeta + Send + Sync)> {
         match self {
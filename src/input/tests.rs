@@ -165,6 +165,53 @@ fn test_input_gzip() {
     assert_eq!(buf, b"test\n");
 }
 
+#[test]
+fn test_input_decompression() {
+    // echo 'test' | gzip -cf | xxd -p | sed 's/\(..\)/\\x\1/g'
+    let gzip: &[u8] =
+        b"\x1f\x8b\x08\x00\x9e\xdd\x48\x67\x00\x03\x2b\x49\x2d\x2e\xe1\x02\x00\xc6\x35\xb9\x3b\x05\x00\x00\x00";
+    // echo 'test' | zstd -c | xxd -p | sed 's/\(..\)/\\x\1/g'
+    let zstd: &[u8] = b"\x28\xb5\x2f\xfd\x04\x58\x29\x00\x00\x74\x65\x73\x74\x0a\x3c\xa6\x1f\xda";
+
+    let read = |data: &'static [u8], decompression| {
+        let stream = Stream::RandomAccess(Box::new(Cursor::new(data)));
+        let mut stream = stream.verified().decompressed(decompression)?;
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf)?;
+        io::Result::Ok(buf)
+    };
+
+    assert_eq!(read(gzip, Decompression::Auto).unwrap(), b"test\n");
+    assert_eq!(read(gzip, Decompression::Gzip).unwrap(), b"test\n");
+    assert_eq!(read(gzip, Decompression::None).unwrap(), gzip);
+    assert_eq!(read(zstd, Decompression::Zstd).unwrap(), b"test\n");
+    assert_eq!(read(zstd, Decompression::None).unwrap(), zstd);
+    assert!(read(b"test\n", Decompression::Gzip).is_err());
+    assert!(read(gzip, Decompression::Zstd).is_err());
+}
+
+#[test]
+fn test_input_json_array() {
+    let data = b"[\n  {\"a\": 1},\n  {\"b\": 2}\n]\n";
+
+    let stream = Stream::RandomAccess(Box::new(Cursor::new(data)));
+    let mut stream = stream.verified().decoded().unrolled();
+    assert!(matches!(stream, Stream::Sequential(_)));
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"{\"a\": 1}\n{\"b\": 2}\n");
+
+    let stream = Stream::Sequential(Box::new(Cursor::new(data)));
+    let mut stream = stream.verified().decoded().unrolled();
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"{\"a\": 1}\n{\"b\": 2}\n");
+
+    let stream = Stream::RandomAccess(Box::new(Cursor::new(b"{\"a\": 1}\n")));
+    let stream = stream.verified().decoded().unrolled();
+    assert!(matches!(stream, Stream::RandomAccess(_)));
+}
+
 #[test]
 fn test_indexed_input_stdin() {
     let data = br#"{"ts":"2024-10-01T01:02:03Z","level":"info","msg":"some test message"}\n"#;
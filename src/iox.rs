@@ -1,5 +1,11 @@
 // std imports
-use std::io::{Read, Result};
+use std::{
+    cmp::min,
+    io::{BufRead, BufReader, Error, ErrorKind, Read, Result},
+};
+
+// third-party imports
+use memchr::memrchr;
 
 // ---
 
@@ -20,3 +26,175 @@ impl<T: Read + ?Sized> ReadFill for T {
         Ok(i)
     }
 }
+
+// ---
+
+/// A reader that holds back the trailing data following the last terminator byte
+/// until the terminator arrives, so that only complete lines are returned.
+pub struct CompleteLines<R> {
+    inner: R,
+    terminator: u8,
+    buf: Vec<u8>,
+    pos: usize,
+    ready: usize,
+}
+
+impl<R: Read> CompleteLines<R> {
+    const CHUNK_SIZE: usize = 64 * 1024;
+
+    #[inline]
+    pub fn new(inner: R, terminator: u8) -> Self {
+        Self {
+            inner,
+            terminator,
+            buf: Vec::new(),
+            pos: 0,
+            ready: 0,
+        }
+    }
+
+    /// Returns the number of bytes that are held back waiting for the terminator.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.buf.len() - self.ready
+    }
+}
+
+impl<R: Read> Read for CompleteLines<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        loop {
+            if self.pos < self.ready {
+                let n = min(buf.len(), self.ready - self.pos);
+                buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+                self.pos += n;
+                return Ok(n);
+            }
+
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+            self.ready = 0;
+
+            let len = self.buf.len();
+            self.buf.resize(len + Self::CHUNK_SIZE, 0);
+            let n = match self.inner.read(&mut self.buf[len..]) {
+                Ok(n) => n,
+                Err(err) => {
+                    self.buf.truncate(len);
+                    return Err(err);
+                }
+            };
+            self.buf.truncate(len + n);
+            if n == 0 {
+                return Ok(0);
+            }
+
+            if let Some(i) = memrchr(self.terminator, &self.buf[len..]) {
+                self.ready = len + i + 1;
+            }
+        }
+    }
+}
+
+// ---
+
+/// Encoding of the length header preceding each frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthHeader {
+    /// Unsigned LEB128 varint as used by protobuf.
+    Varint,
+    U16Be,
+    U32Be,
+    U32Le,
+    U64Be,
+}
+
+impl LengthHeader {
+    const MAX_VARINT_SIZE: usize = 10;
+
+    /// Returns the length encoded in `header` if it is complete.
+    fn decode(&self, header: &[u8]) -> Result<Option<u64>> {
+        let fixed = |n: usize| header.len() >= n;
+        Ok(match self {
+            Self::Varint => match header.last() {
+                Some(byte) if byte & 0x80 == 0 => Some(
+                    header
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, byte| (acc << 7) | (byte & 0x7f) as u64),
+                ),
+                _ if header.len() >= Self::MAX_VARINT_SIZE => {
+                    return Err(Error::new(ErrorKind::InvalidData, "varint frame length is too long"));
+                }
+                _ => None,
+            },
+            Self::U16Be => fixed(2).then(|| u16::from_be_bytes([header[0], header[1]]) as u64),
+            Self::U32Be => fixed(4).then(|| u32::from_be_bytes(header[..4].try_into().unwrap()) as u64),
+            Self::U32Le => fixed(4).then(|| u32::from_le_bytes(header[..4].try_into().unwrap()) as u64),
+            Self::U64Be => fixed(8).then(|| u64::from_be_bytes(header[..8].try_into().unwrap())),
+        })
+    }
+}
+
+/// A reader that decodes length-prefixed frames and returns each frame payload followed by the terminator byte.
+///
+/// Reaching the end of the inner reader in the middle of a frame is not an error,
+/// reading resumes from the same place when more data becomes available.
+pub struct LengthPrefixed<R> {
+    inner: BufReader<R>,
+    header: LengthHeader,
+    terminator: u8,
+    buf: Vec<u8>,
+    remaining: Option<u64>,
+}
+
+impl<R: Read> LengthPrefixed<R> {
+    #[inline]
+    pub fn new(inner: R, header: LengthHeader, terminator: u8) -> Self {
+        Self {
+            inner: BufReader::new(inner),
+            header,
+            terminator,
+            buf: Vec::with_capacity(LengthHeader::MAX_VARINT_SIZE),
+            remaining: None,
+        }
+    }
+}
+
+impl<R: Read> Read for LengthPrefixed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            match self.remaining {
+                Some(0) => {
+                    buf[0] = self.terminator;
+                    self.remaining = None;
+                    return Ok(1);
+                }
+                Some(remaining) => {
+                    let n = min(buf.len() as u64, remaining) as usize;
+                    let n = self.inner.read(&mut buf[..n])?;
+                    self.remaining = Some(remaining - n as u64);
+                    return Ok(n);
+                }
+                None => {
+                    let available = self.inner.fill_buf()?;
+                    if available.is_empty() {
+                        return Ok(0);
+                    }
+                    self.buf.push(available[0]);
+                    self.inner.consume(1);
+                    if let Some(len) = self.header.decode(&self.buf)? {
+                        self.buf.clear();
+                        self.remaining = Some(len);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
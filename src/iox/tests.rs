@@ -0,0 +1,96 @@
+use super::*;
+
+use std::io::Cursor;
+
+#[test]
+fn test_read_fill() {
+    let mut reader = Cursor::new(b"hello");
+    let mut buf = [0; 8];
+    assert_eq!(reader.read_fill(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+}
+
+#[test]
+fn test_complete_lines() {
+    let mut reader = CompleteLines::new(Cursor::new(b"a\nbc\nde".to_vec()), b'\n');
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"a\nbc\n");
+    assert_eq!(reader.pending(), 2);
+}
+
+#[test]
+fn test_complete_lines_appended() {
+    let mut reader = CompleteLines::new(Cursor::new(b"a\nb".to_vec()), b'\n');
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"a\n");
+
+    reader.inner.get_mut().extend_from_slice(b"c\nd");
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"bc\n");
+    assert_eq!(reader.pending(), 1);
+}
+
+#[test]
+fn test_complete_lines_small_buffer() {
+    let mut reader = CompleteLines::new(Cursor::new(b"abc\ndef\n".to_vec()), b'\n');
+    let mut result = Vec::new();
+    let mut buf = [0; 3];
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        result.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(result, b"abc\ndef\n");
+    assert_eq!(reader.pending(), 0);
+}
+
+#[test]
+fn test_length_prefixed_u32be() {
+    let mut data = Vec::new();
+    for frame in [&b"{\"a\":\n1}"[..], b"", b"x"] {
+        data.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        data.extend_from_slice(frame);
+    }
+    let mut reader = LengthPrefixed::new(Cursor::new(data), LengthHeader::U32Be, 0);
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"{\"a\":\n1}\0\0x\0");
+}
+
+#[test]
+fn test_length_prefixed_varint() {
+    let payload = vec![b'a'; 300];
+    let mut data = vec![0xac, 0x02];
+    data.extend_from_slice(&payload);
+    let mut reader = LengthPrefixed::new(Cursor::new(data), LengthHeader::Varint, 0);
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf.len(), 301);
+    assert_eq!(&buf[..300], &payload[..]);
+    assert_eq!(buf[300], 0);
+}
+
+#[test]
+fn test_length_prefixed_resumed() {
+    let mut reader = LengthPrefixed::new(Cursor::new(vec![0, 3, b'a']), LengthHeader::U16Be, 0);
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"a");
+
+    reader.inner.get_mut().get_mut().extend_from_slice(b"bc\x00");
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"bc\0");
+}
+
+#[test]
+fn test_length_prefixed_invalid_varint() {
+    let mut reader = LengthPrefixed::new(Cursor::new(vec![0xff; 16]), LengthHeader::Varint, 0);
+    let mut buf = Vec::new();
+    assert_eq!(reader.read_to_end(&mut buf).unwrap_err().kind(), ErrorKind::InvalidData);
+}
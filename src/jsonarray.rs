@@ -0,0 +1,220 @@
+// std imports
+use std::io::{self, BufRead, Read};
+
+// ---
+
+/// Checks whether the given data looks like the beginning of a top-level JSON array of objects.
+///
+/// Returns `None` if there is not enough data to decide.
+pub fn detect(data: &[u8]) -> Option<bool> {
+    let mut bytes = data.iter().copied().filter(|b| !b.is_ascii_whitespace());
+    match bytes.next() {
+        Some(b'[') => match bytes.next() {
+            Some(b'{' | b']') => Some(true),
+            Some(_) => Some(false),
+            None => None,
+        },
+        Some(_) => Some(false),
+        None => None,
+    }
+}
+
+// ---
+
+/// A reader that converts a top-level JSON array into a stream of newline-delimited elements.
+///
+/// The conversion is done on the fly without buffering the whole array.
+/// Each element is emitted on its own line, line breaks inside the elements are removed.
+/// Any non-whitespace data following the closing bracket of the array is reported as an error.
+pub struct JsonArrayReader<R> {
+    inner: R,
+    mode: Mode,
+    state: State,
+}
+
+impl<R: BufRead> JsonArrayReader<R> {
+    /// Creates a reader that expects the input to be a top-level JSON array.
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self::with_mode(inner, Mode::Array)
+    }
+
+    /// Creates a reader that detects a top-level JSON array on the first read
+    /// and passes any other input through unchanged.
+    #[inline]
+    pub fn auto(inner: R) -> Self {
+        Self::with_mode(inner, Mode::Auto)
+    }
+
+    #[inline]
+    fn with_mode(inner: R, mode: Mode) -> Self {
+        Self {
+            inner,
+            mode,
+            state: State::default(),
+        }
+    }
+}
+
+impl<R: BufRead> Read for JsonArrayReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        if self.mode == Mode::Auto {
+            let detected = detect(self.inner.fill_buf()?).unwrap_or(false);
+            self.mode = if detected { Mode::Array } else { Mode::Verbatim };
+        }
+
+        if self.mode == Mode::Verbatim {
+            return self.inner.read(buf);
+        }
+
+        loop {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                if self.state.line_open {
+                    // truncated array, flush the last incomplete element
+                    self.state.line_open = false;
+                    buf[0] = b'\n';
+                    return Ok(1);
+                }
+                return Ok(0);
+            }
+
+            let (consumed, produced) = self.state.process(input, buf)?;
+            self.inner.consume(consumed);
+            if produced != 0 {
+                return Ok(produced);
+            }
+        }
+    }
+}
+
+// ---
+
+#[derive(Debug, Default)]
+struct State {
+    depth: usize,
+    started: bool,
+    finished: bool,
+    in_string: bool,
+    escaped: bool,
+    line_open: bool,
+}
+
+impl State {
+    fn process(&mut self, input: &[u8], output: &mut [u8]) -> io::Result<(usize, usize)> {
+        let mut i = 0;
+        let mut o = 0;
+
+        while i < input.len() && o < output.len() {
+            let b = input[i];
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if b == b'\\' {
+                    self.escaped = true;
+                } else if b == b'"' {
+                    self.in_string = false;
+                }
+                output[o] = b;
+                o += 1;
+                i += 1;
+                continue;
+            }
+
+            if !self.started {
+                if !b.is_ascii_whitespace() {
+                    if b != b'[' {
+                        if o != 0 {
+                            break;
+                        }
+                        return Err(unexpected(b));
+                    }
+                    self.started = true;
+                    self.depth = 1;
+                }
+                i += 1;
+                continue;
+            }
+
+            if self.finished {
+                if !b.is_ascii_whitespace() {
+                    if o != 0 {
+                        break;
+                    }
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "unexpected data after the end of top-level JSON array",
+                    ));
+                }
+                i += 1;
+                continue;
+            }
+
+            match b {
+                b'\n' | b'\r' => {}
+                b' ' | b'\t' if self.depth == 1 => {}
+                b',' | b']' if self.depth == 1 => {
+                    if self.line_open {
+                        output[o] = b'\n';
+                        o += 1;
+                        self.line_open = false;
+                    }
+                    if b == b']' {
+                        self.depth = 0;
+                        self.finished = true;
+                    }
+                }
+                _ => {
+                    match b {
+                        b'"' => self.in_string = true,
+                        b'{' | b'[' => self.depth += 1,
+                        b'}' | b']' => match self.depth.checked_sub(1) {
+                            // closing the top-level array is handled above, so the depth never drops below 1 here
+                            Some(depth) if depth != 0 => self.depth = depth,
+                            _ => {
+                                if o != 0 {
+                                    break;
+                                }
+                                return Err(io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    format!("unbalanced {:?} in top-level JSON array", b as char),
+                                ));
+                            }
+                        },
+                        _ => {}
+                    }
+                    output[o] = b;
+                    o += 1;
+                    self.line_open = true;
+                }
+            }
+            i += 1;
+        }
+
+        Ok((i, o))
+    }
+}
+
+// ---
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Auto,
+    Array,
+    Verbatim,
+}
+
+fn unexpected(b: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("expected start of top-level JSON array, found {:?}", b as char),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+use super::*;
+
+use std::io::{BufReader, Cursor};
+
+fn unroll(data: &[u8]) -> io::Result<String> {
+    let mut result = String::new();
+    JsonArrayReader::auto(Cursor::new(data)).read_to_string(&mut result)?;
+    Ok(result)
+}
+
+#[test]
+fn test_detect() {
+    assert_eq!(detect(b""), None);
+    assert_eq!(detect(b"  \n"), None);
+    assert_eq!(detect(b"["), None);
+    assert_eq!(detect(b"[{"), Some(true));
+    assert_eq!(detect(b" \n[\n  {\"a\":1}]"), Some(true));
+    assert_eq!(detect(b"[]"), Some(true));
+    assert_eq!(detect(b"{\"a\":1}"), Some(false));
+    assert_eq!(detect(b"[2024-01-01] message"), Some(false));
+    assert_eq!(detect(b"a=b"), Some(false));
+}
+
+#[test]
+fn test_compact() {
+    assert_eq!(unroll(br#"[{"a":1},{"b":2}]"#).unwrap(), "{\"a\":1}\n{\"b\":2}\n");
+}
+
+#[test]
+fn test_pretty() {
+    let data = b"[\n  {\n    \"a\": 1,\n    \"b\": [1, 2]\n  },\n  {\n    \"c\": \"x\"\n  }\n]\n";
+    assert_eq!(
+        unroll(data).unwrap(),
+        "{    \"a\": 1,    \"b\": [1, 2]  }\n{    \"c\": \"x\"  }\n"
+    );
+}
+
+#[test]
+fn test_strings() {
+    let data = br#"[{"a":"x,]}\"\n"},{"b":"[{"}]"#;
+    assert_eq!(unroll(data).unwrap(), "{\"a\":\"x,]}\\\"\\n\"}\n{\"b\":\"[{\"}\n");
+}
+
+#[test]
+fn test_empty() {
+    assert_eq!(unroll(b"[]").unwrap(), "");
+    assert_eq!(unroll(b" [ ] \n").unwrap(), "");
+}
+
+#[test]
+fn test_verbatim() {
+    let data = b"{\"a\":1}\n{\"b\":2}\n";
+    assert_eq!(unroll(data).unwrap(), "{\"a\":1}\n{\"b\":2}\n");
+    let data = b"[2024-01-01] message\n";
+    assert_eq!(unroll(data).unwrap(), "[2024-01-01] message\n");
+}
+
+#[test]
+fn test_trailing_data() {
+    let mut reader = JsonArrayReader::auto(Cursor::new(b"[{\"a\":1}]\n{\"b\":2}\n"));
+    let mut buf = [0; 64];
+    let n = reader.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"{\"a\":1}\n");
+    let err = reader.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_truncated() {
+    assert_eq!(unroll(br#"[{"a":1},{"b":"#).unwrap(), "{\"a\":1}\n{\"b\":\n");
+}
+
+#[test]
+fn test_small_buffers() {
+    let data = b"[\n  {\"a\": 1},\n  {\"b\": \"x y\"}\n]\n";
+    let mut reader = JsonArrayReader::new(BufReader::with_capacity(3, Cursor::new(data)));
+    let mut result = Vec::new();
+    let mut buf = [0; 2];
+    loop {
+        let n = reader.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        result.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(result, b"{\"a\": 1}\n{\"b\": \"x y\"}\n");
+}
+
+#[test]
+fn test_not_array() {
+    assert_eq!(unroll(b"").unwrap(), "");
+    let mut result = Vec::new();
+    let err = JsonArrayReader::new(Cursor::new(b"{}"))
+        .read_to_end(&mut result)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_unbalanced() {
+    let mut reader = JsonArrayReader::new(Cursor::new(b"[{\"a\":1},}{\"b\":2}]"));
+    let mut buf = [0; 64];
+    let n = reader.read(&mut buf).unwrap();
+    assert_eq!(&buf[..n], b"{\"a\":1}\n");
+    let err = reader.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mut result = Vec::new();
+    let err = JsonArrayReader::new(Cursor::new(b"[{\"a\":1}}]"))
+        .read_to_end(&mut result)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
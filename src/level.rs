@@ -1,8 +1,9 @@
 // std imports
-use std::cmp::Ord;
+use std::cmp::Ordering;
 use std::fmt;
 use std::ops::Deref;
 use std::result::Result;
+use std::str::FromStr;
 use std::sync::Arc;
 
 // third-party imports
@@ -19,22 +20,7 @@ use crate::xerr::{HighlightQuoted, Suggestions};
 
 // ---
 
-#[derive(
-    ValueEnum,
-    Clone,
-    Copy,
-    Debug,
-    Deserialize,
-    Serialize,
-    Eq,
-    Hash,
-    Ord,
-    PartialEq,
-    PartialOrd,
-    Enum,
-    EnumIter,
-    AsRefStr,
-)]
+#[derive(ValueEnum, Clone, Copy, Debug, Deserialize, Serialize, Eq, Hash, PartialEq, Enum, EnumIter, AsRefStr)]
 #[serde(rename_all = "kebab-case")]
 #[strum(serialize_all = "lowercase")]
 pub enum Level {
@@ -45,6 +31,58 @@ pub enum Level {
     Trace,
 }
 
+impl Level {
+    /// Returns numeric severity of the level, the higher the more severe.
+    #[inline]
+    pub const fn severity(self) -> u8 {
+        match self {
+            Self::Trace => 0,
+            Self::Debug => 1,
+            Self::Info => 2,
+            Self::Warning => 3,
+            Self::Error => 4,
+        }
+    }
+
+    /// Looks up a level by its textual value in the configured level mapping, e.g. `fields.predefined.level.variants[].values`.
+    ///
+    /// Values are compared case-insensitively.
+    pub fn from_mapping<'a, V, I>(value: &str, mapping: I) -> Option<Self>
+    where
+        V: AsRef<str> + 'a,
+        I: IntoIterator<Item = (&'a Level, &'a Vec<V>)>,
+    {
+        mapping
+            .into_iter()
+            .find(|(_, values)| values.iter().any(|x| x.as_ref().eq_ignore_ascii_case(value)))
+            .map(|(level, _)| *level)
+    }
+}
+
+/// Levels are ordered by severity, i.e. `Trace < Debug < Info < Warning < Error`.
+impl Ord for Level {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.severity().cmp(&other.severity())
+    }
+}
+
+impl PartialOrd for Level {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parses a level name or one of its common aliases, e.g. `warn`, `wrn` or `w`, ignoring case.
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RelaxedLevel::try_from(s).map(|x| x.0)
+    }
+}
+
 // ---
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
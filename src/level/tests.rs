@@ -49,3 +49,29 @@ fn test_level_value_parser() {
         .any(|(level, values)| *level == Level::Warning && values.contains(&"warning"));
     assert!(has_warning, "Should have 'warning' as alternate for Warn level");
 }
+
+#[test]
+fn test_level_order() {
+    assert!(Level::Trace < Level::Debug);
+    assert!(Level::Debug < Level::Info);
+    assert!(Level::Info < Level::Warning);
+    assert!(Level::Warning < Level::Error);
+    assert_eq!(Level::iter().max(), Some(Level::Error));
+    assert_eq!(Level::iter().min(), Some(Level::Trace));
+}
+
+#[test]
+fn test_level_from_str() {
+    assert_eq!("info".parse::<Level>().unwrap(), Level::Info);
+    assert_eq!("WRN".parse::<Level>().unwrap(), Level::Warning);
+    assert_eq!("e".parse::<Level>().unwrap(), Level::Error);
+    assert!("verbose".parse::<Level>().is_err());
+}
+
+#[test]
+fn test_level_from_mapping() {
+    let mapping = std::collections::HashMap::from([(Level::Error, vec!["E", "FATAL"]), (Level::Info, vec!["I"])]);
+    assert_eq!(Level::from_mapping("fatal", &mapping), Some(Level::Error));
+    assert_eq!(Level::from_mapping("i", &mapping), Some(Level::Info));
+    assert_eq!(Level::from_mapping("info", &mapping), None);
+}
@@ -12,9 +12,14 @@ pub mod index_capnp;
 pub mod input;
 pub mod iox;
 pub mod level;
+pub mod listen;
 pub mod output;
+pub mod pathx;
+pub mod progress;
 pub mod query;
+pub mod records;
 pub mod settings;
+pub mod stats;
 pub mod theme;
 pub mod themecfg;
 pub mod timeparse;
@@ -23,17 +28,26 @@ pub mod timezone;
 pub mod types;
 
 // private modules
+mod bookmark;
 mod console;
+mod context;
+mod dedup;
 mod eseq;
 mod filtering;
 mod fsmon;
+mod gelf;
+mod grouping;
+mod jsonarray;
 mod model;
 mod number;
+mod otlp;
 mod replay;
 mod scanning;
 mod serdex;
+mod syslog;
 mod tee;
 mod vfs;
+mod winevent;
 mod xerr;
 
 // test utilities
@@ -49,9 +63,13 @@ pub mod signal;
 pub use app::{App, FieldOptions, Options, SegmentProcessor};
 pub use datefmt::{DateTimeFormatter, LinuxDateFormat};
 pub use filtering::DefaultNormalizing;
-pub use formatting::RecordFormatter;
-pub use model::{FieldFilterSet, Filter, Level, Parser, ParserSettings, RecordFilter};
-pub use query::Query;
+pub use formatting::{RecordFormatter, RecordFormatterBuilder};
+pub use model::{
+    Caller, FieldFilterSet, Filter, Level, ParseOptions, Parser, ParserSettings, RawRecord, RawValue, Record,
+    RecordFilter, parse_record,
+};
+pub use query::{Query, QueryError};
+pub use records::{RecordBuf, Records, records};
 pub use scanning::{Delimit, Delimiter, SearchExt};
 pub use settings::Settings;
 pub use theme::Theme;
@@ -0,0 +1,97 @@
+//! Network sockets accepting log records sent by other processes.
+
+// std imports
+use std::{
+    fmt,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket},
+    str::FromStr,
+};
+
+// local imports
+use crate::error::{Error, Result};
+
+// ---
+
+/// Maximum size of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65536;
+
+/// Address of a socket to listen on, given as `tcp://HOST:PORT` or `udp://HOST:PORT`.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub enum ListenAddress {
+    /// Each connection is a stream of delimited or framed records.
+    Tcp(SocketAddr),
+    /// Each datagram is a single record.
+    Udp(SocketAddr),
+}
+
+impl FromStr for ListenAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let error = |reason| Error::InvalidListenAddress {
+            value: s.to_owned(),
+            reason,
+        };
+
+        let (scheme, addr) = s
+            .split_once("://")
+            .ok_or_else(|| error("expected tcp://HOST:PORT or udp://HOST:PORT"))?;
+        let addr = addr
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| error("cannot resolve the address"))?;
+
+        match scheme.to_lowercase().as_str() {
+            "tcp" => Ok(Self::Tcp(addr)),
+            "udp" => Ok(Self::Udp(addr)),
+            _ => Err(error("unsupported protocol, expected tcp or udp")),
+        }
+    }
+}
+
+impl fmt::Display for ListenAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "tcp://{}", addr),
+            Self::Udp(addr) => write!(f, "udp://{}", addr),
+        }
+    }
+}
+
+// ---
+
+/// Accepts connections until an error occurs, passing each connection with the address of the peer to the handler.
+///
+/// Connections failing to be accepted are skipped.
+pub fn accept<F>(addr: SocketAddr, mut handle: F) -> Result<()>
+where
+    F: FnMut(TcpStream, SocketAddr) -> Result<()>,
+{
+    let listener = TcpListener::bind(addr)?;
+    log::debug!("listening on tcp://{}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        match stream.and_then(|stream| Ok((stream.peer_addr()?, stream))) {
+            Ok((peer, stream)) => handle(stream, peer)?,
+            Err(err) => log::warn!("failed to accept connection: {err}"),
+        }
+    }
+    Ok(())
+}
+
+/// Receives datagrams until an error occurs, passing each datagram with the address of the peer to the handler.
+pub fn receive<F>(addr: SocketAddr, mut handle: F) -> Result<()>
+where
+    F: FnMut(&[u8], SocketAddr) -> Result<()>,
+{
+    let socket = UdpSocket::bind(addr)?;
+    log::debug!("listening on udp://{}", socket.local_addr()?);
+    let mut buf = vec![0; MAX_DATAGRAM_SIZE];
+    loop {
+        let (n, peer) = socket.recv_from(&mut buf)?;
+        handle(&buf[..n], peer)?;
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,29 @@
+use super::*;
+
+use rstest::rstest;
+
+#[rstest]
+#[case("tcp://127.0.0.1:5170", ListenAddress::Tcp(([127, 0, 0, 1], 5170).into()))]
+#[case("UDP://0.0.0.0:12201", ListenAddress::Udp(([0, 0, 0, 0], 12201).into()))]
+#[case("tcp://[::1]:80", ListenAddress::Tcp("[::1]:80".parse().unwrap()))]
+fn test_from_str(#[case] input: &str, #[case] expected: ListenAddress) {
+    assert_eq!(input.parse::<ListenAddress>().unwrap(), expected);
+}
+
+#[rstest]
+#[case("127.0.0.1:5170")]
+#[case("http://127.0.0.1:80")]
+#[case("tcp://127.0.0.1")]
+#[case("udp://")]
+fn test_from_str_invalid(#[case] input: &str) {
+    assert!(matches!(
+        input.parse::<ListenAddress>(),
+        Err(Error::InvalidListenAddress { .. })
+    ));
+}
+
+#[test]
+fn test_display() {
+    let addr = "udp://127.0.0.1:12201".parse::<ListenAddress>().unwrap();
+    assert_eq!(addr.to_string(), "udp://127.0.0.1:12201");
+}
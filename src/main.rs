@@ -1,7 +1,8 @@
 // std imports
 use std::{
     default::Default,
-    io::{IsTerminal, stdin, stdout},
+    env,
+    io::{Cursor, IsTerminal, stderr, stdin, stdout},
     path::PathBuf,
     process,
     sync::Arc,
@@ -25,8 +26,15 @@ use hl::{
     cli, config,
     datefmt::LinuxDateFormat,
     error::*,
-    input::InputReference,
-    output::{OutputStream, Pager},
+    formatting::{
+        FieldQuote, StyleRule,
+        highlight::Highlighter,
+        wrap::{WrapMode, Wrapper},
+    },
+    index::IndexMode,
+    input::{InputHolder, InputReference},
+    output::{ExecMode, ExecOutput, OutputFile, OutputStream, Pager},
+    pathx,
     query::Query,
     settings::{AsciiModeOpt, InputInfo, Settings},
     signal::SignalHandler,
@@ -40,6 +48,9 @@ mod help;
 
 const HL_DEBUG_LOG: &str = "HL_DEBUG_LOG";
 
+/// Value of `--theme` option printing the available themes instead of selecting one.
+const THEME_LIST: &str = "list";
+
 // ---
 
 fn bootstrap() -> Result<Settings> {
@@ -69,31 +80,41 @@ fn bootstrap() -> Result<Settings> {
     Ok(settings)
 }
 
-fn run() -> Result<()> {
+/// Runs the application and returns `false` if filters are active but no messages matched them.
+fn run() -> Result<bool> {
     let settings = bootstrap()?;
 
-    let opt = cli::Opt::parse_from(wild::args());
+    let mut opt = cli::Opt::parse_from(wild::args());
+    // watching a directory and listening on a socket are variants of follow mode
+    opt.follow |= opt.watch.is_some() || opt.listen.is_some();
     if opt.help {
-        return cli::Opt::command().print_help().map_err(Error::Io);
+        cli::Opt::command().print_help()?;
+        return Ok(true);
     }
 
     if let Some(shell) = opt.shell_completions {
         let mut cmd = cli::Opt::command();
         let name = cmd.get_name().to_string();
         clap_complete::generate(shell, &mut cmd, name, &mut stdout());
-        return Ok(());
+        return Ok(true);
     }
 
     if opt.man_page {
         let man = clap_mangen::Man::new(cli::Opt::command());
         man.render(&mut stdout())?;
-        return Ok(());
+        return Ok(true);
     }
 
     let app_dirs = config::app_dirs().ok_or(Error::AppDirs)?;
 
     if let Some(tags) = opt.list_themes {
-        return list_themes(&app_dirs, tags);
+        list_themes(&app_dirs, tags)?;
+        return Ok(true);
+    }
+
+    if opt.theme == THEME_LIST {
+        list_themes(&app_dirs, None)?;
+        return Ok(true);
     }
 
     let color_supported = if stdout().is_terminal() {
@@ -107,38 +128,66 @@ fn run() -> Result<()> {
         false
     };
 
+    // Print only the value of a single field if requested.
+    let only = if opt.only_message {
+        Some("msg".to_owned())
+    } else {
+        opt.only.clone()
+    };
+
     // Configure color scheme.
     let color = if opt.color_always {
         cli::ColorOption::Always
+    } else if opt.no_color {
+        cli::ColorOption::Never
     } else {
         opt.color
     };
+    // See https://no-color.org.
+    let no_color = env::var_os("NO_COLOR").is_some_and(|x| !x.is_empty());
     let use_colors = match color {
-        cli::ColorOption::Auto => stdout().is_terminal() && color_supported,
+        _ if opt.preview_theme.is_some() => true,
+        _ if opt.output_format == cli::OutputFormat::Html => true,
+        cli::ColorOption::Auto if only.is_some() => false,
+        cli::ColorOption::Auto => opt.output.is_none() && stdout().is_terminal() && color_supported && !no_color,
         cli::ColorOption::Always => true,
         cli::ColorOption::Never => false,
     };
 
     let theme = if use_colors {
-        let theme = &opt.theme;
-        Theme::load(&app_dirs, theme)?
+        let theme = pathx::expand(opt.preview_theme.as_ref().unwrap_or(&opt.theme))?;
+        Theme::load(&app_dirs, &theme)?
     } else {
         Theme::none()
     };
 
+    // Custom theme files are watched for changes in follow mode.
+    let theme_file = if use_colors && opt.follow {
+        hl::themecfg::Theme::custom_path(&app_dirs, &pathx::expand(&opt.theme)?)
+    } else {
+        None
+    };
+
     // Configure concurrency.
     let concurrency = match opt.concurrency.or(settings.concurrency) {
         None | Some(0) => num_cpus::get(),
         Some(value) => value,
     };
     // Configure timezone.
-    let tz = if opt.local { Tz::Local } else { Tz::IANA(opt.time_zone) };
+    let tz = if opt.utc {
+        Tz::IANA(chrono_tz::UTC)
+    } else if opt.local {
+        Tz::Local
+    } else {
+        Tz::IANA(opt.time_zone)
+    };
     // Configure time format.
     let time_format = LinuxDateFormat::new(&opt.time_format).compile();
     // Configure filter.
     let filter = hl::Filter {
         fields: hl::FieldFilterSet::new(&opt.filter)?,
         level: opt.level.map(|x| x.into()),
+        unknown_level: opt.unknown_level == cli::UnknownLevelOption::Show,
         since: if let Some(v) = &opt.since {
             Some(parse_time(v, &tz, &time_format)?.with_timezone(&Utc))
         } else {
@@ -157,13 +206,19 @@ fn run() -> Result<()> {
     let all = || IncludeExcludeKeyFilter::new(KeyMatchOptions::default());
     let none = || all().excluded();
     let mut fields = all();
+    if !opt.show.is_empty() {
+        fields = none();
+        for key in &opt.show {
+            fields.entry(key).include();
+        }
+    }
     for (i, key) in settings.fields.hide.iter().chain(&opt.hide).enumerate() {
         if key == "*" {
             fields = none();
         } else if key == "!*" {
             fields = all();
         } else if let Some(stripped) = key.strip_prefix("!") {
-            if i == 0 {
+            if i == 0 && opt.show.is_empty() {
                 fields = none();
             }
             fields.entry(stripped).include();
@@ -177,9 +232,14 @@ fn run() -> Result<()> {
     let max_message_size = opt.max_message_size;
     let buffer_size = std::cmp::min(max_message_size, opt.buffer_size);
 
+    let queries = opt.query.iter().map(|q| Query::parse(q).map_err(Error::from));
+    let included = opt.include.iter().map(|f| Query::field(f));
+    let excluded = opt.exclude.iter().map(|f| Query::field(f).map(|q| !q));
+    let present = only.iter().filter(|_| opt.skip_missing).map(|key| Query::exists(key));
+
     let mut query: Option<Query> = None;
-    for q in &opt.query {
-        let right = Query::parse(q)?;
+    for right in queries.chain(included).chain(excluded).chain(present) {
+        let right = right?;
         if let Some(left) = query {
             query = Some(left.and(right));
         } else {
@@ -187,24 +247,52 @@ fn run() -> Result<()> {
         }
     }
 
-    let mut delimiter = Delimiter::default();
-    if let Some(d) = opt.delimiter {
-        delimiter = match d.to_lowercase().as_str() {
-            "nul" => Delimiter::Byte(0),
-            "lf" => Delimiter::Byte(b'\n'),
-            "cr" => Delimiter::Byte(b'\r'),
-            "crlf" => Delimiter::default(),
-            _ => {
-                if d.len() == 1 {
-                    Delimiter::Byte(d.as_bytes()[0])
-                } else if d.len() > 1 {
-                    Delimiter::Str(d)
-                } else {
-                    Delimiter::default()
-                }
-            }
-        };
-    }
+    // Compile conditional style rules, the ones given on the command line take precedence.
+    let highlighted = opt
+        .highlight
+        .iter()
+        .map(Query::parse)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let marked = highlighted.iter().map(|query| {
+        Ok(StyleRule {
+            query: query.clone(),
+            field: None,
+            style: settings.highlight_style.clone(),
+        })
+    });
+    let configured = settings.rules.iter().map(|rule| {
+        Ok(StyleRule {
+            query: Query::parse(&rule.when)?,
+            field: rule.field.clone(),
+            style: rule.style.clone(),
+        })
+    });
+    let rules = marked.chain(configured).collect::<Result<Vec<_>>>()?;
+
+    // Configure highlighting of text matched by filters and highlighting queries.
+    let highlighter = if opt.no_highlight {
+        None
+    } else {
+        let queried = query
+            .iter()
+            .chain(&highlighted)
+            .flat_map(|q| q.highlights().iter().cloned());
+        Highlighter::new(filter.fields.highlights().chain(queried))?.map(Arc::new)
+    };
+
+    let mut delimiter = match &opt.delimiter {
+        Some(d) => d.parse()?,
+        None => Delimiter::default(),
+    };
+
+    // frames are decoded into NUL-terminated records, which cannot appear in valid JSON or logfmt
+    let framing = match opt.framing {
+        cli::Framing::Lines => None,
+        cli::Framing::LengthPrefixed => {
+            delimiter = Delimiter::Byte(0);
+            Some(opt.frame_header.into())
+        }
+    };
 
     let mut input_info = *opt.input_info;
     if input_info.contains(InputInfo::Auto) {
@@ -241,9 +329,36 @@ fn run() -> Result<()> {
     let utf8_is_supported = matches!(utf8_supported(), Utf8Support::UTF8);
     let ascii = ascii_opt.resolve(utf8_is_supported);
 
+    // Prefer timestamp and message keys given on the command line.
+    let mut field_settings = settings.fields.clone();
+    field_settings.predefined.time.0.prioritize(&opt.time_key);
+    field_settings.predefined.message.0.prioritize(&opt.message_key);
+
+    // Configure wrapping of long lines at the terminal width.
+    let wrap_mode = match opt.wrap {
+        cli::WrapOption::Never => WrapMode::Never,
+        cli::WrapOption::Word => WrapMode::Word,
+        cli::WrapOption::Char => WrapMode::Char,
+    };
+    let wrapper = match wrap_mode {
+        WrapMode::Never => None,
+        _ => match terminal_size_of(stdout()) {
+            Some((w, _)) => Wrapper::new(wrap_mode, w.0.into()),
+            None => {
+                log::debug!("no terminal detected, lines are not wrapped");
+                None
+            }
+        },
+    };
+
+    // Configure advanced filter.
+    let filter = app::AdvancedFilter::new(filter, query);
+    let filtering = !filter.is_empty();
+
     // Create app.
     let app = hl::App::new(hl::Options {
         theme: Arc::new(theme),
+        colors: use_colors,
         raw: opt.raw,
         raw_fields: opt.raw_fields,
         allow_prefix: opt.allow_prefix,
@@ -251,9 +366,9 @@ fn run() -> Result<()> {
         buffer_size,
         max_message_size,
         concurrency,
-        filter: app::AdvancedFilter::new(filter, query).into(),
+        filter: filter.into(),
         fields: hl::FieldOptions {
-            settings: settings.fields.clone(),
+            settings: field_settings,
             filter: Arc::new(fields),
         },
         formatting: settings.formatting.clone(),
@@ -263,15 +378,59 @@ fn run() -> Result<()> {
         follow: opt.follow,
         sync_interval: Duration::from_millis(opt.sync_interval_ms),
         input_info,
+        filename: match (opt.with_filename, opt.no_filename) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        },
         input_format: match opt.input_format {
             cli::InputFormat::Auto => None,
             cli::InputFormat::Json => Some(app::InputFormat::Json),
             cli::InputFormat::Logfmt => Some(app::InputFormat::Logfmt),
+            cli::InputFormat::Syslog => Some(app::InputFormat::Syslog),
+            cli::InputFormat::Otlp => Some(app::InputFormat::Otlp),
+            cli::InputFormat::Gelf => Some(app::InputFormat::Gelf),
+            cli::InputFormat::Winevent => Some(app::InputFormat::WinEvent),
         },
+        output_format: match opt.output_format {
+            // the external command receives messages as JSON
+            _ if opt.exec.is_some() => app::OutputFormat::Json,
+            cli::OutputFormat::Text => app::OutputFormat::Text,
+            cli::OutputFormat::Logfmt => app::OutputFormat::Logfmt,
+            cli::OutputFormat::Html => app::OutputFormat::Html,
+            cli::OutputFormat::Json => app::OutputFormat::Json,
+        },
+        html_standalone: opt.html_standalone,
         dump_index: opt.dump_index,
+        progress: !opt.no_progress && stderr().is_terminal(),
+        index_mode: match opt.index {
+            cli::IndexOption::Auto => IndexMode::Auto,
+            cli::IndexOption::Rebuild => IndexMode::Rebuild,
+            cli::IndexOption::None => IndexMode::Disabled,
+        },
         app_dirs: Some(app_dirs),
         tail: opt.tail,
+        range: match &opt.range {
+            Some(range) => Some(range.parse()?),
+            None if opt.skip.is_some() || opt.head.is_some() => {
+                Some(app::RecordRange::new(opt.skip.unwrap_or(0), opt.head))
+            }
+            None => None,
+        },
+        group_by: opt.group_by.clone(),
+        watch: opt
+            .watch
+            .as_deref()
+            .map(pathx::expand_path)
+            .transpose()?
+            .map(|dir| app::WatchOptions {
+                dir,
+                glob: opt.glob.clone(),
+            }),
+        listen: opt.listen.as_deref().map(str::parse).transpose()?,
         delimiter,
+        framing,
+        decompression: opt.decompress.into(),
         unix_ts_unit: match opt.unix_timestamp_unit {
             cli::UnixTimestampUnit::Auto => None,
             cli::UnixTimestampUnit::S => Some(app::UnixTimestampUnit::Seconds),
@@ -280,7 +439,56 @@ fn run() -> Result<()> {
             cli::UnixTimestampUnit::Ns => Some(app::UnixTimestampUnit::Nanoseconds),
         },
         flatten: opt.flatten != cli::FlattenOption::Never,
+        flatten_depth: opt.flatten_depth,
         ascii,
+        stats: opt.stats.then(|| hl::stats::StatsOptions {
+            group_by: opt.stats_group_by.clone(),
+            top: opt.stats_top,
+            format: match opt.stats_format {
+                cli::StatsFormat::Table => hl::stats::StatsFormat::Table,
+                cli::StatsFormat::Json => hl::stats::StatsFormat::Json,
+            },
+        }),
+        count: (opt.count || opt.count_by.is_some()).then(|| hl::stats::CountOptions {
+            by: opt.count_by.clone(),
+        }),
+        highlighter,
+        output_fields: opt.output_fields.clone(),
+        color_by: opt.color_by.clone(),
+        strict: opt.strict.map(|strict| match strict {
+            cli::StrictOption::Error => app::StrictMode::Error,
+            cli::StrictOption::Warn => app::StrictMode::Warn,
+        }),
+        dedup: opt.dedup || opt.dedup_window.is_some(),
+        dedup_window: opt.dedup_window,
+        context: app::ContextOptions {
+            before: opt.before_context.or(opt.context).unwrap_or(0),
+            after: opt.after_context.or(opt.context).unwrap_or(0),
+        },
+        sorted_input: opt.sorted_input,
+        wrapper,
+        max_message_width: opt.max_message_width,
+        max_field_width: opt.max_field_width,
+        error_key: opt.error_key.clone(),
+        expand_json: opt.expand_json,
+        render_template: opt.render_template,
+        drop_template_fields: opt.drop_template_fields,
+        strip_input_ansi: opt.strip_input_ansi,
+        pretty: opt.pretty && !opt.compact,
+        field_quote: match opt.field_quote {
+            cli::FieldQuoteOption::Auto => FieldQuote::Auto,
+            cli::FieldQuoteOption::Always => FieldQuote::Always,
+            cli::FieldQuoteOption::Never => FieldQuote::Never,
+        },
+        debug_parse: opt.debug_parse,
+        bookmark: opt.bookmark,
+        rules,
+        normalize: opt.normalize,
+        original_keys: opt.original_keys,
+        columns: opt.columns.clone(),
+        column_width: opt.column_width,
+        only,
+        theme_file,
     });
 
     // Configure the input.
@@ -289,35 +497,50 @@ fn run() -> Result<()> {
         .iter()
         .map(|x| {
             if x.to_str() == Some("-") {
-                Ok::<_, std::io::Error>(InputReference::Stdin)
+                Ok(InputReference::Stdin)
             } else {
-                Ok(InputReference::File(x.clone().try_into()?))
+                Ok(InputReference::File(pathx::expand_path(x)?.try_into()?))
             }
         })
-        .collect::<std::result::Result<Vec<_>, _>>()?;
-    if inputs.is_empty() {
-        if stdin().is_terminal() {
-            let mut cmd = cli::Opt::command();
-            return cmd.print_help().map_err(Error::Io);
+        .collect::<Result<Vec<_>>>()?;
+    if inputs.is_empty() && opt.watch.is_none() && opt.listen.is_none() {
+        if stdin().is_terminal() && opt.preview_theme.is_none() {
+            cli::Opt::command().print_help()?;
+            return Ok(true);
         }
         inputs.push(InputReference::Stdin);
     }
 
     let n = inputs.len();
     log::debug!("hold {n} inputs");
-    let inputs = inputs
-        .into_iter()
-        .map(|input| input.hold().map_err(Error::Io))
-        .collect::<Result<Vec<_>>>()?;
+    let inputs = if opt.preview_theme.is_some() {
+        vec![InputHolder::new(
+            InputReference::Stdin,
+            Some(Box::new(Cursor::new(THEME_PREVIEW_SAMPLE))),
+        )]
+    } else {
+        inputs
+            .into_iter()
+            .map(|input| input.hold().map_err(Error::Io))
+            .collect::<Result<Vec<_>>>()?
+    };
 
     let paging = match opt.paging {
         cli::PagingOption::Auto => stdout().is_terminal(),
         cli::PagingOption::Always => true,
         cli::PagingOption::Never => false,
     };
-    let paging = if opt.paging_never || opt.follow { false } else { paging };
+    let paging = if opt.paging_never || opt.follow || opt.quiet {
+        false
+    } else {
+        paging
+    };
     let mut output: OutputStream = match opt.output {
-        Some(output) => Box::new(std::fs::File::create(PathBuf::from(&output))?),
+        _ if opt.quiet => Box::new(std::io::sink()),
+        Some(output) => Box::new(OutputFile::create(
+            PathBuf::from(&output),
+            opt.output_max_size.map(|size| size.get() as u64),
+        )?),
         None => {
             if paging {
                 if let Ok(pager) = Pager::new() {
@@ -331,10 +554,24 @@ fn run() -> Result<()> {
         }
     };
 
+    // Pass the formatted messages through the external command.
+    let mut exec = None;
+    let output: &mut app::Output = match &opt.exec {
+        Some(command) => {
+            let mode = if opt.exec_per_message {
+                ExecMode::PerRecord
+            } else {
+                ExecMode::Stream
+            };
+            exec.insert(ExecOutput::new(command, mode, output)?)
+        }
+        None => output.as_mut(),
+    };
+
     log::debug!("run the app");
 
     // Run the app.
-    let run = || match app.run(inputs, output.as_mut()) {
+    let run = || match app.run(inputs, output) {
         Ok(()) => Ok(()),
         Err(Error::Io(ref e)) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
         Err(err) => Err(err),
@@ -343,9 +580,31 @@ fn run() -> Result<()> {
     let interrupt_ignore_count = if opt.follow { 0 } else { opt.interrupt_ignore_count };
 
     // Run the app with signal handling.
-    SignalHandler::run(interrupt_ignore_count, std::time::Duration::from_secs(1), run)
+    SignalHandler::run(interrupt_ignore_count, std::time::Duration::from_secs(1), run)?;
+
+    // Wait for the external command to exit and report its failure.
+    if let Some(exec) = &mut exec {
+        exec.finish()?;
+    }
+
+    // Report failure like grep does if filters are active and nothing matched.
+    Ok(app.matched() || !(filtering || opt.quiet))
 }
 
+/// Sample messages used by --preview-theme option, one per level.
+const THEME_PREVIEW_SAMPLE: &str = concat!(
+    r#"{"ts":"2024-01-02T03:04:05.006Z","level":"trace","logger":"app.http","msg":"connection opened","peer":{"addr":"10.0.0.1","port":50123},"caller":"http/conn.go:42"}"#,
+    "\n",
+    r#"{"ts":"2024-01-02T03:04:05.124Z","level":"debug","logger":"app.http","msg":"request received","req":{"method":"GET","path":"/api/users","headers":{"accept":"application/json"}},"caller":"http/server.go:118"}"#,
+    "\n",
+    r#"{"ts":"2024-01-02T03:04:05.371Z","level":"info","logger":"app.db","msg":"query completed","rows":42,"cached":false,"duration":"1.2ms","tags":["users","read"],"caller":"db/query.go:77"}"#,
+    "\n",
+    r#"{"ts":"2024-01-02T03:04:06.003Z","level":"warning","logger":"app.pool","msg":"pool is almost exhausted","pool":{"size":10,"idle":1},"threshold":0.9,"caller":"pool/pool.go:203"}"#,
+    "\n",
+    r#"{"ts":"2024-01-02T03:04:06.518Z","level":"error","logger":"app.http","msg":"request failed","req":{"method":"POST","path":"/api/orders"},"error":"upstream timeout","retry":null,"caller":"http/server.go:164"}"#,
+    "\n",
+);
+
 fn list_themes(app_dirs: &AppDirs, tags: Option<cli::ThemeTagSet>) -> Result<()> {
     let items = Theme::list(app_dirs)?;
     let mut formatter = help::Formatter::new(stdout());
@@ -372,9 +631,13 @@ fn list_themes(app_dirs: &AppDirs, tags: Option<cli::ThemeTagSet>) -> Result<()>
 }
 
 fn main() {
-    if let Err(err) = run() {
-        err.log(&AppInfo);
-        process::exit(1);
+    match run() {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            err.log(&AppInfo);
+            process::exit(2);
+        }
     }
 }
 
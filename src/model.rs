@@ -8,12 +8,13 @@ use std::{
     marker::PhantomData,
     ops::Range,
     str::FromStr,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 // third-party imports
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use enumset::{EnumSet, EnumSetType, enum_set};
+use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde_json::{self as json};
@@ -28,11 +29,16 @@ use serde_logfmt::logfmt;
 use crate::{
     app::{InputFormat, UnixTimestampUnit},
     error::{Error, Result},
+    filtering::pointer_segment,
+    gelf,
     level::{self},
+    otlp,
     serdex::StreamDeserializerWithOffsets,
-    settings::PredefinedFields,
+    settings::{FieldAliases, PredefinedFields, Settings},
+    syslog,
     timestamp::Timestamp,
     types::FieldKind,
+    winevent,
 };
 
 // test imports
@@ -123,7 +129,7 @@ impl<'a> RawValue<'a> {
     pub fn parse<T: Deserialize<'a>>(&self) -> Result<T> {
         let (s, is_json) = match self {
             Self::String(EncodedString::Json(value)) => (value.source(), true),
-            Self::String(EncodedString::Raw(value)) => (value.source(), false),
+            Self::String(value) => (value.source(), false),
             Self::Object(value) => (value.get(), true),
             Self::Array(value) => (value.get(), true),
             Self::Null => ("null", true),
@@ -216,6 +222,8 @@ impl<'a> From<RawArray<'a>> for RawValue<'a> {
 #[derive(Clone, Copy, Debug)]
 pub enum RawObject<'a> {
     Json(&'a json::value::RawValue),
+    /// Parameters of a syslog structured data element, e.g. `a="1" b="2"`.
+    Syslog(&'a str),
 }
 
 impl<'a> RawObject<'a> {
@@ -223,6 +231,7 @@ impl<'a> RawObject<'a> {
     pub fn get(&self) -> &'a str {
         match self {
             Self::Json(value) => value.get(),
+            Self::Syslog(value) => value,
         }
     }
 
@@ -230,6 +239,7 @@ impl<'a> RawObject<'a> {
     pub fn parse(&self) -> Result<Object<'a>> {
         match self {
             Self::Json(value) => Object::from_json(value.get()),
+            Self::Syslog(value) => Object::from_syslog(value),
         }
     }
 
@@ -237,6 +247,7 @@ impl<'a> RawObject<'a> {
     pub fn parse_into(&self, target: &mut Object<'a>) -> Result<()> {
         match self {
             Self::Json(value) => target.set_from_json(value.get()),
+            Self::Syslog(value) => target.set_from_syslog(value),
         }
     }
 
@@ -244,6 +255,7 @@ impl<'a> RawObject<'a> {
     pub fn is_empty(&self) -> bool {
         match self {
             Self::Json(value) => json_match(value, "{}"),
+            Self::Syslog(value) => value.trim_start_matches(' ').is_empty(),
         }
     }
 }
@@ -315,12 +327,21 @@ impl Eq for RawArray<'_> {}
 
 // ---
 
+/// Parsed log record referring to the source data.
+///
+/// Predefined fields recognized using the parser settings are exposed directly,
+/// and the rest of the fields are available via [`Record::fields`] and [`Record::get`].
 #[derive(Default)]
 pub struct Record<'a> {
+    /// Timestamp, if found.
     pub ts: Option<Timestamp<'a>>,
+    /// Message, if found.
     pub message: Option<RawValue<'a>>,
+    /// Level, if found and recognized.
     pub level: Option<Level>,
+    /// Logger name, if found.
     pub logger: Option<&'a str>,
+    /// Caller information, empty if not found.
     pub caller: Caller<'a>,
     pub(crate) fields: RecordFields<'a>,
     pub(crate) predefined: heapless::Vec<(&'a str, RawValue<'a>), MAX_PREDEFINED_FIELDS>,
@@ -342,6 +363,45 @@ impl<'a> Record<'a> {
         filter.apply(self)
     }
 
+    /// Returns the value of the field with the given key.
+    ///
+    /// Dotted keys are looked up in nested objects as well as in flat keys containing dots.
+    /// JSON Pointers, e.g. `/a/b`, are looked up by whole keys at each level.
+    pub fn get(&self, key: &str) -> Option<RawValue<'a>> {
+        match FieldFilterKey::parse(key).ok()? {
+            FieldFilterKey::Predefined(FieldKind::Message) => self.message,
+            FieldFilterKey::Predefined(FieldKind::Logger) => {
+                self.logger.map(|x| RawValue::String(EncodedString::raw(x)))
+            }
+            FieldFilterKey::Predefined(_) => None,
+            FieldFilterKey::Custom(key) => Self::get_nested(self.fields_for_search(), KeyMatcher::new(key)),
+        }
+    }
+
+    fn get_nested<'b, I>(fields: I, matcher: KeyMatcher) -> Option<RawValue<'a>>
+    where
+        I: IntoIterator<Item = &'b (&'a str, RawValue<'a>)>,
+        'a: 'b,
+    {
+        for (k, v) in fields {
+            match matcher.match_key(k) {
+                Some(KeyMatch::Full) => return Some(*v),
+                Some(KeyMatch::Partial(subkey)) => {
+                    if let RawValue::Object(value) = v {
+                        let mut item = Object::default();
+                        if value.parse_into(&mut item).is_ok() {
+                            if let Some(value) = Self::get_nested(item.fields.iter(), subkey) {
+                                return Some(value);
+                            }
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+        None
+    }
+
     fn with_capacity(capacity: usize) -> Self {
         Self {
             ts: None,
@@ -477,7 +537,7 @@ impl<T: RecordFilter> RecordFilter for &T {
 impl RecordFilter for Level {
     #[inline]
     fn apply<'a>(&self, record: &Record<'a>) -> bool {
-        record.level.is_some_and(|x| x <= *self)
+        record.level.is_some_and(|x| x >= *self)
     }
 }
 
@@ -538,6 +598,7 @@ pub struct ParserSettings {
     level: Vec<(HashMap<String, Level>, Option<Level>)>,
     blocks: Vec<ParserSettingsBlock>,
     ignore: Vec<Pattern<String>>,
+    aliases: HashMap<String, &'static str>,
 }
 
 impl ParserSettings {
@@ -551,12 +612,32 @@ impl ParserSettings {
             level: Vec::new(),
             blocks: vec![ParserSettingsBlock::default()],
             ignore: ignore.into_iter().map(|x| Pattern::new(x.to_string())).collect(),
+            aliases: HashMap::new(),
         };
 
         result.init(predefined);
         result
     }
 
+    /// Sets the canonical keys replacing the given alternative keys of top-level fields before any other processing.
+    pub fn with_aliases(self, aliases: &FieldAliases) -> Self {
+        Self {
+            aliases: aliases
+                .iter()
+                .map(|(alias, key)| (alias.clone(), intern(key)))
+                .collect(),
+            ..self
+        }
+    }
+
+    #[inline]
+    fn canonical<'a>(&self, key: &'a str) -> &'a str {
+        if self.aliases.is_empty() {
+            return key;
+        }
+        self.aliases.get(key).copied().unwrap_or(key)
+    }
+
     fn init(&mut self, pf: &PredefinedFields) {
         self.build_block(0, &pf.time.names, FieldSettings::Time, 0);
         self.build_block(0, &pf.message.names, FieldSettings::Message, 0);
@@ -681,6 +762,8 @@ impl ParserSettingsBlock {
         pc: &mut PriorityController,
         is_root: bool,
     ) {
+        let key = if is_root { ps.canonical(key) } else { key };
+        let traced = pc.trace.as_mut().map(|trace| trace.enter(key));
         let done = match self.fields.get(key) {
             Some((field, priority)) => {
                 let kind = field.kind();
@@ -693,7 +776,20 @@ impl ParserSettingsBlock {
             }
             None => false,
         };
+        if let (Some(trace), Some(len)) = (&mut pc.trace, traced) {
+            trace.leave(len);
+        }
         if is_root && done {
+            // a message candidate replaced by the one with higher priority remains a normal field
+            if matches!(self.fields.get(key), Some((FieldSettings::Message, _))) {
+                let replaced = to
+                    .predefined
+                    .iter()
+                    .position(|(k, _)| matches!(self.fields.get(*k), Some((FieldSettings::Message, _))));
+                if let Some(i) = replaced {
+                    to.fields.push(to.predefined.remove(i));
+                }
+            }
             to.predefined.push((key, value)).ok();
         }
         if done || !is_root {
@@ -702,6 +798,9 @@ impl ParserSettingsBlock {
 
         for pattern in &ps.ignore {
             if pattern.matches(key) {
+                if let Some(trace) = &mut pc.trace {
+                    trace.events.push(ParseEvent::Ignored(key.into()));
+                }
                 return;
             }
         }
@@ -737,6 +836,7 @@ struct PriorityController {
     caller: Option<usize>,
     caller_file: Option<usize>,
     caller_line: Option<usize>,
+    trace: Option<ParseTrace>,
 }
 
 impl PriorityController {
@@ -753,8 +853,16 @@ impl PriorityController {
         };
 
         if p.is_none() || Some(priority) <= *p {
-            *p = Some(priority);
-            update(self)
+            let prev = p.replace(priority);
+            let done = update(self);
+            // an empty message does not prevent the next candidate from being used
+            if !done && kind == FieldKind::Message {
+                self.message = prev;
+            }
+            if let Some(trace) = &mut self.trace {
+                trace.add(kind, done);
+            }
+            done
         } else {
             false
         }
@@ -808,6 +916,9 @@ impl FieldSettings {
                 true
             }
             Self::Message => {
+                if value.is_empty() {
+                    return false;
+                }
                 to.message = Some(value);
                 true
             }
@@ -901,6 +1012,194 @@ impl Parser {
 
         record
     }
+
+    /// Parses the record like [`Parser::parse`] and also returns how its fields were interpreted.
+    pub fn parse_traced<'a>(&self, record: &RawRecord<'a>) -> (Record<'a>, ParseTrace) {
+        let fields = record.fields();
+        let count = fields.size_hint().1.unwrap_or(0);
+        let mut record = Record::<'a>::with_capacity(count);
+
+        let mut pc = PriorityController {
+            trace: Some(ParseTrace::default()),
+            ..Default::default()
+        };
+        self.settings.apply_each_ctx(fields, &mut record, &mut pc);
+
+        (record, pc.trace.unwrap_or_default())
+    }
+}
+
+// ---
+
+/// Decisions made by [`Parser::parse_traced`] about the fields of a record, in the order of the fields.
+///
+/// Nested fields are identified by their dot-separated path.
+#[derive(Default, Debug)]
+pub struct ParseTrace {
+    path: String,
+    events: Vec<ParseEvent>,
+}
+
+impl ParseTrace {
+    #[inline]
+    pub fn events(&self) -> &[ParseEvent] {
+        &self.events
+    }
+
+    /// Returns the key of the field used as the predefined field of the given kind.
+    pub fn key(&self, kind: FieldKind) -> Option<&str> {
+        self.events.iter().rev().find_map(|event| match event {
+            ParseEvent::Mapped(k, key) if *k == kind => Some(key.as_str()),
+            _ => None,
+        })
+    }
+
+    fn enter(&mut self, key: &str) -> usize {
+        let len = self.path.len();
+        if len != 0 {
+            self.path.push('.');
+        }
+        self.path.push_str(key);
+        len
+    }
+
+    fn leave(&mut self, len: usize) {
+        self.path.truncate(len);
+    }
+
+    fn add(&mut self, kind: FieldKind, done: bool) {
+        let key = self.path.clone();
+        self.events.push(match done {
+            true => ParseEvent::Mapped(kind, key),
+            false => ParseEvent::Rejected(kind, key),
+        });
+    }
+}
+
+/// Formats the trace as `time=ts level=level message=msg`, followed by the logger, caller,
+/// ignored and rejected fields if there are any, with `-` standing for a missing field.
+impl fmt::Display for ParseTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kinds = [
+            (FieldKind::Time, "time"),
+            (FieldKind::Level, "level"),
+            (FieldKind::Message, "message"),
+        ];
+        for (i, (kind, name)) in kinds.into_iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", name, self.key(kind).unwrap_or("-"))?;
+        }
+
+        let optional = [
+            (FieldKind::Logger, "logger"),
+            (FieldKind::Caller, "caller"),
+            (FieldKind::CallerFile, "caller-file"),
+            (FieldKind::CallerLine, "caller-line"),
+        ];
+        for (kind, name) in optional {
+            if let Some(key) = self.key(kind) {
+                write!(f, " {}={}", name, key)?;
+            }
+        }
+
+        let mut list = |name: &str, keys: &mut dyn Iterator<Item = &str>| -> fmt::Result {
+            if let Some(first) = keys.next() {
+                write!(f, " {}={}", name, first)?;
+                for key in keys {
+                    write!(f, ",{}", key)?;
+                }
+            }
+            Ok(())
+        };
+        list(
+            "ignored",
+            &mut self.events.iter().filter_map(|event| match event {
+                ParseEvent::Ignored(key) => Some(key.as_str()),
+                _ => None,
+            }),
+        )?;
+        list(
+            "unmapped",
+            &mut self.events.iter().filter_map(|event| match event {
+                ParseEvent::Rejected(_, key) => Some(key.as_str()),
+                _ => None,
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEvent {
+    /// The field is used as the predefined field of the given kind.
+    Mapped(FieldKind, String),
+    /// The field is a candidate for the predefined field of the given kind, but its value is not recognized.
+    Rejected(FieldKind, String),
+    /// The field is dropped because its key matches an ignore pattern.
+    Ignored(String),
+}
+
+// ---
+
+/// Options for [`parse_record`].
+pub struct ParseOptions {
+    parser: Parser,
+    allow_prefix: bool,
+    format: Option<InputFormat>,
+}
+
+impl ParseOptions {
+    /// Creates options using the given parser settings with automatic input format detection.
+    pub fn new(settings: ParserSettings) -> Self {
+        Self {
+            parser: Parser::new(settings),
+            allow_prefix: false,
+            format: None,
+        }
+    }
+
+    /// Allows non-JSON prefixes before JSON records.
+    pub fn with_allow_prefix(self, allow_prefix: bool) -> Self {
+        Self { allow_prefix, ..self }
+    }
+
+    /// Sets the input format, or enables automatic detection if `None`.
+    pub fn with_input_format(self, format: Option<InputFormat>) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Returns a parser splitting a line into raw records according to the options.
+    pub(crate) fn raw_parser(&self) -> RawRecordParser {
+        RawRecordParser::new()
+            .allow_prefix(self.allow_prefix)
+            .format(self.format)
+    }
+}
+
+impl Default for ParseOptions {
+    /// Creates options using the fields configured in the default settings.
+    fn default() -> Self {
+        let settings: &Settings = Default::default();
+        Self::new(
+            ParserSettings::new(&settings.fields.predefined, &settings.fields.ignore, None)
+                .with_aliases(&settings.fields.aliases),
+        )
+    }
+}
+
+/// Parses a log line in JSON or logfmt format into a record.
+///
+/// The input format is detected automatically unless it is set in the options.
+/// If the line contains several records, only the first one is returned.
+pub fn parse_record<'a>(line: &'a str, options: &ParseOptions) -> Result<Record<'a>> {
+    let mut stream = options.raw_parser().parse(line.as_bytes());
+
+    match stream.next() {
+        Some(Ok(ar)) => Ok(options.parser.parse(&ar.record)),
+        Some(Err(err)) => Err(err),
+        None => Err(Error::NoRecord),
+    }
 }
 
 // ---
@@ -988,7 +1287,11 @@ impl RawRecordParser {
     pub fn parse<'a>(
         &self,
         line: &'a [u8],
-    ) -> RawRecordStream<impl RawRecordIterator<'a> + use<'a>, impl RawRecordIterator<'a> + use<'a>> {
+    ) -> RawRecordStream<
+        impl RawRecordIterator<'a> + use<'a>,
+        impl RawRecordIterator<'a> + use<'a>,
+        impl RawRecordIterator<'a> + use<'a>,
+    > {
         let prefix = if self.allow_prefix && line.last() == Some(&b'}') {
             line.split(|c| *c == b'{').next().unwrap()
         } else {
@@ -1003,6 +1306,8 @@ impl RawRecordParser {
                 None
             } else if data[0] == b'{' {
                 Some(InputFormat::Json)
+            } else if syslog::looks_like_syslog(data) {
+                Some(InputFormat::Syslog)
             } else {
                 Some(InputFormat::Logfmt)
             }
@@ -1010,10 +1315,18 @@ impl RawRecordParser {
 
         match format {
             None => RawRecordStream::Empty,
-            Some(InputFormat::Json) => RawRecordStream::Json(RawRecordJsonStream {
-                prefix,
-                delegate: StreamDeserializerWithOffsets(json::Deserializer::from_slice(data).into_iter::<RawRecord>()),
-            }),
+            Some(InputFormat::Syslog) => RawRecordStream::Syslog(RawRecordSyslogStream { line, done: false }),
+            Some(format @ (InputFormat::Json | InputFormat::Otlp | InputFormat::Gelf | InputFormat::WinEvent)) => {
+                RawRecordStream::Json(RawRecordJsonStream {
+                    prefix,
+                    otlp: format == InputFormat::Otlp,
+                    gelf: format == InputFormat::Gelf,
+                    winevent: format == InputFormat::WinEvent,
+                    delegate: StreamDeserializerWithOffsets(
+                        json::Deserializer::from_slice(data).into_iter::<RawRecord>(),
+                    ),
+                })
+            }
             Some(InputFormat::Logfmt) => RawRecordStream::Logfmt(RawRecordLogfmtStream {
                 line,
                 prefix,
@@ -1026,16 +1339,18 @@ impl RawRecordParser {
 // ---
 
 #[derive(Debug)]
-pub enum RawRecordStream<Json, Logfmt> {
+pub enum RawRecordStream<Json, Logfmt, Syslog> {
     Empty,
     Json(Json),
     Logfmt(Logfmt),
+    Syslog(Syslog),
 }
 
-impl<'a, Json, Logfmt> RawRecordStream<Json, Logfmt>
+impl<'a, Json, Logfmt, Syslog> RawRecordStream<Json, Logfmt, Syslog>
 where
     Json: RawRecordIterator<'a>,
     Logfmt: RawRecordIterator<'a>,
+    Syslog: RawRecordIterator<'a>,
 {
     #[inline]
     pub fn next(&mut self) -> Option<Result<AnnotatedRawRecord<'a>>> {
@@ -1043,6 +1358,18 @@ where
             Self::Empty => None,
             Self::Json(stream) => stream.next(),
             Self::Logfmt(stream) => stream.next(),
+            Self::Syslog(stream) => stream.next(),
+        }
+    }
+
+    /// Returns the name of the format the line was parsed as, or `None` if it is empty.
+    #[inline]
+    pub fn format_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Empty => None,
+            Self::Json(_) => Some("json"),
+            Self::Logfmt(_) => Some("logfmt"),
+            Self::Syslog(_) => Some("syslog"),
         }
     }
 
@@ -1074,6 +1401,9 @@ pub struct AnnotatedRawRecord<'a> {
 
 struct RawRecordJsonStream<'a, R> {
     prefix: &'a [u8],
+    otlp: bool,
+    gelf: bool,
+    winevent: bool,
     delegate: StreamDeserializerWithOffsets<'a, R, RawRecord<'a>>,
 }
 
@@ -1085,7 +1415,16 @@ where
     fn next(&mut self) -> Option<Result<AnnotatedRawRecord<'a>>> {
         let pl = self.prefix.len();
         self.delegate.next().map(|res| {
-            res.map(|(record, range)| {
+            res.map(|(mut record, range)| {
+                if self.otlp {
+                    otlp::flatten(&mut record.fields);
+                }
+                if self.gelf {
+                    gelf::strip_extra_field_prefixes(&mut record.fields);
+                }
+                if self.winevent {
+                    winevent::flatten(&mut record.fields);
+                }
                 let range = range.start + pl..range.end + pl;
                 AnnotatedRawRecord {
                     prefix: self.prefix,
@@ -1127,6 +1466,62 @@ impl<'a> RawRecordIterator<'a> for RawRecordLogfmtStream<'a> {
 
 // ---
 
+struct RawRecordSyslogStream<'a> {
+    line: &'a [u8],
+    done: bool,
+}
+
+impl<'a> RawRecordIterator<'a> for RawRecordSyslogStream<'a> {
+    #[inline]
+    fn next(&mut self) -> Option<Result<AnnotatedRawRecord<'a>>> {
+        if self.done {
+            return None;
+        }
+
+        self.done = true;
+        let message = match std::str::from_utf8(self.line)
+            .map_err(Error::from)
+            .and_then(syslog::Message::parse)
+        {
+            Ok(message) => message,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let mut record = RawRecord::default();
+        let fields = &mut record.fields;
+        let string = |value: &'a str| RawValue::String(EncodedString::raw(value));
+        if let Some(ts) = message.timestamp {
+            fields.push(("ts", string(ts)));
+        }
+        fields.push(("level", string(message.level())));
+        fields.push(("facility", string(message.facility_name())));
+        for (key, value) in [
+            ("host", message.hostname),
+            ("app", message.app_name),
+            ("pid", message.proc_id),
+            ("msgid", message.msg_id),
+        ] {
+            if let Some(value) = value {
+                fields.push((key, string(value)));
+            }
+        }
+        for (id, params) in message.elements() {
+            fields.push((id, RawValue::Object(RawObject::Syslog(params))));
+        }
+        if let Some(msg) = message.msg {
+            fields.push(("msg", string(msg)));
+        }
+
+        Some(Ok(AnnotatedRawRecord {
+            prefix: b"",
+            record,
+            offsets: 0..self.line.len(),
+        }))
+    }
+}
+
+// ---
+
 struct ObjectVisitor<'a, 't, RV, const N: usize>
 where
     RV: ?Sized + 'a,
@@ -1209,18 +1604,30 @@ pub enum KeyMatch<'a> {
 
 // ---
 
+/// Matches field keys against a dotted key, e.g. `a.b`, or a JSON Pointer, e.g. `/a/b`.
+///
+/// A dotted key matches both a field named `a.b` and field `b` nested in field `a`,
+/// while JSON Pointer segments match whole field names only.
 #[derive(Debug, Clone, Copy)]
 pub struct KeyMatcher<'a> {
     key: &'a str,
+    pointer: bool,
 }
 
 impl<'a> KeyMatcher<'a> {
     #[inline]
     pub fn new(key: &'a str) -> Self {
-        Self { key }
+        match key.strip_prefix('/') {
+            Some(key) => Self { key, pointer: true },
+            None => Self { key, pointer: false },
+        }
     }
 
     pub fn match_key<'b>(&'b self, key: &str) -> Option<KeyMatch<'a>> {
+        if self.pointer {
+            return self.match_pointer_key(key);
+        }
+
         let bytes = self.key.as_bytes();
         if bytes
             .iter()
@@ -1234,7 +1641,7 @@ impl<'a> KeyMatcher<'a> {
             Some(KeyMatch::Full)
         } else if self.key.len() > key.len() {
             if bytes[key.len()] == b'.' {
-                Some(KeyMatch::Partial(KeyMatcher::new(&self.key[key.len() + 1..])))
+                Some(KeyMatch::Partial(self.tail(&self.key[key.len() + 1..])))
             } else {
                 None
             }
@@ -1244,10 +1651,16 @@ impl<'a> KeyMatcher<'a> {
     }
 
     pub fn index_matcher<'b>(&'b self) -> Option<(IndexMatcher, Option<KeyMatcher<'a>>)> {
+        if self.pointer {
+            let (segment, tail) = self.pointer_split();
+            let idx = segment.parse().ok()?;
+            return Some((IndexMatcher::Exact(idx), tail.map(|tail| self.tail(tail))));
+        }
+
         let bytes = self.key.as_bytes();
         match bytes {
             b"[]" => return Some((IndexMatcher::Any, None)),
-            [b'[', b']', b'.', ..] => return Some((IndexMatcher::Any, Some(KeyMatcher::new(&self.key[3..])))),
+            [b'[', b']', b'.', ..] => return Some((IndexMatcher::Any, Some(self.tail(&self.key[3..])))),
             [b'[', ..] => {
                 let tail = &bytes[1..];
                 if let Some(pos) = tail.iter().position(|c| !c.is_ascii_digit()) {
@@ -1259,9 +1672,7 @@ impl<'a> KeyMatcher<'a> {
                             } else {
                                 return Some((
                                     idx,
-                                    Some(KeyMatcher::new(unsafe {
-                                        std::str::from_utf8_unchecked(&tail[pos + 2..])
-                                    })),
+                                    Some(self.tail(unsafe { std::str::from_utf8_unchecked(&tail[pos + 2..]) })),
                                 ));
                             }
                         }
@@ -1273,6 +1684,40 @@ impl<'a> KeyMatcher<'a> {
         None
     }
 
+    fn match_pointer_key(&self, key: &str) -> Option<KeyMatch<'a>> {
+        let (segment, tail) = self.pointer_split();
+        let segment = pointer_segment(segment);
+        if segment.len() != key.len()
+            || segment
+                .chars()
+                .zip(key.chars())
+                .any(|(x, y)| Self::norm(x) != Self::norm(y))
+        {
+            return None;
+        }
+
+        Some(match tail {
+            None => KeyMatch::Full,
+            Some(tail) => KeyMatch::Partial(self.tail(tail)),
+        })
+    }
+
+    #[inline]
+    fn pointer_split(&self) -> (&'a str, Option<&'a str>) {
+        match self.key.split_once('/') {
+            Some((segment, tail)) => (segment, Some(tail)),
+            None => (self.key, None),
+        }
+    }
+
+    #[inline]
+    fn tail(&self, key: &'a str) -> Self {
+        Self {
+            key,
+            pointer: self.pointer,
+        }
+    }
+
     #[inline]
     fn norm(c: char) -> char {
         if c == '_' { '-' } else { c.to_ascii_lowercase() }
@@ -1368,6 +1813,26 @@ pub enum NumericOp {
     In(Vec<Number>),
 }
 
+/// Comparison of a time value, e.g. a timestamp in RFC 3339 format or a Unix timestamp, with a point in time.
+#[derive(Debug)]
+pub enum TimeOp {
+    Gt(DateTime<Utc>),
+    Ge(DateTime<Utc>),
+    Lt(DateTime<Utc>),
+    Le(DateTime<Utc>),
+}
+
+impl TimeOp {
+    fn matches<Tz: TimeZone>(&self, value: &DateTime<Tz>) -> bool {
+        match self {
+            Self::Gt(pattern) => value > pattern,
+            Self::Ge(pattern) => value >= pattern,
+            Self::Lt(pattern) => value < pattern,
+            Self::Le(pattern) => value <= pattern,
+        }
+    }
+}
+
 // ---
 
 pub enum ValueMatchPolicy {
@@ -1377,6 +1842,7 @@ pub enum ValueMatchPolicy {
     In(HashSet<String>),
     WildCard(Pattern<String>),
     Numerically(NumericOp),
+    Chronologically(TimeOp),
     Any,
 }
 
@@ -1403,6 +1869,7 @@ impl ValueMatchPolicy {
                     false
                 }
             }
+            Self::Chronologically(op) => Timestamp::new(subject).parse().is_some_and(|value| op.matches(&value)),
             Self::Any => true,
         }
     }
@@ -1473,7 +1940,7 @@ impl FieldFilter {
             flags,
             flat_key: match key {
                 FieldFilterKey::Predefined(_) => true,
-                FieldFilterKey::Custom(key) => !key.contains('.'),
+                FieldFilterKey::Custom(key) => !key.contains('.') && !key.starts_with('/'),
             },
         }
     }
@@ -1496,6 +1963,19 @@ impl FieldFilter {
         Err(Error::WrongFieldFilter(text.into()))
     }
 
+    /// Returns a regular expression matching the text this filter looks for,
+    /// if the filter searches for a substring or a regular expression.
+    pub(crate) fn highlight(&self) -> Option<String> {
+        if self.flags.contains(FieldFilterFlag::Negate) {
+            return None;
+        }
+        match &self.match_policy {
+            ValueMatchPolicy::SubString(value) => Some(regex::escape(value)),
+            ValueMatchPolicy::RegularExpression(regex) => Some(regex.as_str().to_owned()),
+            _ => None,
+        }
+    }
+
     fn parse_mp_op<'k>(key: &'k str, value: &str) -> Result<(&'k str, ValueMatchPolicy, FieldFilterFlags)> {
         let flags = |key: &'k str| {
             let (key, flags) = if let Some(key) = key.strip_suffix('!') {
@@ -1537,14 +2017,7 @@ impl FieldFilter {
     }
 
     fn match_value(&self, value: &str, escaped: bool) -> bool {
-        let apply = |value| {
-            let result = self.match_policy.matches(value);
-            if self.flags.contains(FieldFilterFlag::Negate) {
-                !result
-            } else {
-                result
-            }
-        };
+        let apply = |value| self.negate_if_needed(self.match_policy.matches(value));
         if escaped {
             if let Ok(value) = json::from_str::<&str>(value) {
                 return apply(value);
@@ -1556,6 +2029,25 @@ impl FieldFilter {
         apply(value)
     }
 
+    // Compares the recognized timestamp, which has already been parsed honoring the configured Unix timestamp unit.
+    fn match_timestamp(&self, ts: &Timestamp) -> bool {
+        match &self.match_policy {
+            ValueMatchPolicy::Chronologically(op) => {
+                self.negate_if_needed(ts.parse().is_some_and(|value| op.matches(&value)))
+            }
+            _ => self.match_value(ts.raw(), false),
+        }
+    }
+
+    #[inline]
+    fn negate_if_needed(&self, result: bool) -> bool {
+        if self.flags.contains(FieldFilterFlag::Negate) {
+            !result
+        } else {
+            result
+        }
+    }
+
     // Returns
     // * `None` if subkey does not match
     // * `Some(true)` if the subkey matches and the value matches
@@ -1621,7 +2113,7 @@ impl RecordFilter for FieldFilter {
             FieldFilterKey::Predefined(kind) => match kind {
                 FieldKind::Time => {
                     if let Some(ts) = &record.ts {
-                        self.match_value(ts.raw(), false)
+                        self.match_timestamp(ts)
                     } else {
                         false
                     }
@@ -1691,6 +2183,11 @@ impl FieldFilterSet {
         }
         Ok(FieldFilterSet(fields))
     }
+
+    /// Returns regular expressions matching the text the filters look for.
+    pub fn highlights(&self) -> impl Iterator<Item = String> + '_ {
+        self.0.iter().filter_map(|field| field.highlight())
+    }
 }
 
 impl RecordFilter for FieldFilterSet {
@@ -1706,6 +2203,8 @@ impl RecordFilter for FieldFilterSet {
 pub struct Filter {
     pub fields: FieldFilterSet,
     pub level: Option<Level>,
+    /// Keep records without a recognized level when `level` is set.
+    pub unknown_level: bool,
     pub since: Option<DateTime<Utc>>,
     pub until: Option<DateTime<Utc>>,
 }
@@ -1735,12 +2234,10 @@ impl RecordFilter for Filter {
         }
 
         if let Some(bound) = &self.level {
-            if let Some(level) = record.level.as_ref() {
-                if level > bound {
-                    return false;
-                }
-            } else {
-                return false;
+            match record.level {
+                Some(level) if level < *bound => return false,
+                None if !self.unknown_level => return false,
+                _ => {}
             }
         }
 
@@ -1773,6 +2270,22 @@ impl<'a, const N: usize> Object<'a, N> {
         let mut deserializer = json::Deserializer::from_str(s);
         deserializer.deserialize_map(visitor).map_err(Error::JsonParseError)
     }
+
+    #[inline]
+    pub fn from_syslog(s: &'a str) -> Result<Self> {
+        let mut result = Self::default();
+        result.set_from_syslog(s)?;
+        Ok(result)
+    }
+
+    #[inline]
+    pub fn set_from_syslog(&mut self, s: &'a str) -> Result<()> {
+        self.fields.clear();
+        for param in syslog::Params::new(s) {
+            self.fields.push(param?);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Default)]
@@ -1860,6 +2373,21 @@ fn json_match(value: &json::value::RawValue, s: &str) -> bool {
         .eq(s.as_bytes().iter())
 }
 
+/// Returns a copy of the string living until the end of the program, allocating each distinct string only once.
+///
+/// Used for canonical field keys which have to outlive any record they are substituted into.
+pub(crate) fn intern(s: &str) -> &'static str {
+    static POOL: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+    let mut pool = POOL.lock().unwrap();
+    if let Some(&s) = pool.get(s) {
+        return s;
+    }
+    let s: &'static str = Box::leak(s.into());
+    pool.insert(s);
+    s
+}
+
 // ---
 
 const RECORD_EXTRA_CAPACITY: usize = 32;
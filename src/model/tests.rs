@@ -5,7 +5,10 @@ use maplit::hashmap;
 use rstest::rstest;
 use serde_logfmt::logfmt;
 
-use crate::settings::{Field, FieldShowOption};
+use crate::{
+    query::Query,
+    settings::{Field, FieldShowOption, LevelField, RawLevelFieldVariant},
+};
 
 #[test]
 fn test_raw_record_parser_empty_line() {
@@ -52,7 +55,7 @@ fn test_raw_record_parser_default() {
 
     // Verify they both parse empty JSON correctly
     // Use a helper function to check discriminant without requiring Debug on inner types
-    fn is_json_stream<Json, Logfmt>(stream: &RawRecordStream<Json, Logfmt>) -> bool {
+    fn is_json_stream<Json, Logfmt, Syslog>(stream: &RawRecordStream<Json, Logfmt, Syslog>) -> bool {
         matches!(stream, RawRecordStream::Json(_))
     }
 
@@ -382,6 +385,25 @@ fn test_record_filter_level() {
     assert!(filter.apply(&record));
 }
 
+#[test]
+fn test_record_filter_unknown_level() {
+    let mut filter = Filter {
+        level: Some(Level::Warning),
+        ..Default::default()
+    };
+    let unknown = parse(r#"{"level":"notice","msg":"m"}"#);
+    let missing = parse(r#"{"msg":"m"}"#);
+    let info = parse(r#"{"level":"info"}"#);
+    assert!(!filter.apply(&unknown));
+    assert!(!filter.apply(&missing));
+    assert!(!filter.apply(&info));
+
+    filter.unknown_level = true;
+    assert!(filter.apply(&unknown));
+    assert!(filter.apply(&missing));
+    assert!(!filter.apply(&info));
+}
+
 #[test]
 fn test_record_filter_since() {
     let filter = Filter {
@@ -652,6 +674,95 @@ fn test_level(#[case] input: &[u8], #[case] expected: Option<Level>) {
     assert_eq!(record.level, expected);
 }
 
+#[rstest]
+#[case(br#"{"level":30}"#, Some(Level::Info))]
+#[case(br#"{"level":60}"#, Some(Level::Error))]
+#[case(br#"{"level":10}"#, Some(Level::Trace))]
+#[case(br#"level=40"#, Some(Level::Warning))]
+#[case(br#"{"level":"WARN"}"#, Some(Level::Warning))]
+#[case(br#"{"PRIORITY":"4"}"#, Some(Level::Warning))]
+#[case(br#"{"PRIORITY":7}"#, Some(Level::Debug))]
+#[case(br#"{"level":35}"#, None)]
+fn test_level_default_settings(#[case] input: &[u8], #[case] expected: Option<Level>) {
+    let settings = crate::settings::Settings::default();
+    let parser = Parser::new(ParserSettings::new(&settings.fields.predefined, [], None));
+    let record = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let record = parser.parse(&record.record);
+    assert_eq!(record.level, expected);
+}
+
+#[rstest]
+#[case(br#"{"severity":"W"}"#, Some(Level::Warning))]
+#[case(br#"{"severity":"e"}"#, Some(Level::Error))]
+#[case(br#"{"severity":"X"}"#, Some(Level::Info))]
+fn test_level_custom_mapping(#[case] input: &[u8], #[case] expected: Option<Level>) {
+    let predefined = PredefinedFields {
+        level: LevelField {
+            show: FieldShowOption::Always,
+            variants: vec![RawLevelFieldVariant {
+                names: vec!["severity".into()],
+                values: hashmap! {
+                    Level::Error.into() => vec!["E".into()],
+                    Level::Warning.into() => vec!["W".into()],
+                },
+                level: Some(Level::Info.into()),
+            }],
+        },
+        ..Default::default()
+    };
+    let parser = Parser::new(ParserSettings::new(&predefined, [], None));
+    let record = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let record = parser.parse(&record.record);
+    assert_eq!(record.level, expected);
+}
+
+#[rstest]
+#[case(br#"{"ts":"1","@t":"2","eventTime":"3"}"#, Some("2"))]
+#[case(br#"{"eventTime":"3","ts":"1"}"#, Some("3"))]
+#[case(br#"{"ts":"1"}"#, Some("1"))]
+#[case(br#"{"msg":"m"}"#, None)]
+fn test_time_key_priority(#[case] input: &[u8], #[case] expected: Option<&str>) {
+    let mut predefined = PredefinedFields::default();
+    predefined.time.0.prioritize(["@t", "eventTime"]);
+    let parser = Parser::new(ParserSettings::new(&predefined, [], None));
+    let record = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let record = parser.parse(&record.record);
+    assert_eq!(record.ts.as_ref().map(|ts| ts.raw()), expected);
+}
+
+#[rstest]
+#[case(br#"{"message":"b","msg":"a","event":"c"}"#, Some("a"), vec!["message", "event"])]
+#[case(br#"{"msg":"","message":"b","event":"c"}"#, Some("b"), vec!["msg", "event"])]
+#[case(br#"{"event":"c"}"#, Some("c"), vec![])]
+#[case(br#"{"msg":"","x":1}"#, None, vec!["msg", "x"])]
+fn test_message_key_fallback(#[case] input: &[u8], #[case] expected: Option<&str>, #[case] fields: Vec<&str>) {
+    let mut predefined = PredefinedFields::default();
+    predefined.message.0.prioritize(["msg", "message", "event"]);
+    let parser = Parser::new(ParserSettings::new(&predefined, [], None));
+    let record = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let record = parser.parse(&record.record);
+    assert_eq!(record.message.map(|m| m.parse::<&str>().unwrap()), expected);
+    assert_eq!(record.fields().map(|(k, _)| *k).collect::<Vec<_>>(), fields);
+}
+
+#[test]
+fn test_aliases() {
+    let aliases = FieldAliases::from(std::collections::BTreeMap::from([
+        ("svc".to_string(), "service".to_string()),
+        ("lvl".to_string(), "level".to_string()),
+    ]));
+    let parser = Parser::new(ParserSettings::default().with_aliases(&aliases));
+    let input = br#"{"lvl":"error","svc":"api","x":{"svc":"db"}}"#;
+    let record = RawRecord::parser().parse(input).next().unwrap().unwrap();
+    let record = parser.parse(&record.record);
+    assert_eq!(record.level, Some(Level::Error));
+    assert_eq!(
+        record.fields().map(|(k, _)| *k).collect::<Vec<_>>(),
+        vec!["service", "x"]
+    );
+    assert!(record.matches(&Query::parse("service=api and x.svc=db").unwrap()));
+}
+
 #[rstest]
 #[case(br#"{"logger":""}"#, None)]
 #[case(br#"{"logger":"x"}"#, Some("x"))]
@@ -842,3 +953,131 @@ fn test_wrong_field_filter() {
     let result = FieldFilter::parse("xx");
     assert!(result.is_err_and(|e| matches!(e, Error::WrongFieldFilter(_))));
 }
+
+#[test]
+fn test_record_get() {
+    let record = parse(r#"{"msg":"m","logger":"l","a":{"b":{"c":1}},"x.y":"z","d_e":2}"#);
+    assert_eq!(record.get("msg").map(|v| v.raw_str()), Some(r#""m""#));
+    assert_eq!(record.get("message").map(|v| v.raw_str()), Some(r#""m""#));
+    assert_eq!(record.get("logger").map(|v| v.raw_str()), Some("l"));
+    assert_eq!(record.get("a.b.c"), Some(RawValue::Number("1")));
+    assert_eq!(record.get(".a.b.c"), Some(RawValue::Number("1")));
+    assert!(matches!(record.get("a.b"), Some(RawValue::Object(_))));
+    assert_eq!(record.get("x.y").map(|v| v.raw_str()), Some(r#""z""#));
+    assert_eq!(record.get("d-e"), Some(RawValue::Number("2")));
+    assert_eq!(record.get("a.c"), None);
+    assert_eq!(record.get("missing"), None);
+}
+
+#[test]
+fn test_record_get_pointer() {
+    let record = parse(r#"{"a.b":1,"a":{"b":2,"c":[{"d":3}]},"e/f":4}"#);
+    assert_eq!(record.get("a.b"), Some(RawValue::Number("1")));
+    assert_eq!(record.get("/a.b"), Some(RawValue::Number("1")));
+    assert_eq!(record.get("/a/b"), Some(RawValue::Number("2")));
+    assert_eq!(record.get("/e~1f"), Some(RawValue::Number("4")));
+    assert_eq!(record.get("/a/c"), record.get("a.c"));
+    assert_eq!(record.get("/a.c"), None);
+    assert_eq!(record.get("/b"), None);
+}
+
+#[rstest]
+#[case("a.b=1", true)]
+#[case("a.b=2", true)]
+#[case("/a.b=1", true)]
+#[case("/a.b=2", false)]
+#[case("/a/b=1", false)]
+#[case("/a/b=2", true)]
+#[case("/a/c/0/d=3", true)]
+#[case("/a/c/1/d=3", false)]
+fn test_field_filter_pointer(#[case] filter: &str, #[case] expected: bool) {
+    let record = parse(r#"{"a.b":1,"a":{"b":2,"c":[{"d":3}]}}"#);
+    assert_eq!(FieldFilter::parse(filter).unwrap().apply(&record), expected);
+}
+
+#[rstest]
+#[case(r#"{"ts":"2024-01-25T19:10:20Z","level":"warn","msg":"m","a":{"b":1}}"#)]
+#[case(r#"ts=2024-01-25T19:10:20Z level=warn msg=m a.b=1"#)]
+fn test_parse_record(#[case] line: &str) {
+    let record = parse_record(line, &ParseOptions::default()).unwrap();
+    assert_eq!(record.ts.as_ref().map(|ts| ts.raw()), Some("2024-01-25T19:10:20Z"));
+    assert_eq!(record.level, Some(Level::Warning));
+    assert_eq!(
+        record.message.map(|m| m.raw_str()),
+        Some(if line.starts_with('{') { r#""m""# } else { "m" })
+    );
+    assert_eq!(record.get("a.b").map(|v| v.raw_str()), Some("1"));
+}
+
+#[test]
+fn test_parse_record_options() {
+    let options = ParseOptions::default().with_input_format(Some(InputFormat::Json));
+    assert!(parse_record("msg=m", &options).is_err());
+
+    let options = ParseOptions::default().with_allow_prefix(true);
+    let record = parse_record(r#"prefix {"msg":"m"}"#, &options).unwrap();
+    assert_eq!(record.message.map(|m| m.raw_str()), Some(r#""m""#));
+
+    assert!(matches!(
+        parse_record("", &ParseOptions::default()),
+        Err(Error::NoRecord)
+    ));
+}
+
+#[test]
+fn test_parse_record_syslog() {
+    let line = r#"<134>1 2023-10-11T22:14:15.003Z host1 app 1234 - [req@1 id="17" path="/x"] user logged in"#;
+    for options in [
+        ParseOptions::default(),
+        ParseOptions::default().with_input_format(Some(InputFormat::Syslog)),
+    ] {
+        let record = parse_record(line, &options).unwrap();
+        assert_eq!(record.ts.as_ref().map(|ts| ts.raw()), Some("2023-10-11T22:14:15.003Z"));
+        assert_eq!(record.level, Some(Level::Info));
+        assert_eq!(record.message.map(|m| m.raw_str()), Some("user logged in"));
+        assert_eq!(record.get("host").map(|v| v.raw_str()), Some("host1"));
+        assert_eq!(record.get("facility").map(|v| v.raw_str()), Some("local0"));
+        assert_eq!(record.get("req@1.id").map(|v| v.raw_str()), Some("17"));
+        assert_eq!(record.get("msgid"), None);
+    }
+
+    let options = ParseOptions::default().with_input_format(Some(InputFormat::Syslog));
+    assert!(parse_record("msg=m", &options).is_err());
+}
+
+#[test]
+fn test_parse_traced() {
+    let settings = ParserSettings::new(&PredefinedFields::default(), &["_*".to_owned()], None);
+    let parser = Parser::new(settings);
+
+    let line = r#"{"ts":"2024-01-25T19:10:20Z","level":"info","msg":"m","_id":1,"a":2}"#;
+    let raw = RawRecord::parser()
+        .parse(line.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+        .record;
+    let (record, trace) = parser.parse_traced(&raw);
+    assert_eq!(record.level, Some(Level::Info));
+    assert_eq!(trace.key(FieldKind::Time), Some("ts"));
+    assert_eq!(trace.key(FieldKind::Logger), None);
+    assert_eq!(trace.to_string(), "time=ts level=level message=msg ignored=_id");
+
+    let line = r#"level=verbose message=m"#;
+    let raw = RawRecord::parser()
+        .parse(line.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+        .record;
+    let (record, trace) = parser.parse_traced(&raw);
+    assert_eq!(record.level, None);
+    assert_eq!(
+        trace.events(),
+        &[
+            ParseEvent::Rejected(FieldKind::Level, "level".into()),
+            ParseEvent::Mapped(FieldKind::Message, "message".into()),
+        ]
+    );
+    assert_eq!(trace.to_string(), "time=- level=- message=message unmapped=level");
+}
@@ -0,0 +1,110 @@
+//! Mapping of [OTLP JSON](https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding) log records onto plain fields.
+
+// std imports
+use std::collections::HashMap;
+
+// third-party imports
+use serde::Deserialize;
+use serde_json::{self as json};
+
+// local imports
+use crate::{
+    level::Level,
+    model::{RawRecordFields, RawValue},
+    settings::{PredefinedFields, RawLevelFieldVariant},
+};
+
+// ---
+
+const TIME: &[&str] = &["timeUnixNano", "observedTimeUnixNano"];
+const LEVEL: &str = "severityText";
+const MESSAGE: &str = "body";
+const ATTRIBUTES: &str = "attributes";
+
+/// Returns the predefined fields extended with the OTLP field names,
+/// which take precedence over the configured ones.
+pub fn predefined_fields(base: &PredefinedFields) -> PredefinedFields {
+    let mut result = base.clone();
+
+    result
+        .time
+        .0
+        .names
+        .splice(0..0, TIME.iter().map(|&name| name.to_owned()));
+    result.message.0.names.insert(0, MESSAGE.into());
+
+    let values = [
+        (Level::Error, vec!["ERROR", "FATAL"]),
+        (Level::Warning, vec!["WARN"]),
+        (Level::Info, vec!["INFO"]),
+        (Level::Debug, vec!["DEBUG"]),
+        (Level::Trace, vec!["TRACE"]),
+    ];
+    let variant = RawLevelFieldVariant {
+        names: vec![LEVEL.into()],
+        values: values
+            .into_iter()
+            .map(|(level, names)| (level.into(), names.into_iter().map(Into::into).collect()))
+            .collect(),
+        level: None,
+    };
+    result.level.variants.insert(0, variant);
+
+    result
+}
+
+/// Replaces the `AnyValue` wrapper of the body with its content
+/// and the attribute list with a field per attribute.
+///
+/// Parts having an unexpected shape are left as is.
+pub fn flatten<'a>(fields: &mut RawRecordFields<'a>) {
+    if !fields.iter().any(|&(key, _)| key == MESSAGE || key == ATTRIBUTES) {
+        return;
+    }
+
+    let mut result = RawRecordFields::default();
+    for &(key, value) in fields.iter() {
+        match (key, value) {
+            (MESSAGE, RawValue::Object(_)) => result.push((key, any_value(value))),
+            (ATTRIBUTES, RawValue::Array(array)) => match json::from_str::<Vec<Attribute>>(array.get()) {
+                Ok(attributes) => {
+                    for attribute in attributes {
+                        result.push((attribute.key, any_value(RawValue::from(attribute.value))));
+                    }
+                }
+                Err(_) => result.push((key, value)),
+            },
+            _ => result.push((key, value)),
+        }
+    }
+
+    *fields = result;
+}
+
+/// Unwraps an `AnyValue` object, e.g. `{"stringValue":"x"}`, into its content.
+fn any_value<'a>(value: RawValue<'a>) -> RawValue<'a> {
+    let RawValue::Object(object) = value else {
+        return value;
+    };
+
+    match json::from_str::<HashMap<&'a str, &'a json::value::RawValue>>(object.get()) {
+        Ok(map) if map.len() == 1 => match map.into_iter().next() {
+            Some((key, inner)) if key.ends_with("Value") => RawValue::from(inner),
+            _ => value,
+        },
+        _ => value,
+    }
+}
+
+// ---
+
+#[derive(Deserialize)]
+struct Attribute<'a> {
+    #[serde(borrow)]
+    key: &'a str,
+    #[serde(borrow)]
+    value: &'a json::value::RawValue,
+}
+
+#[cfg(test)]
+mod tests;
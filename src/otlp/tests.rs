@@ -0,0 +1,64 @@
+use super::*;
+
+use crate::{
+    app::InputFormat,
+    model::{Parser, ParserSettings, RawRecord, RawRecordParser},
+};
+
+const RECORD: &str = concat!(
+    r#"{"timeUnixNano":"1700000000123456789","severityNumber":13,"severityText":"WARN","#,
+    r#""body":{"stringValue":"disk almost full"},"#,
+    r#""attributes":[{"key":"host.name","value":{"stringValue":"node-1"}},{"key":"free","value":{"intValue":"42"}}],"#,
+    r#""traceId":"5b8efff798038103d269b633813fc60c"}"#,
+);
+
+fn raw(line: &str) -> RawRecord<'_> {
+    RawRecordParser::new()
+        .format(Some(InputFormat::Otlp))
+        .parse(line.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+        .record
+}
+
+#[test]
+fn test_flatten() {
+    let record = raw(RECORD);
+    let fields: Vec<_> = record.fields().map(|(k, v)| (*k, v.raw_str())).collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("timeUnixNano", r#""1700000000123456789""#),
+            ("severityNumber", "13"),
+            ("severityText", r#""WARN""#),
+            ("body", r#""disk almost full""#),
+            ("host.name", r#""node-1""#),
+            ("free", r#""42""#),
+            ("traceId", r#""5b8efff798038103d269b633813fc60c""#),
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_unexpected_shape() {
+    let record = raw(r#"{"body":{"a":1,"b":2},"attributes":[1,2]}"#);
+    let fields: Vec<_> = record.fields().map(|(k, v)| (*k, v.raw_str())).collect();
+    assert_eq!(fields, vec![("body", r#"{"a":1,"b":2}"#), ("attributes", "[1,2]")]);
+}
+
+#[test]
+fn test_predefined_fields() {
+    let predefined = predefined_fields(&PredefinedFields::default());
+    let parser = Parser::new(ParserSettings::new(&predefined, Vec::new(), None));
+    let raw = raw(RECORD);
+    let record = parser.parse(&raw);
+
+    assert_eq!(record.level, Some(Level::Warning));
+    assert_eq!(record.message.map(|v| v.raw_str()), Some(r#""disk almost full""#));
+    assert_eq!(
+        record.ts.and_then(|ts| ts.parse()).map(|ts| ts.timestamp_nanos_opt()),
+        Some(Some(1700000000123456789))
+    );
+    assert_eq!(record.get("host.name").map(|v| v.raw_str()), Some(r#""node-1""#));
+}
@@ -1,8 +1,10 @@
 use std::env;
 use std::ffi::OsString;
-use std::io::Write;
+use std::fs::{self, File};
+use std::io::{self as io, Write};
 use std::path::PathBuf;
-use std::process::{Child, Command, ExitStatus, Stdio};
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
 
 #[cfg(unix)]
 use std::{
@@ -86,3 +88,224 @@ impl Write for Pager {
         self.process.stdin.as_mut().unwrap().flush()
     }
 }
+
+/// Output file that is rotated when it grows beyond the size limit.
+///
+/// On rotation, `PATH` is renamed to `PATH.1`, the previously rotated files are shifted to `PATH.2`, `PATH.3` and so on,
+/// and writing continues to a fresh `PATH`. The file is rotated only between lines, so no record is split across files.
+pub struct OutputFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: Option<u64>,
+    at_line_start: bool,
+}
+
+impl OutputFile {
+    /// Creates the file, along with its parent directories, truncating it if it exists.
+    pub fn create(path: PathBuf, max_size: Option<u64>) -> Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = File::create(&path)?;
+
+        Ok(Self {
+            path,
+            file,
+            size: 0,
+            max_size,
+            at_line_start: true,
+        })
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+
+        let rotated = |n: usize| -> PathBuf {
+            let mut name = self.path.as_os_str().to_owned();
+            name.push(format!(".{}", n));
+            name.into()
+        };
+        let mut n = 1;
+        while rotated(n).exists() {
+            n += 1;
+        }
+        for i in (1..n).rev() {
+            fs::rename(rotated(i), rotated(i + 1))?;
+        }
+        fs::rename(&self.path, rotated(1))?;
+
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.at_line_start && self.max_size.is_some_and(|max| self.size >= max) {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        self.at_line_start = buf.ends_with(b"\n");
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Output passing formatted records to an external command and relaying its output to the inner stream.
+///
+/// In [`ExecMode::Stream`] mode the command is started once, receives all records on its stdin, one per line,
+/// and its stdout is copied to the inner stream as it comes. If the command exits early, writing fails
+/// with a broken pipe error, which stops processing.
+///
+/// In [`ExecMode::PerRecord`] mode the command is started for each record, which receives only that record on its stdin.
+/// This is much slower and should be used only for small inputs or commands that cannot process a stream.
+/// Processing stops with an error as soon as the command fails for any record.
+///
+/// [`ExecOutput::finish`] must be called once all records are written, dropping the output does not wait for the command.
+pub struct ExecOutput {
+    command: Vec<String>,
+    mode: ExecMode,
+    inner: Option<OutputStream>,
+    child: Option<Child>,
+    stdin: Option<ChildStdin>,
+    relay: Option<JoinHandle<io::Result<()>>>,
+    pending: Vec<u8>,
+}
+
+impl ExecOutput {
+    pub fn new(command: &str, mode: ExecMode, inner: OutputStream) -> Result<Self> {
+        let args = shellwords::split(command).map_err(|_| Error::InvalidCommand(command.into()))?;
+        if args.is_empty() {
+            return Err(Error::InvalidCommand(command.into()));
+        }
+
+        let mut result = Self {
+            command: args,
+            mode,
+            inner: Some(inner),
+            child: None,
+            stdin: None,
+            relay: None,
+            pending: Vec::new(),
+        };
+
+        if mode == ExecMode::Stream {
+            let mut child = result.spawn()?;
+            let mut stdout = child.stdout.take().unwrap();
+            let mut inner = result.inner.take().unwrap();
+            result.stdin = child.stdin.take();
+            result.child = Some(child);
+            result.relay = Some(thread::spawn(move || {
+                io::copy(&mut stdout, &mut inner)?;
+                inner.flush()
+            }));
+        }
+
+        Ok(result)
+    }
+
+    /// Passes the last record if it has no trailing line break and waits for the command to exit.
+    ///
+    /// Fails if the command fails for the last record or, in [`ExecMode::Stream`] mode, exits with a failure status.
+    pub fn finish(&mut self) -> Result<()> {
+        if !self.pending.is_empty() {
+            let record = std::mem::take(&mut self.pending);
+            self.run(&record)?;
+        }
+
+        // closing stdin lets the command know there are no more records
+        self.stdin.take();
+        let status = match self.child.take() {
+            Some(mut child) => Some(child.wait()?),
+            None => None,
+        };
+        if let Some(relay) = self.relay.take() {
+            match relay.join() {
+                Ok(Err(e)) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+                _ => {}
+            }
+        }
+
+        match status {
+            Some(status) if !status.success() => Err(Error::CommandFailed {
+                command: self.command.join(" "),
+                status,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn spawn(&self) -> io::Result<Child> {
+        Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+    }
+
+    fn run(&mut self, record: &[u8]) -> io::Result<()> {
+        let mut child = self.spawn()?;
+        let mut stdin = child.stdin.take().unwrap();
+        // the command may exit without reading its input, which is not an error by itself
+        match stdin.write_all(record) {
+            Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+            _ => drop(stdin),
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "command '{}' {}",
+                self.command.join(" "),
+                output.status
+            )));
+        }
+        match &mut self.inner {
+            Some(inner) => inner.write_all(&output.stdout),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for ExecOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(stdin) = &mut self.stdin {
+            return stdin.write(buf);
+        }
+
+        self.pending.extend(buf);
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let record: Vec<u8> = self.pending.drain(..=end).collect();
+            self.run(&record)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match (&mut self.stdin, &mut self.inner) {
+            (Some(stdin), _) => stdin.flush(),
+            (None, Some(inner)) => inner.flush(),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Defines how records are passed to the command of [`ExecOutput`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecMode {
+    /// A single command receives all records.
+    Stream,
+    /// A new command is started for each record.
+    PerRecord,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,76 @@
+use super::*;
+
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_output_file_rotation() {
+    let dir = env::temp_dir().join(format!("hl-test-output-{}", std::process::id()));
+    fs::remove_dir_all(&dir).ok();
+    let path = dir.join("nested").join("out.log");
+
+    let mut file = OutputFile::create(path.clone(), Some(4)).unwrap();
+    file.write_all(b"first ").unwrap();
+    file.write_all(b"line\n").unwrap();
+    file.write_all(b"second\n").unwrap();
+    file.write_all(b"third line\n").unwrap();
+    file.write_all(b"fourth\n").unwrap();
+    file.flush().unwrap();
+
+    let read = |suffix: &str| fs::read_to_string(format!("{}{}", path.display(), suffix)).unwrap();
+    assert_eq!(read(".3"), "first line\n");
+    assert_eq!(read(".2"), "second\n");
+    assert_eq!(read(".1"), "third line\n");
+    assert_eq!(read(""), "fourth\n");
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_output() {
+    for mode in [ExecMode::Stream, ExecMode::PerRecord] {
+        let buf = SharedBuf::default();
+        let mut output = ExecOutput::new("tr a-z A-Z", mode, Box::new(buf.clone())).unwrap();
+        output.write_all(b"{\"msg\":\"a\"}\n{\"msg\"").unwrap();
+        output.write_all(b":\"b\"}\n{\"msg\":\"c\"}").unwrap();
+        output.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf.0.lock().unwrap().clone()).unwrap(),
+            "{\"MSG\":\"A\"}\n{\"MSG\":\"B\"}\n{\"MSG\":\"C\"}"
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_output_failure() {
+    let mut output = ExecOutput::new("false", ExecMode::PerRecord, Box::new(io::sink())).unwrap();
+    assert!(output.write_all(b"{}\n").is_err());
+
+    // the last record without a trailing line break is passed when finishing
+    let mut output = ExecOutput::new("false", ExecMode::PerRecord, Box::new(io::sink())).unwrap();
+    output.write_all(b"{}").unwrap();
+    assert!(output.finish().is_err());
+
+    let mut output = ExecOutput::new("false", ExecMode::Stream, Box::new(io::sink())).unwrap();
+    assert!(matches!(output.finish(), Err(Error::CommandFailed { .. })));
+
+    assert!(matches!(
+        ExecOutput::new("'unclosed", ExecMode::Stream, Box::new(io::sink())),
+        Err(Error::InvalidCommand(_))
+    ));
+}
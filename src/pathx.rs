@@ -0,0 +1,98 @@
+//! Expansion of `~` and environment variables in paths given in the configuration and on the command line.
+
+// std imports
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+// local imports
+use crate::error::{Error, Result};
+
+// ---
+
+/// Expands a leading `~` to the home directory and `$VAR` or `${VAR}` to the value of the environment variable.
+///
+/// A `$$` stands for a literal `$`, and a `$` not followed by a variable name is kept as is.
+/// Fails if a referenced variable or the home directory is not set.
+pub fn expand(value: &str) -> Result<String> {
+    expand_with(value, |name| env::var(name).ok())
+}
+
+/// Expands the path like [`expand`] does, paths that are not valid UTF-8 are returned unchanged.
+pub fn expand_path(path: &Path) -> Result<PathBuf> {
+    match path.to_str() {
+        Some(value) => Ok(expand(value)?.into()),
+        None => Ok(path.into()),
+    }
+}
+
+fn expand_with(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let undefined = |name: &str| Error::UndefinedEnvVar {
+        name: name.to_owned(),
+        value: value.to_owned(),
+    };
+
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    if let Some(tail) = rest.strip_prefix('~') {
+        if tail.is_empty() || tail.starts_with(['/', std::path::MAIN_SEPARATOR]) {
+            let home = HOME_VARS
+                .iter()
+                .find_map(|name| lookup(name))
+                .ok_or_else(|| undefined(HOME_VARS[0]))?;
+            result.push_str(&home);
+            rest = tail;
+        }
+    }
+
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        let tail = &rest[pos + 1..];
+        if let Some(next) = tail.strip_prefix('$') {
+            result.push('$');
+            rest = next;
+            continue;
+        }
+        let (name, next) = match tail.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) if is_name(&braced[..end]) => (&braced[..end], &braced[end + 1..]),
+                _ => ("", tail),
+            },
+            None => {
+                let end = tail
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(tail.len());
+                let name = &tail[..end];
+                if is_name(name) {
+                    (name, &tail[end..])
+                } else {
+                    ("", tail)
+                }
+            }
+        };
+        if name.is_empty() {
+            result.push('$');
+        } else {
+            result.push_str(&lookup(name).ok_or_else(|| undefined(name))?);
+        }
+        rest = next;
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn is_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// ---
+
+/// Environment variables holding the home directory, in the order of preference.
+const HOME_VARS: &[&str] = &["HOME", "USERPROFILE"];
+
+#[cfg(test)]
+mod tests;
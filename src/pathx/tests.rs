@@ -0,0 +1,35 @@
+use super::*;
+
+fn expanded(value: &str) -> Result<String> {
+    expand_with(value, |name| match name {
+        "HOME" => Some("/home/user".to_owned()),
+        "APP" => Some("hl".to_owned()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_expand() {
+    assert_eq!(expanded("~/themes/x.yaml").unwrap(), "/home/user/themes/x.yaml");
+    assert_eq!(expanded("~").unwrap(), "/home/user");
+    assert_eq!(expanded("~other/x").unwrap(), "~other/x");
+    assert_eq!(expanded("$HOME/.config/$APP").unwrap(), "/home/user/.config/hl");
+    assert_eq!(expanded("/etc/${APP}.d/config").unwrap(), "/etc/hl.d/config");
+    assert_eq!(expanded("/var/log/app.log").unwrap(), "/var/log/app.log");
+    assert_eq!(expanded("price$").unwrap(), "price$");
+    assert_eq!(expanded("a$1/${}/${APP").unwrap(), "a$1/${}/${APP");
+    assert_eq!(expanded("/tmp/$$HOME/$$$APP").unwrap(), "/tmp/$HOME/$hl");
+    assert_eq!(expanded("$$$$").unwrap(), "$$");
+}
+
+#[test]
+fn test_expand_undefined() {
+    match expanded("$HOME/$MISSING/x") {
+        Err(Error::UndefinedEnvVar { name, value }) => {
+            assert_eq!(name, "MISSING");
+            assert_eq!(value, "$HOME/$MISSING/x");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+    assert!(expand_with("~/x", |_| None).is_err());
+}
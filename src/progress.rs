@@ -0,0 +1,135 @@
+// std imports
+use std::{
+    io::{Write, stderr},
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+// ---
+
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const CLEAR_LINE: &[u8] = b"\r\x1b[2K";
+const BAR_WIDTH: usize = 24;
+
+/// Progress of a long-running processing of the input, drawn on a single line of the terminal attached to stderr.
+///
+/// The line is updated in place at most once per [`REDRAW_INTERVAL`] and is erased by [`Progress::finish`].
+/// Nothing is drawn if processing completes faster than the first redraw.
+pub struct Progress {
+    started: Instant,
+    total: AtomicU64,
+    bytes: AtomicU64,
+    records: AtomicU64,
+    drawn: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            total: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            records: AtomicU64::new(0),
+            drawn: Mutex::new(None),
+        }
+    }
+
+    /// Adds the size of another input about to be processed to the total number of bytes.
+    pub fn expect(&self, bytes: u64) {
+        self.total.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Accounts processed data and redraws the line if it is time to.
+    pub fn advance(&self, bytes: u64, records: u64) {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.records.fetch_add(records, Ordering::Relaxed);
+
+        let Ok(mut drawn) = self.drawn.try_lock() else {
+            return;
+        };
+        let now = Instant::now();
+        let last = drawn.unwrap_or(self.started);
+        if now.duration_since(last) < REDRAW_INTERVAL {
+            return;
+        }
+        *drawn = Some(now);
+
+        let mut buf = Vec::with_capacity(128);
+        buf.extend(CLEAR_LINE);
+        render(
+            &mut buf,
+            self.bytes.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+            self.records.load(Ordering::Relaxed),
+            now.duration_since(self.started),
+        );
+        stderr().write_all(&buf).ok();
+    }
+
+    /// Erases the line if it was drawn.
+    pub fn finish(&self) {
+        if self.drawn.lock().unwrap().take().is_some() {
+            stderr().write_all(CLEAR_LINE).ok();
+        }
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+// ---
+
+/// Renders the progress line, e.g. `[#####-----] 1.2 GiB / 2.4 GiB, 154321 records/s`.
+///
+/// The bar and the total size are omitted if the total size is unknown.
+fn render(buf: &mut Vec<u8>, bytes: u64, total: u64, records: u64, elapsed: Duration) {
+    if total != 0 {
+        let filled = (bytes.min(total) as u128 * BAR_WIDTH as u128 / total as u128) as usize;
+        buf.push(b'[');
+        buf.extend(std::iter::repeat_n(b'#', filled));
+        buf.extend(std::iter::repeat_n(b'-', BAR_WIDTH - filled));
+        buf.extend(b"] ");
+    }
+    size(buf, bytes);
+    if total != 0 {
+        buf.extend(b" / ");
+        size(buf, total);
+    }
+    let rate = match elapsed.as_millis() {
+        0 => 0,
+        ms => (records as u128 * 1000 / ms) as u64,
+    };
+    write!(buf, ", {} records/s", rate).ok();
+}
+
+fn size(buf: &mut Vec<u8>, bytes: u64) {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        write!(buf, "{} B", bytes).ok();
+        return;
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    write!(buf, "{:.1} {}", value, UNITS[unit]).ok();
+}
+
+#[cfg(test)]
+mod tests;
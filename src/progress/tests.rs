@@ -0,0 +1,33 @@
+use super::*;
+
+fn rendered(bytes: u64, total: u64, records: u64, elapsed: Duration) -> String {
+    let mut buf = Vec::new();
+    render(&mut buf, bytes, total, records, elapsed);
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_render() {
+    assert_eq!(
+        rendered(512 * 1024 * 1024, 2 * 1024 * 1024 * 1024, 3000, Duration::from_secs(2)),
+        "[######------------------] 512.0 MiB / 2.0 GiB, 1500 records/s"
+    );
+    assert_eq!(
+        rendered(3 << 20, 2 << 20, 1, Duration::ZERO),
+        "[########################] 3.0 MiB / 2.0 MiB, 0 records/s"
+    );
+}
+
+#[test]
+fn test_render_unknown_total() {
+    assert_eq!(rendered(100, 0, 10, Duration::from_millis(500)), "100 B, 20 records/s");
+}
+
+#[test]
+fn test_finish_without_draw() {
+    let progress = Progress::new();
+    progress.expect(100);
+    progress.advance(10, 1);
+    progress.finish();
+    assert!(progress.drawn.lock().unwrap().is_none());
+}
@@ -7,21 +7,32 @@ use std::{
 
 // third-party imports
 use closure::closure;
-use pest::{Parser, iterators::Pair};
 use pest_derive::Parser;
 use serde_json as json;
+use thiserror::Error;
 use wildflower::Pattern;
 
 // local imports
 use crate::{
     error::{Error, Result},
-    level::RelaxedLevel,
     model::{
-        FieldFilter, FieldFilterKey, Level, Number, NumericOp, Record, RecordFilter, RecordFilterNone, ValueMatchPolicy,
+        FieldFilter, FieldFilterKey, Level, Number, NumericOp, Record, RecordFilter, RecordFilterNone, TimeOp,
+        ValueMatchPolicy,
     },
     model::{FieldFilterFlag, FieldFilterFlags},
+    timeparse::parse_time_literal,
+    types::FieldKind,
 };
 
+pub mod ast;
+
+use ast::{Expr, FieldExpr, FieldName, LevelExpr, Value};
+
+// ---
+
+/// Name standing for the recognized timestamp of a record in time comparisons, e.g. `ts > -1h`.
+const TIME_KEY: &str = "ts";
+
 // ---
 
 #[derive(Parser)]
@@ -31,34 +42,106 @@ pub struct QueryParser;
 #[derive(Clone)]
 pub struct Query {
     filter: Arc<dyn RecordFilter + Sync + Send>,
+    highlights: Vec<String>,
+    ast: Option<Arc<Expr>>,
 }
 
 impl Query {
-    pub fn parse(str: impl AsRef<str>) -> Result<Self> {
-        let mut pairs = QueryParser::parse(Rule::input, str.as_ref())?;
-        expression(pairs.next().unwrap())
+    /// Parses the query text and prepares it for matching.
+    ///
+    /// Files referenced by `in @file` conditions are loaded at this point.
+    pub fn parse(str: impl AsRef<str>) -> std::result::Result<Self, QueryError> {
+        Self::compile(&Expr::parse(str)?)
+    }
+
+    /// Prepares a parsed or hand-built expression for matching.
+    pub fn compile(expr: &Expr) -> std::result::Result<Self, QueryError> {
+        Ok(compile(expr)?.with_ast(expr.clone()))
     }
 
     pub fn and(self, rhs: Query) -> Query {
+        let highlights = [self.highlights(), rhs.highlights()].concat();
+        let ast = Self::combine(&self, &rhs, |lhs, rhs| Expr::And(vec![lhs, rhs]));
         Query::new(OpAnd { lhs: self, rhs })
+            .with_highlights(highlights)
+            .with_ast_opt(ast)
     }
 
     pub fn or(self, rhs: Query) -> Query {
+        let highlights = [self.highlights(), rhs.highlights()].concat();
+        let ast = Self::combine(&self, &rhs, |lhs, rhs| Expr::Or(vec![lhs, rhs]));
         Query::new(OpOr { lhs: self, rhs })
+            .with_highlights(highlights)
+            .with_ast_opt(ast)
+    }
+
+    /// Returns true if the record satisfies the query.
+    #[inline]
+    pub fn matches(&self, record: &Record) -> bool {
+        self.apply(record)
+    }
+
+    /// Returns the expression the query was built from.
+    /// Queries built from custom filters with [`Query::new`] or [`Query::field`] have none,
+    /// as do any combinations including them.
+    pub fn ast(&self) -> Option<&Expr> {
+        self.ast.as_deref()
+    }
+
+    /// Creates a query from a single field filter, accepting the same expressions as `--filter` option, e.g. `k=v`.
+    pub fn field(text: &str) -> Result<Self> {
+        let filter = FieldFilter::parse(text)?;
+        let highlights = filter.highlight().into_iter().collect();
+        Ok(Query::new(filter).with_highlights(highlights))
+    }
+
+    /// Creates a query matching records having the field with the given key, like `exists(key)` does.
+    pub fn exists(key: &str) -> Result<Self> {
+        Ok(Query::new(FieldFilter::new(
+            FieldFilterKey::parse(key)?,
+            ValueMatchPolicy::Any,
+            FieldFilterFlags::empty(),
+        )))
     }
 
     pub fn new<F: RecordFilter + Sync + Send + 'static>(filter: F) -> Self {
         Self {
             filter: Arc::new(filter),
+            highlights: Vec::new(),
+            ast: None,
         }
     }
+
+    /// Returns regular expressions matching the text the query looks for,
+    /// used to highlight it in the output.
+    /// Negated conditions do not contribute to the result.
+    pub fn highlights(&self) -> &[String] {
+        &self.highlights
+    }
+
+    fn with_highlights(self, highlights: Vec<String>) -> Self {
+        Self { highlights, ..self }
+    }
+
+    fn with_ast(self, ast: Expr) -> Self {
+        self.with_ast_opt(Some(Arc::new(ast)))
+    }
+
+    fn with_ast_opt(self, ast: Option<Arc<Expr>>) -> Self {
+        Self { ast, ..self }
+    }
+
+    fn combine(lhs: &Query, rhs: &Query, f: impl FnOnce(Expr, Expr) -> Expr) -> Option<Arc<Expr>> {
+        Some(Arc::new(f(lhs.ast()?.clone(), rhs.ast()?.clone())))
+    }
 }
 
 impl Not for Query {
     type Output = Query;
 
     fn not(self) -> Self::Output {
-        Query::new(OpNot { arg: self })
+        let ast = self.ast().map(|arg| Arc::new(Expr::Not(Box::new(arg.clone()))));
+        Query::new(OpNot { arg: self }).with_ast_opt(ast)
     }
 }
 
@@ -103,109 +186,150 @@ impl Default for &'static Query {
 
 // ---
 
-fn expression(pair: Pair<Rule>) -> Result<Query> {
-    match pair.as_rule() {
-        Rule::expr_or => binary_op::<OpOr>(pair),
-        Rule::expr_and => binary_op::<OpAnd>(pair),
-        Rule::expr_not => not(pair),
-        Rule::primary => primary(pair),
-        _ => unreachable!(),
-    }
+/// Error returned by [`Query::parse`], pointing to the place in the query text where it was detected.
+#[derive(Error, Debug)]
+#[error("{source}")]
+pub struct QueryError {
+    offset: usize,
+    source: Box<Error>,
 }
 
-fn binary_op<Op: BinaryOp + Sync + Send + 'static>(pair: Pair<Rule>) -> Result<Query> {
-    let mut inner = pair.into_inner();
-    let mut result = expression(inner.next().unwrap())?;
-    for inner in inner {
-        result = Query::new(Op::new(result, expression(inner)?));
+impl QueryError {
+    pub fn new(offset: usize, source: Error) -> Self {
+        Self {
+            offset,
+            source: Box::new(source),
+        }
     }
-    Ok(result)
-}
 
-fn not(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::expr_not);
+    /// Returns the byte offset in the query text where the error was detected.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
 
-    Ok(Query::new(OpNot {
-        arg: expression(pair.into_inner().next().unwrap())?,
-    }))
+    /// Returns the underlying error.
+    #[inline]
+    pub fn error(&self) -> &Error {
+        &self.source
+    }
 }
 
-fn primary(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::primary);
-
-    let inner = pair.into_inner().next().unwrap();
-    match inner.as_rule() {
-        Rule::term => term(inner),
-        _ => expression(inner),
+impl From<QueryError> for Error {
+    fn from(err: QueryError) -> Self {
+        *err.source
     }
 }
 
-fn term(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::term);
+trait WithOffset<T> {
+    fn at(self, offset: usize) -> std::result::Result<T, QueryError>;
+}
 
-    let inner = pair.into_inner().next().unwrap();
-    match inner.as_rule() {
-        Rule::field_filter => field_filter(inner),
-        Rule::level_filter => level_filter(inner),
-        _ => unreachable!(),
+impl<T, E: Into<Error>> WithOffset<T> for std::result::Result<T, E> {
+    #[inline]
+    fn at(self, offset: usize) -> std::result::Result<T, QueryError> {
+        self.map_err(|e| QueryError::new(offset, e.into()))
     }
 }
 
-fn field_filter(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::field_filter);
+// ---
 
-    let inner = pair.into_inner().next().unwrap();
-    match inner.as_rule() {
-        Rule::field_expr_filter => field_expr_filter(inner),
-        Rule::field_exists_filter => field_exists_filter(inner),
-        _ => unreachable!(),
+type CompileResult<T> = std::result::Result<T, QueryError>;
+
+fn compile(expr: &Expr) -> CompileResult<Query> {
+    match expr {
+        Expr::Or(args) => binary_op::<OpOr>(args),
+        Expr::And(args) => binary_op::<OpAnd>(args),
+        Expr::Not(arg) => Ok(Query::new(OpNot { arg: compile(arg)? })),
+        Expr::Field(expr) => field_expr_filter(expr),
+        Expr::Exists(expr) => Ok(Query::new(FieldFilter::new(
+            field_key(&expr.name, expr.offset)?.borrowed(),
+            ValueMatchPolicy::Any,
+            FieldFilterFlags::empty(),
+        ))),
+        Expr::Level(expr) => level_filter(expr),
     }
 }
 
-fn field_expr_filter(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::field_expr_filter);
-
-    let mut inner = pair.into_inner();
-    let lhs = inner.next().unwrap();
-
-    let (op, has_include_absent_flag) = match inner.next().unwrap().as_rule() {
-        Rule::include_absent_flag => (inner.next().unwrap().as_rule(), true),
-        v => (v, false),
+fn binary_op<Op: BinaryOp + Sync + Send + 'static>(args: &[Expr]) -> CompileResult<Query> {
+    let mut args = args.iter();
+    let Some(first) = args.next() else {
+        return Ok(Query::default());
     };
+    let mut result = compile(first)?;
+    for arg in args {
+        let rhs = compile(arg)?;
+        let highlights = [result.highlights(), rhs.highlights()].concat();
+        result = Query::new(Op::new(result, rhs)).with_highlights(highlights);
+    }
+    Ok(result)
+}
 
-    let rhs = inner.next().unwrap();
+fn field_expr_filter(expr: &FieldExpr) -> CompileResult<Query> {
+    let at = expr.offset;
+    let op = expr.op;
 
-    let (match_policy, negated) = match (op, rhs.as_rule()) {
-        (Rule::op_in | Rule::op_not_in, Rule::string_set) => (
-            ValueMatchPolicy::In(parse_string_set(rhs)?.into_iter().collect()),
-            op == Rule::op_not_in,
+    let (match_policy, negated) = match (op, &expr.value) {
+        (ast::Op::In | ast::Op::NotIn, Value::StringSet(set)) => (
+            ValueMatchPolicy::In(set.iter().cloned().collect()),
+            op == ast::Op::NotIn,
         ),
-        (Rule::op_equal | Rule::op_not_equal, Rule::string) => {
-            (ValueMatchPolicy::Exact(parse_string(rhs)?), op == Rule::op_not_equal)
-        }
-        (Rule::op_like | Rule::op_not_like, Rule::string) => (
-            ValueMatchPolicy::WildCard(Pattern::new(parse_string(rhs)?.to_string())),
-            op == Rule::op_not_like,
+        (ast::Op::In | ast::Op::NotIn, Value::StringSetFile(filename)) => (
+            ValueMatchPolicy::In(load_string_set_file(filename).at(at)?.into_iter().collect()),
+            op == ast::Op::NotIn,
         ),
-        (Rule::op_contain | Rule::op_not_contain, Rule::string) => (
-            ValueMatchPolicy::SubString(parse_string(rhs)?),
-            op == Rule::op_not_contain,
+        (ast::Op::Equal | ast::Op::NotEqual, Value::String(value)) => {
+            (ValueMatchPolicy::Exact(value.clone()), op == ast::Op::NotEqual)
+        }
+        (ast::Op::Like | ast::Op::NotLike, Value::String(value)) => (
+            ValueMatchPolicy::WildCard(Pattern::new(value.clone())),
+            op == ast::Op::NotLike,
         ),
-        (Rule::op_regex_match | Rule::op_not_regex_match, Rule::string) => (
-            ValueMatchPolicy::RegularExpression(parse_string(rhs)?.parse()?),
-            op == Rule::op_not_regex_match,
+        (ast::Op::Contain | ast::Op::NotContain, Value::String(value)) => {
+            (ValueMatchPolicy::SubString(value.clone()), op == ast::Op::NotContain)
+        }
+        (ast::Op::RegexMatch | ast::Op::NotRegexMatch, Value::String(value)) => (
+            ValueMatchPolicy::RegularExpression(value.parse().at(at)?),
+            op == ast::Op::NotRegexMatch,
         ),
-        (Rule::op_in | Rule::op_not_in, Rule::number_set) => (
-            ValueMatchPolicy::Numerically(NumericOp::In(parse_number_set(rhs)?)),
-            op == Rule::op_not_in,
+        (ast::Op::In | ast::Op::NotIn, Value::NumberSet(set)) => (
+            ValueMatchPolicy::Numerically(NumericOp::In(
+                set.iter().map(|v| v.parse()).collect::<Result<Vec<_>>>().at(at)?,
+            )),
+            op == ast::Op::NotIn,
         ),
-        (Rule::op_equal, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Eq(parse_number(rhs)?)), false),
-        (Rule::op_not_equal, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Ne(parse_number(rhs)?)), false),
-        (Rule::op_ge, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Ge(parse_number(rhs)?)), false),
-        (Rule::op_gt, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Gt(parse_number(rhs)?)), false),
-        (Rule::op_le, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Le(parse_number(rhs)?)), false),
-        (Rule::op_lt, Rule::number) => (ValueMatchPolicy::Numerically(NumericOp::Lt(parse_number(rhs)?)), false),
-        _ => unreachable!(),
+        (ast::Op::Less | ast::Op::LessOrEqual | ast::Op::Greater | ast::Op::GreaterOrEqual, Value::String(value)) => {
+            let value = parse_time_literal(value).at(at)?;
+            let op = match op {
+                ast::Op::GreaterOrEqual => TimeOp::Ge(value),
+                ast::Op::Greater => TimeOp::Gt(value),
+                ast::Op::LessOrEqual => TimeOp::Le(value),
+                _ => TimeOp::Lt(value),
+            };
+            (ValueMatchPolicy::Chronologically(op), false)
+        }
+        (ast::Op::Less | ast::Op::LessOrEqual | ast::Op::Greater | ast::Op::GreaterOrEqual, Value::Number(value))
+            if is_time_key(&expr.name) =>
+        {
+            return Err(QueryError::new(
+                at,
+                Error::AmbiguousTimeComparison { value: value.clone() },
+            ));
+        }
+        (_, Value::Number(value)) => {
+            let value: Number = value.parse().at(at)?;
+            let op = match op {
+                ast::Op::Equal => NumericOp::Eq(value),
+                ast::Op::NotEqual => NumericOp::Ne(value),
+                ast::Op::GreaterOrEqual => NumericOp::Ge(value),
+                ast::Op::Greater => NumericOp::Gt(value),
+                ast::Op::LessOrEqual => NumericOp::Le(value),
+                ast::Op::Less => NumericOp::Lt(value),
+                _ => return Err(invalid_operand(expr)),
+            };
+            (ValueMatchPolicy::Numerically(op), false)
+        }
+        _ => return Err(invalid_operand(expr)),
     };
 
     let mut flags = if negated {
@@ -214,97 +338,72 @@ fn field_expr_filter(pair: Pair<Rule>) -> Result<Query> {
         FieldFilterFlags::empty()
     };
 
-    if has_include_absent_flag {
+    if expr.include_absent {
         flags |= FieldFilterFlag::IncludeAbsent;
     }
 
-    Ok(Query::new(FieldFilter::new(
-        parse_field_name(lhs)?.borrowed(),
-        match_policy,
-        flags,
-    )))
-}
-
-fn field_exists_filter(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::field_exists_filter);
-
-    let args = pair.into_inner();
+    // the recognized timestamp is compared instead of a field named `ts`, whatever its original key is
+    let key = match &match_policy {
+        ValueMatchPolicy::Chronologically(_) if is_time_key(&expr.name) => FieldFilterKey::Predefined(FieldKind::Time),
+        _ => field_key(&expr.name, at)?,
+    };
 
-    let mut result: Option<Query> = None;
-    for arg in args {
-        let field_name = parse_field_name(arg)?;
-        let query = Query::new(FieldFilter::new(
-            field_name.borrowed(),
-            ValueMatchPolicy::Any,
-            FieldFilterFlags::empty(),
-        ));
-        result = Some(if let Some(prev) = result {
-            prev.and(query)
-        } else {
-            query
-        });
-    }
+    let filter = FieldFilter::new(key.borrowed(), match_policy, flags);
+    let highlights = filter.highlight().into_iter().collect();
 
-    Ok(result.unwrap_or_default())
+    Ok(Query::new(filter).with_highlights(highlights))
 }
 
-fn level_filter(pair: Pair<Rule>) -> Result<Query> {
-    assert_eq!(pair.as_rule(), Rule::level_filter);
-
-    let mut inner = pair.into_inner();
-
-    let op = inner.next().unwrap().as_rule();
-    let level = parse_level(inner.next().unwrap())?;
-    Ok(match op {
-        Rule::op_equal => LevelFilter::query(closure!(clone level, | l | l == level)),
-        Rule::op_not_equal => LevelFilter::query(closure!(clone level, | l | l != level)),
-        Rule::op_lt => LevelFilter::query(closure!(clone level, | l | l > level)),
-        Rule::op_le => LevelFilter::query(closure!(clone level, | l | l >= level)),
-        Rule::op_gt => LevelFilter::query(closure!(clone level, | l | l < level)),
-        Rule::op_ge => LevelFilter::query(closure!(clone level, | l | l <= level)),
-        _ => unreachable!(),
-    })
+fn invalid_operand(expr: &FieldExpr) -> QueryError {
+    QueryError::new(
+        expr.offset,
+        Error::InvalidQueryOperand {
+            op: expr.op.as_str(),
+            operand: expr.value.kind(),
+        },
+    )
 }
 
-fn parse_string(pair: Pair<Rule>) -> Result<String> {
-    assert_eq!(pair.as_rule(), Rule::string);
+fn level_filter(expr: &LevelExpr) -> CompileResult<Query> {
+    if let ast::Op::In | ast::Op::NotIn = expr.op {
+        let levels = expr.levels.clone();
+        return Ok(match expr.op {
+            ast::Op::In => LevelFilter::query(move |l| levels.contains(&l)),
+            _ => LevelFilter::query(move |l| !levels.contains(&l)),
+        });
+    }
 
-    let inner = pair.into_inner().next().unwrap();
-    Ok(match inner.as_rule() {
-        Rule::json_string => json::from_str(inner.as_str())?,
-        Rule::simple_string => inner.as_str().into(),
-        _ => unreachable!(),
-    })
-}
+    let invalid = || {
+        QueryError::new(
+            expr.offset,
+            Error::InvalidQueryOperand {
+                op: expr.op.as_str(),
+                operand: "level",
+            },
+        )
+    };
 
-fn parse_string_set(pair: Pair<Rule>) -> Result<Vec<String>> {
-    assert_eq!(pair.as_rule(), Rule::string_set);
+    let &[level] = &expr.levels[..] else {
+        return Err(invalid());
+    };
 
-    let inner = pair.into_inner().next().unwrap();
-    Ok(match inner.as_rule() {
-        Rule::string_set_literal => parse_string_set_literal(inner)?,
-        Rule::string_set_file => parse_string_set_file(inner)?,
-        _ => unreachable!(),
+    Ok(match expr.op {
+        ast::Op::Equal => LevelFilter::query(closure!(clone level, | l | l == level)),
+        ast::Op::NotEqual => LevelFilter::query(closure!(clone level, | l | l != level)),
+        ast::Op::Less => LevelFilter::query(closure!(clone level, | l | l < level)),
+        ast::Op::LessOrEqual => LevelFilter::query(closure!(clone level, | l | l <= level)),
+        ast::Op::Greater => LevelFilter::query(closure!(clone level, | l | l > level)),
+        ast::Op::GreaterOrEqual => LevelFilter::query(closure!(clone level, | l | l >= level)),
+        _ => return Err(invalid()),
     })
 }
 
-fn parse_string_set_literal(pair: Pair<Rule>) -> Result<Vec<String>> {
-    assert_eq!(pair.as_rule(), Rule::string_set_literal);
-
-    let inner = pair.into_inner();
-    inner.map(|p| parse_string(p)).collect::<Result<Vec<_>>>()
-}
-
-fn parse_string_set_file(pair: Pair<Rule>) -> Result<Vec<String>> {
-    assert_eq!(pair.as_rule(), Rule::string_set_file);
-
-    let inner = pair.into_inner().next().unwrap();
-    let filename = parse_string(inner)?;
+fn load_string_set_file(filename: &str) -> Result<Vec<String>> {
     let stream: Box<dyn Read> = if filename == "-" {
         Box::new(std::io::stdin())
     } else {
-        Box::new(std::fs::File::open(&filename).map_err(|e| Error::FailedToReadFile {
-            path: filename.clone(),
+        Box::new(std::fs::File::open(filename).map_err(|e| Error::FailedToReadFile {
+            path: filename.to_owned(),
             source: e,
         })?)
     };
@@ -321,40 +420,20 @@ fn parse_string_set_file(pair: Pair<Rule>) -> Result<Vec<String>> {
         })
         .collect::<Result<Vec<_>>>()
         .map_err(|e| Error::FailedToLoadFile {
-            path: filename.clone(),
+            path: filename.to_owned(),
             source: Box::new(e),
         })
 }
 
-fn parse_number(pair: Pair<Rule>) -> Result<Number> {
-    assert_eq!(pair.as_rule(), Rule::number);
-
-    let inner = pair.as_str();
-    inner.parse()
-}
-
-fn parse_number_set(pair: Pair<Rule>) -> Result<Vec<Number>> {
-    assert_eq!(pair.as_rule(), Rule::number_set);
-
-    let inner = pair.into_inner();
-    inner.map(|p| parse_number(p)).collect::<Result<Vec<_>>>()
+/// Returns true if the field name stands for the recognized timestamp of a record in time comparisons.
+fn is_time_key(name: &FieldName) -> bool {
+    matches!(name, FieldName::Plain(name) if name == TIME_KEY)
 }
 
-fn parse_level(pair: Pair<Rule>) -> Result<Level> {
-    assert_eq!(pair.as_rule(), Rule::level);
-
-    let mut inner = pair.into_inner();
-    let level = parse_string(inner.next().unwrap())?;
-    Ok(RelaxedLevel::try_from(level.as_str())?.into())
-}
-
-fn parse_field_name(pair: Pair<Rule>) -> Result<FieldFilterKey<String>> {
-    assert_eq!(pair.as_rule(), Rule::field_name);
-
-    let inner = pair.into_inner().next().unwrap();
-    Ok(match inner.as_rule() {
-        Rule::json_string => FieldFilterKey::Custom(json::from_str(inner.as_str())?),
-        _ => FieldFilterKey::parse(inner.as_str())?.to_owned(),
+fn field_key(name: &FieldName, offset: usize) -> CompileResult<FieldFilterKey<String>> {
+    Ok(match name {
+        FieldName::Quoted(name) => FieldFilterKey::Custom(name.clone()),
+        FieldName::Plain(name) => FieldFilterKey::parse(name).at(offset)?.to_owned(),
     })
 }
 
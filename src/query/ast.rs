@@ -0,0 +1,330 @@
+// std imports
+use std::fmt;
+
+// third-party imports
+use pest::{Parser, error::InputLocation, iterators::Pair};
+use serde_json as json;
+
+// local imports
+use super::{QueryError, QueryParser, Rule, WithOffset};
+use crate::{level::RelaxedLevel, model::Level};
+
+// ---
+
+/// Query expression as written by the user, not yet bound to any record matching logic.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Matches if any of the operands matches, e.g. `.a=1 or .b=2`.
+    Or(Vec<Expr>),
+    /// Matches if all of the operands match, e.g. `.a=1 and .b=2`.
+    And(Vec<Expr>),
+    /// Matches if the operand does not match, e.g. `not .a=1`.
+    Not(Box<Expr>),
+    /// Matches a field value, e.g. `.a=1`.
+    Field(FieldExpr),
+    /// Matches if a field is present, e.g. `exists(.a)` or `has .a`.
+    Exists(ExistsExpr),
+    /// Matches the record level, e.g. `level>=warn`.
+    Level(LevelExpr),
+}
+
+impl Expr {
+    /// Parses the query text without loading referenced files or compiling regular expressions.
+    pub fn parse(text: impl AsRef<str>) -> Result<Self, QueryError> {
+        let text = text.as_ref();
+        let mut pairs = QueryParser::parse(Rule::input, text).map_err(|e| {
+            let offset = match e.location {
+                InputLocation::Pos(pos) => pos,
+                InputLocation::Span((start, _)) => start,
+            };
+            QueryError::new(offset, e.into())
+        })?;
+        expression(pairs.next().unwrap())
+    }
+}
+
+/// Field value condition, e.g. `.a?!=1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldExpr {
+    pub name: FieldName,
+    pub op: Op,
+    pub value: Value,
+    /// Set by the `?` modifier, makes the condition hold if the field is absent.
+    pub include_absent: bool,
+    /// Byte offset of the term in the query text.
+    pub offset: usize,
+}
+
+/// Field presence condition, e.g. `exists(.a)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExistsExpr {
+    pub name: FieldName,
+    /// Byte offset of the term in the query text.
+    pub offset: usize,
+}
+
+/// Level condition, e.g. `level>=warn` or `level in (warn,error)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelExpr {
+    pub op: Op,
+    /// Exactly one level unless `op` is [`Op::In`] or [`Op::NotIn`].
+    pub levels: Vec<Level>,
+    /// Byte offset of the term in the query text.
+    pub offset: usize,
+}
+
+/// Field name as written in the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldName {
+    /// Unquoted name, e.g. `.a.b`, `/a/b` or `msg`.
+    Plain(String),
+    /// Name given as a JSON string, e.g. `"a.b"`, matched literally.
+    Quoted(String),
+}
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    In,
+    NotIn,
+    Like,
+    NotLike,
+    Contain,
+    NotContain,
+    RegexMatch,
+    NotRegexMatch,
+}
+
+impl Op {
+    /// Returns the canonical spelling of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::In => "in",
+            Self::NotIn => "not in",
+            Self::Like => "like",
+            Self::NotLike => "not like",
+            Self::Contain => "~=",
+            Self::NotContain => "!~=",
+            Self::RegexMatch => "~~=",
+            Self::NotRegexMatch => "!~~=",
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Right-hand side of a field condition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    /// Number literal as written in the query.
+    Number(String),
+    StringSet(Vec<String>),
+    /// Path of a file with one string per line, `-` stands for stdin.
+    StringSetFile(String),
+    NumberSet(Vec<String>),
+}
+
+impl Value {
+    /// Returns a short human readable name of the value kind.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::String(_) => "string",
+            Self::Number(_) => "number",
+            Self::StringSet(_) | Self::StringSetFile(_) => "string set",
+            Self::NumberSet(_) => "number set",
+        }
+    }
+}
+
+// ---
+
+type Result<T> = std::result::Result<T, QueryError>;
+
+fn expression(pair: Pair<Rule>) -> Result<Expr> {
+    match pair.as_rule() {
+        Rule::expr_or => Ok(Expr::Or(operands(pair)?)),
+        Rule::expr_and => Ok(Expr::And(operands(pair)?)),
+        Rule::expr_not => Ok(Expr::Not(Box::new(expression(pair.into_inner().next().unwrap())?))),
+        Rule::primary => primary(pair),
+        _ => unreachable!(),
+    }
+}
+
+fn operands(pair: Pair<Rule>) -> Result<Vec<Expr>> {
+    pair.into_inner().map(expression).collect()
+}
+
+fn primary(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::primary);
+
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::term => term(inner),
+        _ => expression(inner),
+    }
+}
+
+fn term(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::term);
+
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::field_filter => field_filter(inner),
+        Rule::level_filter => level_filter(inner),
+        _ => unreachable!(),
+    }
+}
+
+fn field_filter(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::field_filter);
+
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::field_expr_filter => field_expr_filter(inner),
+        Rule::field_exists_filter => field_exists_filter(inner),
+        Rule::field_missing_filter => field_missing_filter(inner),
+        _ => unreachable!(),
+    }
+}
+
+fn field_expr_filter(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::field_expr_filter);
+
+    let offset = pair.as_span().start();
+    let mut inner = pair.into_inner();
+    let name = parse_field_name(inner.next().unwrap())?;
+
+    let (op, include_absent) = match inner.next().unwrap() {
+        flag if flag.as_rule() == Rule::include_absent_flag => (inner.next().unwrap(), true),
+        op => (op, false),
+    };
+    let op = parse_op(op);
+
+    let rhs = inner.next().unwrap();
+    let value = match rhs.as_rule() {
+        Rule::string => Value::String(parse_string(rhs)?),
+        Rule::number => Value::Number(rhs.as_str().to_owned()),
+        Rule::string_set => parse_string_set(rhs)?,
+        Rule::number_set => Value::NumberSet(rhs.into_inner().map(|p| p.as_str().to_owned()).collect()),
+        _ => unreachable!(),
+    };
+
+    Ok(Expr::Field(FieldExpr {
+        name,
+        op,
+        value,
+        include_absent,
+        offset,
+    }))
+}
+
+fn field_exists_filter(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::field_exists_filter);
+
+    let offset = pair.as_span().start();
+    let name = parse_field_name(pair.into_inner().next().unwrap())?;
+
+    Ok(Expr::Exists(ExistsExpr { name, offset }))
+}
+
+/// Parses `missing .a` as `not exists(.a)`.
+fn field_missing_filter(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::field_missing_filter);
+
+    let offset = pair.as_span().start();
+    let name = parse_field_name(pair.into_inner().next().unwrap())?;
+
+    Ok(Expr::Not(Box::new(Expr::Exists(ExistsExpr { name, offset }))))
+}
+
+fn level_filter(pair: Pair<Rule>) -> Result<Expr> {
+    assert_eq!(pair.as_rule(), Rule::level_filter);
+
+    let offset = pair.as_span().start();
+    let mut inner = pair.into_inner();
+
+    let op = parse_op(inner.next().unwrap());
+    let rhs = inner.next().unwrap();
+    let levels = match rhs.as_rule() {
+        Rule::level_set => rhs.into_inner().map(parse_level).collect::<Result<Vec<_>>>()?,
+        Rule::level => vec![parse_level(rhs.into_inner().next().unwrap())?],
+        _ => unreachable!(),
+    };
+
+    Ok(Expr::Level(LevelExpr { op, levels, offset }))
+}
+
+fn parse_op(pair: Pair<Rule>) -> Op {
+    match pair.as_rule() {
+        Rule::op_equal => Op::Equal,
+        Rule::op_not_equal => Op::NotEqual,
+        Rule::op_lt => Op::Less,
+        Rule::op_le => Op::LessOrEqual,
+        Rule::op_gt => Op::Greater,
+        Rule::op_ge => Op::GreaterOrEqual,
+        Rule::op_in => Op::In,
+        Rule::op_not_in => Op::NotIn,
+        Rule::op_like => Op::Like,
+        Rule::op_not_like => Op::NotLike,
+        Rule::op_contain => Op::Contain,
+        Rule::op_not_contain => Op::NotContain,
+        Rule::op_regex_match => Op::RegexMatch,
+        Rule::op_not_regex_match => Op::NotRegexMatch,
+        _ => unreachable!(),
+    }
+}
+
+fn parse_level(pair: Pair<Rule>) -> Result<Level> {
+    let offset = pair.as_span().start();
+    let level = parse_string(pair)?;
+    Ok(RelaxedLevel::try_from(level.as_str()).at(offset)?.into())
+}
+
+fn parse_string(pair: Pair<Rule>) -> Result<String> {
+    assert_eq!(pair.as_rule(), Rule::string);
+
+    let inner = pair.into_inner().next().unwrap();
+    Ok(match inner.as_rule() {
+        Rule::json_string => json::from_str(inner.as_str()).at(inner.as_span().start())?,
+        Rule::simple_string => inner.as_str().into(),
+        _ => unreachable!(),
+    })
+}
+
+fn parse_string_set(pair: Pair<Rule>) -> Result<Value> {
+    assert_eq!(pair.as_rule(), Rule::string_set);
+
+    let inner = pair.into_inner().next().unwrap();
+    Ok(match inner.as_rule() {
+        Rule::string_set_literal => Value::StringSet(inner.into_inner().map(parse_string).collect::<Result<_>>()?),
+        Rule::string_set_file => Value::StringSetFile(parse_string(inner.into_inner().next().unwrap())?),
+        _ => unreachable!(),
+    })
+}
+
+fn parse_field_name(pair: Pair<Rule>) -> Result<FieldName> {
+    assert_eq!(pair.as_rule(), Rule::field_name);
+
+    let inner = pair.into_inner().next().unwrap();
+    Ok(match inner.as_rule() {
+        Rule::json_string => FieldName::Quoted(json::from_str(inner.as_str()).at(inner.as_span().start())?),
+        _ => FieldName::Plain(inner.as_str().to_owned()),
+    })
+}
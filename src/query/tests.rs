@@ -1,5 +1,12 @@
 use super::*;
-use crate::model::{Parser as RecordParser, ParserSettings, RawRecord};
+use crate::{
+    app::UnixTimestampUnit,
+    model::{Parser as RecordParser, ParserSettings, RawRecord},
+    settings::PredefinedFields,
+};
+use assert_matches::assert_matches;
+use ast::{ExistsExpr, Op};
+use pest::Parser;
 use rstest::rstest;
 
 #[test]
@@ -98,6 +105,42 @@ fn test_query_not() {
     }
 }
 
+#[test]
+fn test_query_field() {
+    let query = Query::field("a=x y").unwrap() & !Query::field("b~=debug").unwrap();
+
+    assert!(parse(r#"{"a":"x y","b":"info"}"#).matches(&query));
+    assert!(!parse(r#"{"a":"x y","b":"a debug message"}"#).matches(&query));
+    assert!(!parse(r#"{"a":"x","b":"info"}"#).matches(&query));
+    assert!(Query::field("a").is_err());
+    assert_eq!(Query::field("a~=x").unwrap().highlights(), &["x"]);
+    assert!(query.highlights().is_empty());
+}
+
+#[test]
+fn test_query_exists() {
+    let query = Query::exists("a.b").unwrap();
+    assert!(parse(r#"{"a":{"b":""}}"#).matches(&query));
+    assert!(!parse(r#"{"a":{"c":1}}"#).matches(&query));
+
+    let query = Query::exists("msg").unwrap();
+    assert!(parse(r#"{"msg":"m"}"#).matches(&query));
+    assert!(!parse(r#"{"a":1}"#).matches(&query));
+}
+
+#[rstest]
+#[case("/a.b=1", true)]
+#[case("/a.b=2", false)]
+#[case("/a/b=2", true)]
+#[case("/a/b in (1, 3)", false)]
+#[case("/e~1f=4", true)]
+#[case("exists(/a/b)", true)]
+#[case("exists(/a.c)", false)]
+fn test_query_pointer(#[case] raw_query: &str, #[case] expected: bool) {
+    let record = parse(r#"{"a.b":1,"a":{"b":2},"e/f":4}"#);
+    assert_eq!(record.matches(&Query::parse(raw_query).unwrap()), expected);
+}
+
 #[test]
 fn test_query_bitwise_operators() {
     let q1 = Query::parse(".a=1").unwrap();
@@ -144,6 +187,56 @@ fn test_query_level() {
     assert!(!record.matches(&query));
 }
 
+#[rstest]
+#[case("level > info", "warning", true)]
+#[case("level > info", "info", false)]
+#[case("level >= info", "info", true)]
+#[case("level >= info", "debug", false)]
+#[case("level < info", "debug", true)]
+#[case("level < info", "error", false)]
+#[case("level <= warn", "trace", true)]
+#[case("level <= warn", "error", false)]
+fn test_query_level_order(#[case] raw_query: &str, #[case] level: &str, #[case] should_match: bool) {
+    let query = Query::parse(raw_query).unwrap();
+    let record = parse(&format!(r#"{{"level":"{level}"}}"#));
+    assert_eq!(record.matches(&query), should_match);
+}
+
+#[test]
+fn test_query_level_in() {
+    let query = Query::parse("level in (warn, \"error\")").unwrap();
+    let record = parse(r#"{"level":"warning"}"#);
+    assert!(record.matches(&query));
+    let record = parse(r#"{"level":"fatal"}"#);
+    assert!(record.matches(&query));
+    let record = parse(r#"{"level":"info"}"#);
+    assert!(!record.matches(&query));
+    let record = parse(r#"{"msg":"m"}"#);
+    assert!(!record.matches(&query));
+
+    let query = Query::parse("level not in (warn,error) and a=1").unwrap();
+    let record = parse(r#"{"level":"info","a":1}"#);
+    assert!(record.matches(&query));
+    let record = parse(r#"{"level":"error","a":1}"#);
+    assert!(!record.matches(&query));
+
+    assert!(Query::parse("level in (warn,unknown)").is_err());
+}
+
+#[test]
+fn test_query_json_in_str_quoted() {
+    let query = Query::parse(r#"v in ("a,b", c)"#).unwrap();
+    let record = parse(r#"{"v":"a,b"}"#);
+    assert!(record.matches(&query));
+    let record = parse(r#"{"v":"a"}"#);
+    assert!(!record.matches(&query));
+    let query = Query::parse(r#"v not in ("a,b", c)"#).unwrap();
+    let record = parse(r#"{"v":"c"}"#);
+    assert!(!record.matches(&query));
+    let record = parse(r#"{"v":"d"}"#);
+    assert!(record.matches(&query));
+}
+
 #[test]
 fn test_query_json_str_simple() {
     for q in &["mod=test", r#"mod="test""#] {
@@ -216,6 +309,32 @@ fn test_query_json_float() {
     assert!(record.matches(&query));
 }
 
+#[test]
+fn test_query_numeric_comparison_nested() {
+    let query = Query::parse("level=error and req.duration_ms>=500").unwrap();
+    let record = parse(r#"{"level":"error","req":{"duration_ms":500}}"#);
+    assert!(record.matches(&query));
+    let record = parse(r#"{"level":"error","req":{"duration_ms":499}}"#);
+    assert!(!record.matches(&query));
+    let record = parse(r#"{"level":"info","req":{"duration_ms":700}}"#);
+    assert!(!record.matches(&query));
+    let record = parse(r#"{"level":"error","req.duration_ms":"501"}"#);
+    assert!(record.matches(&query));
+}
+
+#[rstest]
+#[case("v > abc")]
+#[case("v >= \"1\"")]
+#[case("v < 1a")]
+#[case("v <= ")]
+fn test_query_numeric_comparison_non_numeric_literal(#[case] raw_query: &str) {
+    assert!(
+        Query::parse(raw_query).is_err(),
+        "query {:?} should fail to parse",
+        raw_query
+    );
+}
+
 #[test]
 fn test_query_json_in_str() {
     let query = Query::parse("v in (a,b,c)").unwrap();
@@ -269,7 +388,9 @@ fn query_in_set_file_invalid() {
     let result = Query::parse(format!("v in @{}", filename));
     assert!(result.is_err());
     let err = result.err().unwrap();
-    if let Error::FailedToLoadFile { path, source } = &err {
+    assert_eq!(err.offset(), 0);
+    let err = err.error();
+    if let Error::FailedToLoadFile { path, source } = err {
         assert_eq!(path, filename);
         if let Error::FailedToParseJsonLine { line, source } = &**source {
             assert_eq!(line, &2);
@@ -288,7 +409,9 @@ fn query_in_set_file_not_found() {
     let result = Query::parse(format!("v in @{}", filename));
     assert!(result.is_err());
     let err = result.err().unwrap();
-    if let Error::FailedToReadFile { path, source } = &err {
+    assert_eq!(err.offset(), 0);
+    let err = err.error();
+    if let Error::FailedToReadFile { path, source } = err {
         assert_eq!(path, filename);
         assert!(source.kind() == std::io::ErrorKind::NotFound);
     } else {
@@ -482,6 +605,20 @@ fn test_query_repeated_fields(#[case] raw_query: &str, #[case] input: &str, #[ca
 // Test exists operator with predefined fields
 #[case("exists(msg)", r#"msg=hello"#, true)] // 13
 #[case("exists(msg)", r#"x=hello"#, false)] // 14
+// Test has and missing shorthands
+#[case("has .price", r#"{"price":3}"#, true)] // 15
+#[case("has .price", r#"{"x":1}"#, false)] // 16
+#[case("missing .price", r#"{"price":3}"#, false)] // 17
+#[case("missing .price", r#"{"x":1}"#, true)] // 18
+#[case("has .a.b", r#"{"a":{"b":null}}"#, true)] // 19
+#[case("has .a.b", r#"{"a":{"c":1}}"#, false)] // 20
+#[case("missing .a.b", r#"{"a":{"c":1}}"#, true)] // 21
+#[case("has .price and missing .discount", r#"{"price":3}"#, true)] // 22
+#[case("has .price and missing .discount", r#"{"price":3,"discount":1}"#, false)] // 23
+#[case("(missing .price) or .x=1", r#"{"price":3,"x":1}"#, true)] // 24
+// Field names that look like the shorthand keywords are still usable
+#[case("has=1", r#"{"has":1}"#, true)] // 25
+#[case("missing=1", r#"{"missing":2}"#, false)] // 26
 fn test_query_exists_operator(#[case] raw_query: &str, #[case] input: &str, #[case] should_match: bool) {
     let query = Query::parse(raw_query).unwrap();
     let record = parse(input);
@@ -510,6 +647,170 @@ fn test_query_numerical_type_mismatch(#[case] raw_query: &str, #[case] input: &s
     );
 }
 
+#[rstest]
+#[case(r#".path ~~= "^/api/v2/""#, r#"{"path":"/api/v2/users"}"#, true)] // 1
+#[case(r#".path ~~= "^/api/v2/""#, r#"{"path":"/internal/api/v2/users"}"#, false)] // 2
+#[case(r#".path !~~= "^/api/v2/""#, r#"{"path":"/api/v1/users"}"#, true)] // 3
+#[case(r#".path match "^/api/v[0-9]+/""#, r#"{"path":"/api/v3/x"}"#, true)] // 4
+#[case(r#".path not match "^/api/v[0-9]+/""#, r#"{"path":"/api/v3/x"}"#, false)] // 5
+#[case(r#".req.path ~~= "^/health$""#, r#"{"req":{"path":"/health"}}"#, true)] // 6
+#[case(r#".req.path ~~= "^/health$""#, r#"{"req":{"path":"/healthz"}}"#, false)] // 7
+#[case(r#".path ~~= "(?i)^/API/""#, r#"{"path":"/api/v2/users"}"#, true)] // 8
+#[case(
+    r#".path ~~= "^/api/" and .status >= 500"#,
+    r#"{"path":"/api/x","status":503}"#,
+    true
+)] // 9
+#[case(
+    r#".path ~~= "^/api/" and .status >= 500"#,
+    r#"{"path":"/api/x","status":200}"#,
+    false
+)] // 10
+#[case(r#".path ~~= "^/api/""#, r#"{"x":1}"#, false)] // 11
+fn test_query_regex_operator(#[case] raw_query: &str, #[case] input: &str, #[case] should_match: bool) {
+    let query = Query::parse(raw_query).unwrap();
+    let record = parse(input);
+    assert_eq!(
+        record.matches(&query),
+        should_match,
+        "Query {:?} should {} input {:?}",
+        raw_query,
+        if should_match { "match" } else { "not match" },
+        input,
+    );
+}
+
+#[test]
+fn test_query_regex_invalid() {
+    assert!(Query::parse(r#".path ~~= "^(/api""#).is_err());
+}
+
+#[rstest]
+#[case(r#"msg ~= "a.b""#, &[r"a\.b"])]
+#[case(r#".path ~~= "^/api/" or msg ~= x"#, &["^/api/", "x"])]
+#[case(r#"(.a ~= x and .b = y) and .c ~~= "z+""#, &["x", "z+"])]
+#[case(r#"not msg ~= x and .a !~= y and .b ~= z"#, &["z"])]
+#[case(r#".a = x and level > info"#, &[])]
+fn test_query_highlights(#[case] raw_query: &str, #[case] expected: &[&str]) {
+    let query = Query::parse(raw_query).unwrap();
+    assert_eq!(query.highlights(), expected);
+}
+
+#[test]
+fn test_query_ast() {
+    let query = Query::parse(r#"level>=warn and (not .a?!=1 or exists("b.c"))"#).unwrap();
+    let expected = Expr::And(vec![
+        Expr::Level(LevelExpr {
+            op: Op::GreaterOrEqual,
+            levels: vec![Level::Warning],
+            offset: 0,
+        }),
+        Expr::Or(vec![
+            Expr::Not(Box::new(Expr::Field(FieldExpr {
+                name: FieldName::Plain(".a".into()),
+                op: Op::NotEqual,
+                value: Value::Number("1".into()),
+                include_absent: true,
+                offset: 21,
+            }))),
+            Expr::Exists(ExistsExpr {
+                name: FieldName::Quoted("b.c".into()),
+                offset: 31,
+            }),
+        ]),
+    ]);
+    assert_eq!(query.ast(), Some(&expected));
+
+    let query = Query::compile(&expected).unwrap();
+    assert!(query.matches(&parse(r#"{"level":"error","a":2}"#)));
+    assert!(!query.matches(&parse(r#"{"level":"info","b":{"c":1}}"#)));
+}
+
+#[test]
+fn test_query_ast_combined() {
+    let query = Query::parse(".a=1").unwrap() & !Query::parse(".b=2").unwrap();
+    assert_matches!(query.ast(), Some(Expr::And(args)) if matches!(args[1], Expr::Not(_)));
+
+    let query = Query::parse(".a=1").unwrap().or(Query::field("b=2").unwrap());
+    assert_eq!(query.ast(), None);
+}
+
+#[rstest]
+#[case(".a=1 and", 8)]
+#[case(".a=1 or (.b=2", 13)]
+#[case(r#".path ~~= "^(/api""#, 0)]
+#[case(r#".a=1 and .path ~~= "^(/api""#, 9)]
+#[case("level in (warn,unknown)", 15)]
+fn test_query_error_offset(#[case] raw_query: &str, #[case] offset: usize) {
+    assert_eq!(Query::parse(raw_query).err().unwrap().offset(), offset);
+}
+
+#[test]
+fn test_query_compile_invalid_operand() {
+    let expr = Expr::Field(FieldExpr {
+        name: FieldName::Plain("a".into()),
+        op: Op::Like,
+        value: Value::Number("1".into()),
+        include_absent: false,
+        offset: 3,
+    });
+    let err = Query::compile(&expr).err().unwrap();
+    assert_eq!(err.offset(), 3);
+    assert_matches!(
+        err.error(),
+        Error::InvalidQueryOperand {
+            op: "like",
+            operand: "number"
+        }
+    );
+}
+
+#[rstest]
+#[case("ts > 2024-01-01T00:00:00Z", true)]
+#[case("ts >= 2024-01-01T10:00:00+02:00", true)]
+#[case("ts < 2024-01-01T08:00:00Z", false)]
+#[case("ts > -1h", false)]
+#[case("ts < +1h and level=error", true)]
+#[case("ts < 2024-01-01T08:00:00Z or .n=2", true)]
+#[case(".started <= \"2024-01-01T00:00:00Z\"", true)]
+#[case(".started > 2023-12-31T23:59:59Z", true)]
+#[case(".msg > 2000-01-01T00:00:00Z", false)]
+#[case(".missing? < 2000-01-01T00:00:00Z", true)]
+fn test_query_time(#[case] raw_query: &str, #[case] expected: bool) {
+    let record = parse(r#"{"timestamp":"2024-01-01T08:30:00Z","level":"error","msg":"m","started":1704067200,"n":2}"#);
+    assert_eq!(record.matches(&Query::parse(raw_query).unwrap()), expected);
+}
+
+#[rstest]
+#[case("ts < 2000-01-01T00:00:00Z", None, false)]
+#[case("ts < 2000-01-01T00:00:00Z", Some(UnixTimestampUnit::Milliseconds), true)]
+#[case("ts > 1970-01-20T00:00:00Z", Some(UnixTimestampUnit::Milliseconds), true)]
+#[case("not ts < 2000-01-01T00:00:00Z", Some(UnixTimestampUnit::Milliseconds), false)]
+fn test_query_time_unix_unit(#[case] raw_query: &str, #[case] unit: Option<UnixTimestampUnit>, #[case] expected: bool) {
+    let raw = RawRecord::parser()
+        .parse(br#"{"ts":1704067200,"msg":"m"}"#)
+        .next()
+        .unwrap()
+        .unwrap()
+        .record;
+    let parser = RecordParser::new(ParserSettings::new(&PredefinedFields::default(), [], unit));
+    let record = parser.parse(&raw);
+    assert_eq!(record.matches(&Query::parse(raw_query).unwrap()), expected);
+}
+
+#[rstest]
+#[case("ts > 1704067200", 0)]
+#[case(".a < abc", 0)]
+#[case(".a > 1 and ts >= 2024-13-01T00:00:00Z", 11)]
+fn test_query_time_error(#[case] raw_query: &str, #[case] offset: usize) {
+    let err = Query::parse(raw_query).err().unwrap();
+    assert_eq!(err.offset(), offset);
+    assert_matches!(
+        err.error(),
+        Error::AmbiguousTimeComparison { .. } | Error::UnrecognizedTime(_)
+    );
+}
+
 fn parse(s: &str) -> Record<'_> {
     let raw = RawRecord::parser().parse(s.as_bytes()).next().unwrap().unwrap().record;
     let parser = RecordParser::new(ParserSettings::default());
@@ -0,0 +1,182 @@
+//! Lazy iteration over records parsed from a reader, for using the parser without any formatting.
+
+// std imports
+use std::{
+    collections::VecDeque,
+    io::{ErrorKind, Read},
+};
+
+// local imports
+use crate::{
+    error::Result,
+    model::{ParseOptions, Record, parse_record},
+    scanning::{Delimit, Delimiter, Search},
+};
+
+// ---
+
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Returns an iterator over the records read from the reader, with lines delimited by the default delimiter.
+///
+/// The input format of each line is detected automatically unless it is set in the options,
+/// and a line containing several JSON records yields each of them.
+///
+/// # Example
+///
+/// ```
+/// let input = "{\"msg\":\"first\"}\nmsg=second\n".as_bytes();
+/// let options = hl::ParseOptions::default();
+/// for item in hl::records(input, &options) {
+///     let item = item.unwrap();
+///     let record = item.record();
+///     assert!(record.message.is_some());
+/// }
+/// ```
+pub fn records<R: Read>(reader: R, options: &ParseOptions) -> Records<'_, R> {
+    Records::new(reader, options)
+}
+
+// ---
+
+/// Iterator over the records read from a reader, see [`records`].
+///
+/// Reading stops after the first I/O error, while a parse error only affects the line it occurs in.
+pub struct Records<'o, R> {
+    reader: R,
+    options: &'o ParseOptions,
+    searcher: Box<dyn Search>,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    pending: VecDeque<Result<RecordBuf<'o>>>,
+}
+
+impl<'o, R: Read> Records<'o, R> {
+    pub fn new(reader: R, options: &'o ParseOptions) -> Self {
+        Self {
+            reader,
+            options,
+            searcher: Delimiter::default().into_searcher(),
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Sets the delimiter of the lines.
+    pub fn with_delimiter(self, delimiter: Delimiter) -> Self {
+        Self {
+            searcher: delimiter.into_searcher(),
+            ..self
+        }
+    }
+
+    /// Reads the next line, without the delimiter.
+    fn next_line(&mut self) -> Option<Result<Vec<u8>>> {
+        loop {
+            let rest = &self.buf[self.pos..];
+            if let Some(range) = self.searcher.search_l(rest, true) {
+                let line = rest[..range.start].to_vec();
+                self.pos += range.end;
+                return Some(Ok(line));
+            }
+            if self.eof {
+                if rest.is_empty() {
+                    return None;
+                }
+                let line = rest.to_vec();
+                self.pos = self.buf.len();
+                return Some(Ok(line));
+            }
+
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+            let len = self.buf.len();
+            self.buf.resize(len + BLOCK_SIZE, 0);
+            let result = self.reader.read(&mut self.buf[len..]);
+            self.buf.truncate(len + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => self.eof = true,
+                Ok(_) => {}
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    self.eof = true;
+                    self.buf.clear();
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+    }
+
+    /// Splits the line into records and queues them, along with a parse error if any.
+    fn enqueue(&mut self, line: &[u8]) {
+        let mut stream = self.options.raw_parser().parse(line);
+        while let Some(item) = stream.next() {
+            match item {
+                Ok(record) => {
+                    let text = String::from_utf8(line[record.offsets].to_vec());
+                    self.pending.push_back(text.map_err(Into::into).map(|text| RecordBuf {
+                        text,
+                        options: self.options,
+                    }));
+                }
+                Err(err) => {
+                    self.pending.push_back(Err(err));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<'o, R: Read> Iterator for Records<'o, R> {
+    type Item = Result<RecordBuf<'o>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(item);
+            }
+            match self.next_line()? {
+                Ok(line) => self.enqueue(&line),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+// ---
+
+/// Text of a single record owned by the iterator item, see [`records`].
+///
+/// A [`Record`] refers to the text it is parsed from, so it is produced by [`RecordBuf::record`]
+/// that parses the text again on each call.
+#[derive(Clone)]
+pub struct RecordBuf<'o> {
+    text: String,
+    options: &'o ParseOptions,
+}
+
+impl RecordBuf<'_> {
+    /// Returns the source text of the record, without a non-JSON prefix if it is allowed by the options.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the parsed record.
+    pub fn record(&self) -> Record<'_> {
+        // the text is already known to be a valid record
+        parse_record(&self.text, self.options).unwrap_or_default()
+    }
+}
+
+impl std::fmt::Debug for RecordBuf<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RecordBuf").field(&self.text).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests;
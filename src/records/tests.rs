@@ -0,0 +1,67 @@
+use super::*;
+
+use crate::level::Level;
+
+fn texts(input: &[u8], options: &ParseOptions) -> Vec<String> {
+    records(input, options)
+        .map(|item| item.unwrap().text().to_owned())
+        .collect()
+}
+
+#[test]
+fn test_records() {
+    let options = ParseOptions::default();
+    let input = b"{\"msg\":\"a\",\"level\":\"error\"}\r\n\nmsg=b level=debug\n{\"msg\":\"c\"}{\"msg\":\"d\"}";
+
+    let items = records(&input[..], &options).collect::<Result<Vec<_>>>().unwrap();
+    assert_eq!(
+        items.iter().map(|item| item.text()).collect::<Vec<_>>(),
+        vec![
+            r#"{"msg":"a","level":"error"}"#,
+            "msg=b level=debug",
+            r#"{"msg":"c"}"#,
+            r#"{"msg":"d"}"#
+        ]
+    );
+
+    let record = items[1].record();
+    assert_eq!(record.level, Some(Level::Debug));
+    assert!(record.message.is_some());
+    assert_eq!(items[0].record().level, Some(Level::Error));
+}
+
+#[test]
+fn test_records_errors() {
+    let options = ParseOptions::default();
+    let input = b"{\"msg\":\"a\"}\n{\"msg\":\n{\"msg\":\"c\"}\n";
+
+    let items = records(&input[..], &options).collect::<Vec<_>>();
+    assert_eq!(items.len(), 3);
+    assert!(items[0].is_ok());
+    assert!(items[1].is_err());
+    assert_eq!(items[2].as_ref().unwrap().text(), r#"{"msg":"c"}"#);
+}
+
+#[test]
+fn test_records_delimiter() {
+    let options = ParseOptions::default();
+    let input = b"msg=a\0{\"msg\":\n\"b\"}";
+
+    let items = records(&input[..], &options)
+        .with_delimiter(Delimiter::Byte(0))
+        .map(|item| item.unwrap().text().to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(items, vec!["msg=a", "{\"msg\":\n\"b\"}"]);
+}
+
+#[test]
+fn test_records_long_lines() {
+    let options = ParseOptions::default();
+    let value = "x".repeat(BLOCK_SIZE * 2);
+    let input = format!("msg={value}\nmsg=y");
+
+    assert_eq!(
+        texts(input.as_bytes(), &options),
+        vec![format!("msg={value}"), "msg=y".to_owned()]
+    );
+}
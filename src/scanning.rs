@@ -2,8 +2,9 @@
 use std::cmp::min;
 use std::collections::VecDeque;
 use std::convert::From;
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 use std::ops::Range;
+use std::str::FromStr;
 use std::sync::Arc;
 
 // third-party imports
@@ -49,6 +50,77 @@ pub enum Delimiter {
     SmartNewLine,
 }
 
+impl Delimiter {
+    /// Returns the last byte of the delimiter, if any.
+    #[inline]
+    pub fn last_byte(&self) -> Option<u8> {
+        match self {
+            Self::Byte(b) => Some(*b),
+            Self::Bytes(b) => b.last().copied(),
+            Self::Char(c) => c.to_string().as_bytes().last().copied(),
+            Self::Str(s) => s.as_bytes().last().copied(),
+            Self::SmartNewLine => Some(b'\n'),
+        }
+    }
+}
+
+impl FromStr for Delimiter {
+    type Err = Error;
+
+    /// Parses a delimiter given either by name, e.g. `NUL`, `LF`, `CR` or `CRLF`,
+    /// or as a string that may contain escape sequences, e.g. `\0`, `\t` or `\x1e`.
+    fn from_str(s: &str) -> Result<Self> {
+        let named = match s.to_lowercase().as_str() {
+            "nul" => Some(Self::Byte(0)),
+            "lf" => Some(Self::Byte(b'\n')),
+            "cr" => Some(Self::Byte(b'\r')),
+            "crlf" | "" => Some(Self::default()),
+            _ => None,
+        };
+        if let Some(delimiter) = named {
+            return Ok(delimiter);
+        }
+
+        let error = |reason| Error::InvalidDelimiter {
+            value: s.to_owned(),
+            reason,
+        };
+
+        let mut bytes = Vec::with_capacity(s.len());
+        let mut input = s.bytes();
+        while let Some(b) = input.next() {
+            if b != b'\\' {
+                bytes.push(b);
+                continue;
+            }
+            bytes.push(match input.next() {
+                Some(b'0') => 0,
+                Some(b'n') => b'\n',
+                Some(b'r') => b'\r',
+                Some(b't') => b'\t',
+                Some(b'\\') => b'\\',
+                Some(b'x') => {
+                    let digits = [input.next(), input.next()];
+                    let [Some(hi), Some(lo)] = digits else {
+                        return Err(error("expected two hex digits after \\x"));
+                    };
+                    let hex = |b: u8| (b as char).to_digit(16);
+                    match (hex(hi), hex(lo)) {
+                        (Some(hi), Some(lo)) => (hi * 16 + lo) as u8,
+                        _ => return Err(error("expected two hex digits after \\x")),
+                    }
+                }
+                _ => return Err(error("unsupported escape sequence")),
+            });
+        }
+
+        Ok(match bytes.len() {
+            1 => Self::Byte(bytes[0]),
+            _ => Self::Bytes(bytes),
+        })
+    }
+}
+
 impl Default for Delimiter {
     #[inline]
     fn default() -> Self {
@@ -891,5 +963,104 @@ impl<'a, 'b, D: Delimit> Iterator for ScannerJumboIter<'a, 'b, D> {
 
 // ---
 
+/// Scans seekable input stream backwards, starting from the end,
+/// and splits it into segments containing a whole number of tokens delimited by the given delimiter.
+///
+/// Segments are returned in reverse order, but tokens within each segment keep their original order.
+/// Tokens crossing the boundary of a block are kept until the preceding block is read.
+pub struct ReverseScanner<D> {
+    delimiter: D,
+    block_size: usize,
+}
+
+impl<D: Delimit> ReverseScanner<D> {
+    /// Returns a new ReverseScanner reading the input by blocks of the given size.
+    #[inline]
+    pub fn new(delimiter: D, block_size: usize) -> Self {
+        Self {
+            delimiter,
+            block_size: block_size.max(1),
+        }
+    }
+
+    /// Returns an iterator over segments found in the input, from the last one to the first one.
+    pub fn items<'a, R: Read + Seek + ?Sized>(
+        &self,
+        input: &'a mut R,
+    ) -> Result<ReverseScannerIter<'a, D::Searcher, R>> {
+        let pos = input.seek(SeekFrom::End(0))?;
+        Ok(ReverseScannerIter {
+            searcher: self.delimiter.clone().into_searcher(),
+            block_size: self.block_size,
+            input,
+            pos,
+            carry: Vec::new(),
+            done: false,
+        })
+    }
+}
+
+pub struct ReverseScannerIter<'a, S, R: ?Sized> {
+    searcher: S,
+    block_size: usize,
+    input: &'a mut R,
+    pos: u64,
+    carry: Vec<u8>,
+    done: bool,
+}
+
+impl<'a, S: Search, R: Read + Seek + ?Sized> ReverseScannerIter<'a, S, R> {
+    fn read_block(&mut self) -> Result<Vec<u8>> {
+        let n = min(self.block_size as u64, self.pos);
+        self.pos -= n;
+        self.input.seek(SeekFrom::Start(self.pos))?;
+        let mut data = vec![0; n as usize];
+        self.input.read_exact(&mut data)?;
+        data.append(&mut self.carry);
+        Ok(data)
+    }
+}
+
+impl<'a, S: Search, R: Read + Seek + ?Sized> Iterator for ReverseScannerIter<'a, S, R> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if self.pos == 0 {
+                self.done = true;
+                return (!self.carry.is_empty()).then(|| Ok(std::mem::take(&mut self.carry)));
+            }
+
+            let mut data = match self.read_block() {
+                Ok(data) => data,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+            if self.pos == 0 {
+                self.done = true;
+                return Some(Ok(data));
+            }
+
+            // the head of the block up to the first delimiter may be the tail of a token started in the preceding block
+            match self.searcher.search_l(&data, false) {
+                Some(range) => {
+                    let segment = data.split_off(range.end);
+                    data.truncate(range.start);
+                    self.carry = data;
+                    if !segment.is_empty() {
+                        return Some(Ok(segment));
+                    }
+                }
+                None => self.carry = data,
+            }
+        }
+        None
+    }
+}
+
+// ---
+
 #[cfg(test)]
 mod tests;
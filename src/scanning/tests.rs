@@ -1,5 +1,7 @@
 use super::*;
 
+use rstest::rstest;
+
 #[test]
 fn test_empty() {
     let searcher = b'/'.into_searcher();
@@ -366,3 +368,58 @@ fn test_smart_newline_searcher_partial_match_l() {
     let result = searcher.partial_match_l(buf);
     assert_eq!(result, None);
 }
+
+#[rstest]
+#[case("nul", Delimiter::Byte(0))]
+#[case("CRLF", Delimiter::SmartNewLine)]
+#[case("", Delimiter::SmartNewLine)]
+#[case(r"\0", Delimiter::Byte(0))]
+#[case(r"\x1e", Delimiter::Byte(0x1e))]
+#[case(r"\x1E\n", Delimiter::Bytes(vec![0x1e, b'\n']))]
+#[case(";", Delimiter::Byte(b';'))]
+#[case("--", Delimiter::Bytes(b"--".to_vec()))]
+fn test_delimiter_from_str(#[case] input: &str, #[case] expected: Delimiter) {
+    assert_eq!(input.parse::<Delimiter>().unwrap(), expected);
+}
+
+#[rstest]
+#[case(r"\x1")]
+#[case(r"\xzz")]
+#[case(r"\q")]
+#[case(r"\")]
+fn test_delimiter_from_str_invalid(#[case] input: &str) {
+    assert!(matches!(
+        input.parse::<Delimiter>(),
+        Err(Error::InvalidDelimiter { .. })
+    ));
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[case(5)]
+#[case(64)]
+fn test_reverse_scanner(#[case] block_size: usize) {
+    let data = b"a\r\nbb\nccc\r\n\ndddd\neeeee";
+    let scanner = ReverseScanner::new(Delimiter::default(), block_size);
+    let mut input = std::io::Cursor::new(&data[..]);
+    let segments = scanner.items(&mut input).unwrap().collect::<Result<Vec<_>>>().unwrap();
+
+    assert!(segments.iter().all(|segment| !segment.is_empty()));
+
+    let searcher = Delimiter::default().into_searcher();
+    let tokens = segments
+        .iter()
+        .rev()
+        .flat_map(|segment| searcher.split(segment).filter(|token| !token.is_empty()))
+        .collect::<Vec<_>>();
+    assert_eq!(tokens, vec![&b"a"[..], b"bb", b"ccc", b"dddd", b"eeeee"]);
+}
+
+#[test]
+fn test_reverse_scanner_empty() {
+    let scanner = ReverseScanner::new(b'\n', 4);
+    let mut input = std::io::Cursor::new(Vec::new());
+    assert_eq!(scanner.items(&mut input).unwrap().count(), 0);
+}
@@ -19,7 +19,7 @@ use strum::{Display, IntoEnumIterator};
 
 // local imports
 use crate::level::{InfallibleLevel, Level};
-use crate::{error::Error, xerr::Suggestions};
+use crate::{error::Error, themecfg, xerr::Suggestions};
 
 // test imports
 #[cfg(test)]
@@ -47,6 +47,10 @@ pub struct Settings {
     #[serde(deserialize_with = "enumset_serde::deserialize")]
     pub input_info: InputInfoSet,
     pub ascii: AsciiModeOpt,
+    pub color: ColorMode,
+    #[serde(default)]
+    pub rules: Vec<StyleRule>,
+    pub highlight_style: themecfg::Style,
 }
 
 impl Settings {
@@ -138,6 +142,21 @@ pub struct Fields {
     pub predefined: PredefinedFields,
     pub ignore: Vec<String>,
     pub hide: Vec<String>,
+    #[serde(default)]
+    pub aliases: FieldAliases,
+}
+
+// ---
+
+/// Maps alternative keys of top-level fields to their canonical keys, e.g. `svc` to `service`.
+#[derive(Debug, Serialize, Deserialize, Deref, Clone, Default, PartialEq, Eq, From)]
+pub struct FieldAliases(BTreeMap<String, String>);
+
+impl Default for &FieldAliases {
+    fn default() -> Self {
+        static DEFAULT: Lazy<FieldAliases> = Lazy::new(FieldAliases::default);
+        &DEFAULT
+    }
 }
 
 // ---
@@ -326,6 +345,13 @@ impl Field {
             show: FieldShowOption::Auto,
         }
     }
+
+    /// Moves the given names to the front of the list, so they take precedence over the rest.
+    pub fn prioritize<S: AsRef<str>, I: IntoIterator<Item = S>>(&mut self, names: I) {
+        let names: Vec<String> = names.into_iter().map(|name| name.as_ref().to_owned()).collect();
+        self.names.retain(|name| !names.contains(name));
+        self.names.splice(0..0, names);
+    }
 }
 
 // ---
@@ -459,6 +485,8 @@ pub struct Punctuation {
     pub input_name_common_part: DisplayVariant,
     pub array_separator: DisplayVariant,
     pub message_delimiter: DisplayVariant,
+    pub color_by_marker: DisplayVariant,
+    pub truncation_indicator: DisplayVariant,
 }
 
 impl Punctuation {
@@ -482,6 +510,8 @@ impl Punctuation {
             input_name_common_part: Self::resolve_field(&self.input_name_common_part, mode),
             array_separator: Self::resolve_field(&self.array_separator, mode),
             message_delimiter: Self::resolve_field(&self.message_delimiter, mode),
+            color_by_marker: Self::resolve_field(&self.color_by_marker, mode),
+            truncation_indicator: Self::resolve_field(&self.truncation_indicator, mode),
         }
     }
 
@@ -511,6 +541,8 @@ impl Default for Punctuation {
             input_name_common_part: "...".into(),
             array_separator: " ".into(),
             message_delimiter: "::".into(),
+            color_by_marker: "| ".into(),
+            truncation_indicator: "...".into(),
         }
     }
 }
@@ -537,6 +569,8 @@ impl Sample for Punctuation {
             input_name_common_part: DisplayVariant::ascii("**").unicode("★★"),
             array_separator: ", ".into(),
             message_delimiter: "::".into(),
+            color_by_marker: DisplayVariant::ascii("| ").unicode("┃ "),
+            truncation_indicator: DisplayVariant::ascii("...").unicode("…"),
         }
     }
 }
@@ -564,6 +598,8 @@ pub struct ResolvedPunctuation {
     pub input_name_common_part: String,
     pub array_separator: String,
     pub message_delimiter: String,
+    pub color_by_marker: String,
+    pub truncation_indicator: String,
 }
 
 /// Configuration option for ASCII mode.
@@ -602,6 +638,36 @@ impl AsciiModeOpt {
     }
 }
 
+// ---
+
+/// Color output control.
+#[derive(Default, Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorMode {
+    /// Use colors if the output is a terminal and `NO_COLOR` environment variable is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+// ---
+
+/// Conditional style applied to the records matching a query.
+///
+/// The style is applied to the whole record, or only to the given field if `field` is set.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct StyleRule {
+    /// Query expression with the same syntax as the `--query` option.
+    pub when: String,
+    #[serde(default)]
+    pub field: Option<String>,
+    pub style: themecfg::Style,
+}
+
+// ---
+
 /// Controls whether ASCII-only characters should be used in formatted output.
 ///
 /// The formatter can produce output in either ASCII-only mode or with full Unicode characters,
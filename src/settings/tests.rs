@@ -7,6 +7,9 @@ fn test_default_settings() {
         assert_eq!(settings.time_format, "%b %d %T.%3N");
         assert_eq!(settings.time_zone, chrono_tz::UTC);
         assert_eq!(settings.theme, "uni");
+        assert_eq!(settings.color, ColorMode::Auto);
+        assert_eq!(settings.rules, vec![]);
+        assert_eq!(settings.highlight_style.modes, vec![themecfg::Mode::Underline]);
     };
 
     let settings: &'static Settings = Default::default();
@@ -14,6 +17,13 @@ fn test_default_settings() {
     test(&Settings::default());
 }
 
+#[test]
+fn test_field_prioritize() {
+    let mut field = Field::new(vec!["ts".into(), "time".into(), "@t".into()]);
+    field.prioritize(["@t", "eventTime"]);
+    assert_eq!(field.names, vec!["@t", "eventTime", "ts", "time"]);
+}
+
 #[test]
 fn test_load_settings_k8s() {
     let settings = Settings::load([SourceFile::new("etc/defaults/config-k8s.yaml").into()]).unwrap();
@@ -29,6 +39,51 @@ fn test_load_settings_k8s() {
     assert_eq!(settings.theme, "uni");
 }
 
+#[test]
+fn test_load_settings_user_defaults() {
+    let settings = Settings::load([Source::string(
+        "theme: classic\ntime-zone: Europe/Berlin\ncolor: never\nfields:\n  hide: [\"pid\", \"host.*\"]\n",
+        FileFormat::Yaml,
+    )])
+    .unwrap();
+    assert_eq!(settings.theme, "classic");
+    assert_eq!(settings.time_zone, chrono_tz::Europe::Berlin);
+    assert_eq!(settings.color, ColorMode::Never);
+    assert_eq!(settings.fields.hide, vec!["pid", "host.*"]);
+    assert_eq!(settings.time_format, "%b %d %T.%3N");
+}
+
+#[test]
+fn test_load_settings_rules() {
+    let settings = Settings::load([Source::string(
+        "rules:\n  - when: .status>=500\n    style: { modes: [bold], background: red }\n  - when: exists(.user)\n    field: user\n    style: { foreground: 214 }\n",
+        FileFormat::Yaml,
+    )])
+    .unwrap();
+    assert_eq!(
+        settings.rules,
+        vec![
+            StyleRule {
+                when: ".status>=500".into(),
+                field: None,
+                style: themecfg::Style {
+                    modes: vec![themecfg::Mode::Bold],
+                    background: Some(themecfg::Color::Plain(themecfg::PlainColor::Red)),
+                    ..Default::default()
+                },
+            },
+            StyleRule {
+                when: "exists(.user)".into(),
+                field: Some("user".into()),
+                style: themecfg::Style {
+                    foreground: Some(themecfg::Color::Palette(214)),
+                    ..Default::default()
+                },
+            },
+        ]
+    );
+}
+
 #[test]
 fn test_unknown_level_values() {
     let variant = RawLevelFieldVariant {
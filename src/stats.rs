@@ -0,0 +1,265 @@
+// std imports
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    io::{Result, Write},
+};
+
+// third-party imports
+use chrono::{DateTime, SecondsFormat};
+use enum_map::EnumMap;
+use serde::Serialize;
+use serde_json as json;
+use strum::IntoEnumIterator;
+
+// local imports
+use crate::{datefmt::DateTimeFormatter, grouping, level::Level, model::Record};
+
+// ---
+
+/// Options for the statistics mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatsOptions {
+    /// Key of the field used to group records, e.g. `msg`.
+    pub group_by: String,
+    /// Maximum number of groups to show.
+    pub top: usize,
+    /// Output format of the summary.
+    pub format: StatsFormat,
+}
+
+impl Default for StatsOptions {
+    fn default() -> Self {
+        Self {
+            group_by: "msg".into(),
+            top: 10,
+            format: StatsFormat::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StatsFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Options for the count mode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CountOptions {
+    /// Key of the field used to break the count down, `level` stands for the recognized level.
+    pub by: Option<String>,
+}
+
+impl CountOptions {
+    /// Returns the key to pass to [`Stats::add`].
+    pub fn group_by(&self) -> &str {
+        match self.by.as_deref() {
+            Some(LEVEL_KEY) | None => "",
+            Some(key) => key,
+        }
+    }
+}
+
+// ---
+
+/// Aggregated statistics of the processed records.
+#[derive(Default, Debug)]
+pub struct Stats {
+    total: u64,
+    levels: EnumMap<Level, u64>,
+    no_level: u64,
+    first: Option<(i64, u32)>,
+    last: Option<(i64, u32)>,
+    groups: HashMap<String, u64>,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accounts the record using the given group-by field key.
+    pub fn add(&mut self, record: &Record, group_by: &str) {
+        self.total += 1;
+
+        match record.level {
+            Some(level) => self.levels[level] += 1,
+            None => self.no_level += 1,
+        }
+
+        if let Some(ts) = record.ts.as_ref().and_then(|ts| ts.unix_utc()) {
+            self.first = Some(self.first.map_or(ts, |first| first.min(ts)));
+            self.last = Some(self.last.map_or(ts, |last| last.max(ts)));
+        }
+
+        if group_by.is_empty() {
+            return;
+        }
+
+        if let Some(key) = grouping::key(record, group_by) {
+            *self.groups.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Writes the summary to the output using the given options.
+    pub fn write<W: Write + ?Sized>(
+        &self,
+        output: &mut W,
+        options: &StatsOptions,
+        tsf: &DateTimeFormatter,
+    ) -> Result<()> {
+        match options.format {
+            StatsFormat::Table => self.write_table(output, options, tsf),
+            StatsFormat::Json => self.write_json(output, options),
+        }
+    }
+
+    /// Writes the number of records, broken down as requested by the options.
+    pub fn write_counts<W: Write + ?Sized>(&self, output: &mut W, options: &CountOptions) -> Result<()> {
+        match options.by.as_deref() {
+            None => writeln!(output, "{}", self.total),
+            Some(LEVEL_KEY) => {
+                for (name, count) in self.level_counts() {
+                    writeln!(output, "{:>10}  {}", count, name)?;
+                }
+                Ok(())
+            }
+            Some(_) => {
+                let groups = self.top_groups(usize::MAX);
+                let missing = self.total - groups.iter().map(|&(_, count)| count).sum::<u64>();
+                for (key, count) in groups {
+                    writeln!(output, "{:>10}  {}", count, key)?;
+                }
+                if missing != 0 {
+                    writeln!(output, "{:>10}  {}", missing, "(none)")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn write_table<W: Write + ?Sized>(
+        &self,
+        output: &mut W,
+        options: &StatsOptions,
+        tsf: &DateTimeFormatter,
+    ) -> Result<()> {
+        let time = |ts: Option<(i64, u32)>| {
+            let mut buf = Vec::new();
+            match ts.and_then(|(s, n)| DateTime::from_timestamp(s, n)) {
+                Some(ts) => tsf.format(&mut buf, ts.fixed_offset()),
+                None => buf.push(b'-'),
+            }
+            String::from_utf8_lossy(&buf).into_owned()
+        };
+
+        writeln!(output, "{:<10} {}", "records", self.total)?;
+        writeln!(output, "{:<10} {}", "first", time(self.first))?;
+        writeln!(output, "{:<10} {}", "last", time(self.last))?;
+
+        writeln!(output)?;
+        writeln!(output, "{:<10} {:>10} {:>7}", "level", "count", "share")?;
+        for (name, count) in self.level_counts() {
+            writeln!(output, "{:<10} {:>10} {:>6.1}%", name, count, self.share(count))?;
+        }
+
+        let groups = self.top_groups(options.top);
+        if !groups.is_empty() {
+            writeln!(output)?;
+            writeln!(output, "{:>10} {:>7}  {}", "count", "share", options.group_by)?;
+            for (key, count) in groups {
+                writeln!(output, "{:>10} {:>6.1}%  {}", count, self.share(count), key)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_json<W: Write + ?Sized>(&self, output: &mut W, options: &StatsOptions) -> Result<()> {
+        let time = |ts: Option<(i64, u32)>| {
+            ts.and_then(|(s, n)| DateTime::from_timestamp(s, n))
+                .map(|ts| ts.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        };
+
+        let summary = JsonSummary {
+            records: self.total,
+            first: time(self.first),
+            last: time(self.last),
+            levels: self
+                .level_counts()
+                .map(|(level, count)| JsonLevel { level, count })
+                .collect(),
+            group_by: &options.group_by,
+            groups: self
+                .top_groups(options.top)
+                .into_iter()
+                .map(|(value, count)| JsonGroup { value, count })
+                .collect(),
+        };
+
+        json::to_writer(&mut *output, &summary)?;
+        writeln!(output)
+    }
+
+    fn level_counts(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        Level::iter()
+            .map(|level| (level_name(level), self.levels[level]))
+            .chain((self.no_level != 0).then_some(("(none)", self.no_level)))
+    }
+
+    fn top_groups(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut groups: Vec<_> = self.groups.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        groups.sort_by_key(|&(k, v)| (Reverse(v), k));
+        groups.truncate(n);
+        groups
+    }
+
+    fn share(&self, count: u64) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+// ---
+
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    records: u64,
+    first: Option<String>,
+    last: Option<String>,
+    levels: Vec<JsonLevel>,
+    group_by: &'a str,
+    groups: Vec<JsonGroup<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonLevel {
+    level: &'static str,
+    count: u64,
+}
+
+#[derive(Serialize)]
+struct JsonGroup<'a> {
+    value: &'a str,
+    count: u64,
+}
+
+const LEVEL_KEY: &str = "level";
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+use super::*;
+
+use chrono_tz::UTC;
+use encstr::EncodedString;
+
+use crate::{datefmt::LinuxDateFormat, model::RawValue, timestamp::Timestamp, timezone::Tz};
+
+fn record<'a>(ts: &'a str, level: Option<Level>, msg: &'a str) -> Record<'a> {
+    Record {
+        ts: Some(Timestamp::new(ts)),
+        level,
+        message: Some(RawValue::String(EncodedString::raw(msg))),
+        ..Default::default()
+    }
+}
+
+fn stats() -> Stats {
+    let mut stats = Stats::new();
+    stats.add(&record("2000-01-02T03:04:06Z", Some(Level::Info), "m1"), "msg");
+    stats.add(&record("2000-01-02T03:04:05.123Z", Some(Level::Error), "m2"), "msg");
+    stats.add(&record("2000-01-02T03:04:05.500Z", Some(Level::Info), "m1"), "msg");
+    stats
+}
+
+fn tsf() -> DateTimeFormatter {
+    DateTimeFormatter::new(LinuxDateFormat::new("%Y-%m-%d %T.%3N").compile(), Tz::IANA(UTC))
+}
+
+#[test]
+fn test_table() {
+    let mut buf = Vec::new();
+    stats().write(&mut buf, &StatsOptions::default(), &tsf()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        concat!(
+            "records    3\n",
+            "first      2000-01-02 03:04:05.123\n",
+            "last       2000-01-02 03:04:06.000\n",
+            "\n",
+            "level           count   share\n",
+            "error               1   33.3%\n",
+            "warning             0    0.0%\n",
+            "info                2   66.7%\n",
+            "debug               0    0.0%\n",
+            "trace               0    0.0%\n",
+            "\n",
+            "     count   share  msg\n",
+            "         2   66.7%  m1\n",
+            "         1   33.3%  m2\n",
+        )
+    );
+}
+
+#[test]
+fn test_json() {
+    let options = StatsOptions {
+        top: 1,
+        format: StatsFormat::Json,
+        ..Default::default()
+    };
+    let mut buf = Vec::new();
+    stats().write(&mut buf, &options, &tsf()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        concat!(
+            r#"{"records":3,"first":"2000-01-02T03:04:05.123Z","last":"2000-01-02T03:04:06Z","#,
+            r#""levels":[{"level":"error","count":1},{"level":"warning","count":0},{"level":"info","count":2},"#,
+            r#"{"level":"debug","count":0},{"level":"trace","count":0}],"#,
+            r#""group_by":"msg","groups":[{"value":"m1","count":2}]}"#,
+            "\n",
+        )
+    );
+}
+
+#[test]
+fn test_counts() {
+    let write = |stats: &Stats, by: Option<&str>| {
+        let mut buf = Vec::new();
+        let options = CountOptions { by: by.map(Into::into) };
+        stats.write_counts(&mut buf, &options).unwrap();
+        String::from_utf8(buf).unwrap()
+    };
+
+    assert_eq!(write(&stats(), None), "3\n");
+    assert_eq!(
+        write(&stats(), Some("level")),
+        concat!(
+            "         1  error\n",
+            "         0  warning\n",
+            "         2  info\n",
+            "         0  debug\n",
+            "         0  trace\n",
+        )
+    );
+
+    let mut stats = stats();
+    let mut other = record("2000-01-02T03:04:07Z", None, "");
+    other.message = None;
+    stats.add(&other, "msg");
+    assert_eq!(
+        write(&stats, Some("msg")),
+        concat!("         2  m1\n", "         1  m2\n", "         1  (none)\n")
+    );
+}
@@ -0,0 +1,240 @@
+//! Parsing of [RFC 5424](https://datatracker.ietf.org/doc/html/rfc5424) syslog messages.
+
+// workspace imports
+use encstr::EncodedString;
+
+// local imports
+use crate::{
+    error::{Error, Result},
+    model::RawValue,
+};
+
+// ---
+
+const NIL: &str = "-";
+const BOM: &str = "\u{feff}";
+
+/// Returns `true` if the data starts with a syslog priority token, e.g. `<134>`.
+pub fn looks_like_syslog(data: &[u8]) -> bool {
+    match data {
+        [b'<', rest @ ..] => {
+            let n = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+            (1..=3).contains(&n) && rest.get(n) == Some(&b'>')
+        }
+        _ => false,
+    }
+}
+
+// ---
+
+/// Syslog message split into its header parts, structured data and free-form message.
+///
+/// Parts having the nil value `-` are represented by `None`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Message<'a> {
+    pub facility: u8,
+    pub severity: u8,
+    pub timestamp: Option<&'a str>,
+    pub hostname: Option<&'a str>,
+    pub app_name: Option<&'a str>,
+    pub proc_id: Option<&'a str>,
+    pub msg_id: Option<&'a str>,
+    pub structured_data: &'a str,
+    pub msg: Option<&'a str>,
+}
+
+impl<'a> Message<'a> {
+    pub fn parse(line: &'a str) -> Result<Self> {
+        let (pri, rest) = line
+            .strip_prefix('<')
+            .and_then(|s| s.split_once('>'))
+            .ok_or(Error::SyslogParseError("missing priority"))?;
+        let pri: u8 = pri
+            .parse()
+            .ok()
+            .filter(|&pri| pri < 192)
+            .ok_or(Error::SyslogParseError("invalid priority"))?;
+
+        let (version, rest) = rest.split_once(' ').ok_or(Error::SyslogParseError("missing version"))?;
+        if version.is_empty() || version.starts_with('0') || !version.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(Error::SyslogParseError("invalid version"));
+        }
+
+        let (timestamp, rest) = header_part(rest, "missing timestamp")?;
+        let (hostname, rest) = header_part(rest, "missing hostname")?;
+        let (app_name, rest) = header_part(rest, "missing application name")?;
+        let (proc_id, rest) = header_part(rest, "missing process id")?;
+        let (msg_id, rest) = header_part(rest, "missing message id")?;
+        let (structured_data, rest) = split_structured_data(rest)?;
+
+        let msg = match rest {
+            "" => None,
+            _ => match rest.strip_prefix(' ') {
+                Some(msg) => Some(msg.strip_prefix(BOM).unwrap_or(msg)).filter(|msg| !msg.is_empty()),
+                None => return Err(Error::SyslogParseError("invalid structured data")),
+            },
+        };
+
+        Ok(Self {
+            facility: pri / 8,
+            severity: pri % 8,
+            timestamp,
+            hostname,
+            app_name,
+            proc_id,
+            msg_id,
+            structured_data,
+            msg,
+        })
+    }
+
+    /// Returns name of the facility, e.g. `daemon` or `local0`.
+    pub fn facility_name(&self) -> &'static str {
+        FACILITIES.get(usize::from(self.facility)).copied().unwrap_or("unknown")
+    }
+
+    /// Returns canonical level name corresponding to the severity.
+    ///
+    /// Severities are mapped the same way as journald priorities in the default configuration.
+    pub fn level(&self) -> &'static str {
+        match self.severity {
+            0..=3 => "error",
+            4 | 5 => "warning",
+            6 => "info",
+            _ => "debug",
+        }
+    }
+
+    /// Returns structured data elements as pairs of element id and its parameters.
+    pub fn elements(&self) -> Elements<'a> {
+        Elements(self.structured_data)
+    }
+}
+
+const FACILITIES: [&str; 24] = [
+    "kern", "user", "mail", "daemon", "auth", "syslog", "lpr", "news", "uucp", "cron", "authpriv", "ftp", "ntp",
+    "audit", "alert", "clock", "local0", "local1", "local2", "local3", "local4", "local5", "local6", "local7",
+];
+
+// ---
+
+/// Iterator over structured data elements.
+pub struct Elements<'a>(&'a str);
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = element_end(self.0.as_bytes()).ok()?;
+        let inner = &self.0[1..end - 1];
+        self.0 = &self.0[end..];
+        Some(inner.split_once(' ').unwrap_or((inner, "")))
+    }
+}
+
+/// Iterator over parameters of a structured data element, e.g. `a="1" b="x"`.
+pub struct Params<'a>(&'a str);
+
+impl<'a> Params<'a> {
+    pub fn new(params: &'a str) -> Self {
+        Self(params)
+    }
+}
+
+impl<'a> Iterator for Params<'a> {
+    type Item = Result<(&'a str, RawValue<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = self.0.trim_start_matches(' ');
+        if s.is_empty() {
+            return None;
+        }
+
+        let Some((name, rest)) = s.split_once("=\"") else {
+            self.0 = "";
+            return Some(Err(Error::SyslogParseError("invalid structured data parameter")));
+        };
+        let Some(end) = quoted_end(rest.as_bytes()) else {
+            self.0 = "";
+            return Some(Err(Error::SyslogParseError(
+                "unterminated structured data parameter value",
+            )));
+        };
+
+        let value = &rest[..end];
+        self.0 = &rest[end + 1..];
+
+        let value = if value.contains('\\') {
+            EncodedString::sd_param(value)
+        } else {
+            EncodedString::raw(value)
+        };
+
+        Some(Ok((name, RawValue::String(value))))
+    }
+}
+
+// ---
+
+fn header_part<'a>(s: &'a str, error: &'static str) -> Result<(Option<&'a str>, &'a str)> {
+    let (value, rest) = s.split_once(' ').ok_or(Error::SyslogParseError(error))?;
+    if value.is_empty() {
+        return Err(Error::SyslogParseError(error));
+    }
+    Ok(((value != NIL).then_some(value), rest))
+}
+
+fn split_structured_data(s: &str) -> Result<(&str, &str)> {
+    if let Some(rest) = s.strip_prefix(NIL) {
+        return Ok(("", rest));
+    }
+
+    let mut end = 0;
+    while s.as_bytes().get(end) == Some(&b'[') {
+        end += element_end(&s.as_bytes()[end..])?;
+    }
+    if end == 0 {
+        return Err(Error::SyslogParseError("invalid structured data"));
+    }
+
+    Ok(s.split_at(end))
+}
+
+/// Returns position after the closing bracket of the structured data element at the start of the data.
+fn element_end(data: &[u8]) -> Result<usize> {
+    if data.first() != Some(&b'[') {
+        return Err(Error::SyslogParseError("invalid structured data"));
+    }
+
+    let mut i = 1;
+    while i < data.len() {
+        match data[i] {
+            b'"' => {
+                let end = quoted_end(&data[i + 1..]).ok_or(Error::SyslogParseError("unterminated structured data"))?;
+                i += end + 1;
+            }
+            b']' => return Ok(i + 1),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Err(Error::SyslogParseError("unterminated structured data"))
+}
+
+/// Returns position of the closing quote, skipping escaped characters.
+fn quoted_end(data: &[u8]) -> Option<usize> {
+    let mut escaped = false;
+    for (i, &b) in data.iter().enumerate() {
+        match b {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'"' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,107 @@
+use super::*;
+
+use encstr::{AnyEncodedString, Builder};
+use rstest::rstest;
+
+#[rstest]
+#[case(b"<134>1 ", true)]
+#[case(b"<0>", true)]
+#[case(b"<1234>", false)]
+#[case(b"<>", false)]
+#[case(b"<13", false)]
+#[case(b"134>", false)]
+fn test_looks_like_syslog(#[case] data: &[u8], #[case] expected: bool) {
+    assert_eq!(looks_like_syslog(data), expected);
+}
+
+#[test]
+fn test_parse() {
+    let line = r#"<134>1 2023-10-11T22:14:15.003Z host1 app 1234 ID47 [a@1 x="1" y="a \"b\""][b@2] hello"#;
+    let message = Message::parse(line).unwrap();
+    assert_eq!(
+        message,
+        Message {
+            facility: 16,
+            severity: 6,
+            timestamp: Some("2023-10-11T22:14:15.003Z"),
+            hostname: Some("host1"),
+            app_name: Some("app"),
+            proc_id: Some("1234"),
+            msg_id: Some("ID47"),
+            structured_data: r#"[a@1 x="1" y="a \"b\""][b@2]"#,
+            msg: Some("hello"),
+        }
+    );
+    assert_eq!(message.facility_name(), "local0");
+    assert_eq!(message.level(), "info");
+    assert_eq!(
+        message.elements().collect::<Vec<_>>(),
+        vec![("a@1", r#"x="1" y="a \"b\"""#), ("b@2", "")]
+    );
+}
+
+#[test]
+fn test_parse_nil() {
+    let message = Message::parse("<11>1 - - - - - -").unwrap();
+    assert_eq!(
+        message,
+        Message {
+            facility: 1,
+            severity: 3,
+            ..Default::default()
+        }
+    );
+    assert_eq!(message.level(), "error");
+    assert_eq!(message.elements().count(), 0);
+
+    let message = Message::parse("<15>1 - - - - - - \u{feff}msg").unwrap();
+    assert_eq!(message.msg, Some("msg"));
+    assert_eq!(message.level(), "debug");
+}
+
+#[rstest]
+#[case("134>1 - - - - - -")]
+#[case("<192>1 - - - - - -")]
+#[case("<134>0 - - - - - -")]
+#[case("<134>1 - - - - -")]
+#[case("<134>1 - - - - - [a x=\"1\"")]
+#[case("<134>1 - - - - - [a x=\"1]")]
+#[case("<134>1 - - - - - x")]
+#[case("<134>1 - - - - - [a]x")]
+fn test_parse_invalid(#[case] line: &str) {
+    assert!(Message::parse(line).is_err());
+}
+
+#[test]
+fn test_params() {
+    let params = Params::new(r#"x="1" y="a \"b\"" z="c\]""#)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        params,
+        vec![
+            ("x", RawValue::String(EncodedString::raw("1"))),
+            ("y", RawValue::String(EncodedString::sd_param(r#"a \"b\""#))),
+            ("z", RawValue::String(EncodedString::sd_param(r#"c\]"#))),
+        ]
+    );
+
+    assert!(Params::new("x=1").next().unwrap().is_err());
+    assert!(Params::new(r#"x="1"#).next().unwrap().is_err());
+    assert!(Params::new("  ").next().is_none());
+}
+
+#[rstest]
+#[case(r#"path="C:\temp""#, r"C:\temp")]
+#[case(r#"text="a\nb""#, r"a\nb")]
+#[case(r#"text="\q""#, r"\q")]
+#[case(r#"text="a \"b\" \\ [c\]""#, r#"a "b" \ [c]"#)]
+fn test_params_escapes(#[case] params: &str, #[case] expected: &str) {
+    let (_, value) = Params::new(params).next().unwrap().unwrap();
+    let RawValue::String(value) = value else {
+        panic!("unexpected value {:?}", value);
+    };
+    let mut decoded = Builder::new();
+    value.decode(&mut decoded).unwrap();
+    assert_eq!(decoded.as_str(), expected);
+}
@@ -1,5 +1,5 @@
 // std imports
-use std::{borrow::Borrow, collections::HashMap, sync::Arc, vec::Vec};
+use std::{borrow::Borrow, collections::HashMap, path::Path, sync::Arc, vec::Vec};
 
 // third-party imports
 use enum_map::EnumMap;
@@ -38,6 +38,8 @@ pub trait StylingPush<B: Push<u8>> {
 pub struct Theme {
     packs: EnumMap<Level, StylePack>,
     default: StylePack,
+    palette: Vec<Style>,
+    patched: bool,
     pub indicators: IndicatorPack,
 }
 
@@ -54,6 +56,10 @@ impl Theme {
         Ok(themecfg::Theme::embedded(name)?.into())
     }
 
+    pub fn load_file(path: &Path) -> Result<Self> {
+        Ok(themecfg::Theme::load_file(path)?.into())
+    }
+
     pub fn list(app_dirs: &AppDirs) -> Result<HashMap<Arc<str>, ThemeInfo>> {
         Ok(themecfg::Theme::list(app_dirs)?)
     }
@@ -66,15 +72,41 @@ impl Theme {
     ) {
         let mut styler = Styler {
             buf,
-            pack: match level {
-                Some(level) => &self.packs[*level],
-                None => &self.default,
-            },
+            pack: self.pack(level),
+            palette: &self.palette,
+            level: *level,
             synced: None,
             current: None,
         };
         f(&mut styler);
-        styler.reset()
+        styler.reset();
+        if self.patched && styler.pack.reset.is_some() {
+            Style::reset().apply(styler.buf);
+        }
+    }
+
+    /// Returns a copy of the theme with the given style merged into the style of each element,
+    /// including the base style used between elements.
+    pub fn patched(&self, patch: &themecfg::Style) -> Self {
+        let mut packs = EnumMap::default();
+        for (level, pack) in &self.packs {
+            packs[level] = pack.patched(patch);
+        }
+        Self {
+            packs,
+            default: self.default.patched(patch),
+            palette: self.palette.clone(),
+            patched: true,
+            indicators: self.indicators.clone(),
+        }
+    }
+
+    #[inline(always)]
+    fn pack(&self, level: &Option<Level>) -> &StylePack {
+        match level {
+            Some(level) => &self.packs[*level],
+            None => &self.default,
+        }
     }
 }
 
@@ -93,9 +125,30 @@ impl<S: Borrow<themecfg::Theme>> From<S> for Theme {
             };
             packs[*level] = StylePack::load(&s.elements.clone().merged(pack.clone()));
         }
+        let palette = if !s.palette.is_empty() {
+            s.palette.clone()
+        } else if !s.elements.is_empty() {
+            DEFAULT_PALETTE
+                .iter()
+                .map(|&code| themecfg::Color::Palette(code))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let palette = palette
+            .iter()
+            .map(|&color| {
+                Style::from(&themecfg::Style {
+                    foreground: Some(color),
+                    ..Default::default()
+                })
+            })
+            .collect();
         Self {
             default,
             packs,
+            palette,
+            patched: false,
             indicators: IndicatorPack::from(&s.indicators),
         }
     }
@@ -207,6 +260,8 @@ impl From<&themecfg::Style> for Style {
 pub struct Styler<'a, B: Push<u8>> {
     buf: &'a mut B,
     pack: &'a StylePack,
+    palette: &'a [Style],
+    level: Option<Level>,
     synced: Option<usize>,
     current: Option<usize>,
 }
@@ -220,6 +275,36 @@ impl<'a, B: Push<u8>> Styler<'a, B> {
         self.synced = None;
     }
 
+    /// Writes the text produced by `f` using a palette color selected by the hash of the given value.
+    ///
+    /// The same value always gets the same color.
+    /// Falls back to the current style if the theme has no palette.
+    pub fn tinted<F: FnOnce(&mut B)>(&mut self, value: &[u8], f: F) {
+        if self.palette.is_empty() {
+            return self.batch(f);
+        }
+
+        let style = &self.palette[(fnv1a(value) % self.palette.len() as u64) as usize];
+        style.apply(self.buf);
+        f(self.buf);
+        Style::reset().apply(self.buf);
+        self.synced = None;
+    }
+
+    /// Writes the output of `f` using the styles of another theme, e.g. a theme [patched](Theme::patched) by a rule,
+    /// and switches back to the styles of the original theme afterwards.
+    pub fn restyled<R, F: FnOnce(&mut Self) -> R>(&mut self, theme: &'a Theme, f: F) -> R {
+        let pack = std::mem::replace(&mut self.pack, theme.pack(&self.level));
+        self.synced = None;
+        let result = f(self);
+        if self.pack.reset.is_some() {
+            Style::reset().apply(self.buf);
+        }
+        self.pack = pack;
+        self.synced = None;
+        result
+    }
+
     #[inline(always)]
     fn set(&mut self, e: Element) -> Option<usize> {
         self.set_style(self.pack.elements[e])
@@ -274,50 +359,88 @@ struct StylePack {
     elements: EnumMap<Element, Option<usize>>,
     reset: Option<usize>,
     styles: Vec<Style>,
+    sources: Vec<themecfg::Style>,
 }
 
 impl StylePack {
-    fn add(&mut self, element: Element, style: &Style) {
-        let pos = match self.styles.iter().position(|x| x == style) {
+    fn add(&mut self, element: Element, source: &themecfg::Style) {
+        let style = Style::from(source);
+        let pos = match self.styles.iter().position(|x| *x == style) {
             Some(pos) => pos,
             None => {
-                self.styles.push(style.clone());
+                self.styles.push(style);
+                self.sources.push(source.clone());
                 self.styles.len() - 1
             }
         };
         self.elements[element] = Some(pos);
     }
 
+    fn patched(&self, patch: &themecfg::Style) -> Self {
+        let sources: Vec<_> = self.sources.iter().map(|s| s.clone().merged(patch)).collect();
+        Self {
+            elements: self.elements.clone(),
+            reset: self.reset,
+            styles: sources.iter().map(Style::from).collect(),
+            sources,
+        }
+    }
+
     fn load(s: &themecfg::StylePack) -> Self {
         let mut result = Self::default();
 
         let items = s.items();
         if !items.is_empty() {
             result.styles.push(Style::reset());
+            result.sources.push(themecfg::Style::default());
             result.reset = Some(0);
         }
 
         for (&element, style) in s.items() {
-            result.add(element, &Style::from(style))
+            result.add(element, style)
         }
 
-        if let Some(base) = s.items().get(&Element::Boolean) {
+        if !items.is_empty() && !items.contains_key(&Element::Match) {
+            let style = themecfg::Style {
+                modes: vec![themecfg::Mode::Reverse],
+                ..Default::default()
+            };
+            result.add(Element::Match, &style);
+        }
+
+        // styles of specific elements are based on the styles of more generic ones
+        let inherited = |element: Element, base: Option<&themecfg::Style>| match (base, items.get(&element)) {
+            (Some(base), Some(patch)) => Some(base.clone().merged(patch)),
+            (Some(base), None) => Some(base.clone()),
+            (None, style) => style.cloned(),
+        };
+
+        for element in [Element::String, Element::Number, Element::Null] {
+            if let Some(style) = inherited(element, items.get(&Element::Value)) {
+                result.add(element, &style);
+            }
+        }
+
+        if let Some(base) = inherited(Element::Boolean, items.get(&Element::Value)) {
+            result.add(Element::Boolean, &base);
             for variant in [Element::BooleanTrue, Element::BooleanFalse] {
-                let mut style = base.clone();
-                if let Some(patch) = s.items().get(&variant) {
-                    style = style.merged(patch)
+                if let Some(style) = inherited(variant, Some(&base)) {
+                    result.add(variant, &style);
                 }
-                result.add(variant, &Style::from(&style));
             }
         }
 
+        if let Some(style) = inherited(Element::KeyValueSeparator, items.get(&Element::Field)) {
+            result.add(Element::KeyValueSeparator, &style);
+        }
+
         result
     }
 }
 
 // ---
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct IndicatorPack {
     pub sync: SyncIndicatorPack,
 }
@@ -332,7 +455,7 @@ impl From<&themecfg::IndicatorPack> for IndicatorPack {
 
 // ---
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct SyncIndicatorPack {
     pub synced: Indicator,
     pub failed: Indicator,
@@ -349,7 +472,7 @@ impl From<&themecfg::SyncIndicatorPack> for SyncIndicatorPack {
 
 // ---
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Indicator {
     pub value: String,
 }
@@ -378,5 +501,17 @@ impl From<&themecfg::Indicator> for Indicator {
 
 // ---
 
+/// Colors of the 256-color palette used by themes that do not define their own palette.
+const DEFAULT_PALETTE: [u8; 16] = [33, 37, 71, 107, 136, 166, 168, 134, 98, 67, 30, 172, 131, 97, 61, 29];
+
+/// Stable FNV-1a hash, which is guaranteed to give the same result across runs and platforms.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// ---
+
 #[cfg(test)]
 mod tests;
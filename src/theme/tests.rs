@@ -37,3 +37,144 @@ fn test_style_from_rgb_color() {
     // that the conversion didn't panic and produced a valid style
     assert_ne!(style.0, Sequence::reset());
 }
+
+#[test]
+fn test_level_message_style() {
+    let style = |color| themecfg::Style {
+        foreground: Some(themecfg::Color::Plain(color)),
+        ..Default::default()
+    };
+
+    let mut cfg = themecfg::Theme::default();
+    cfg.elements = HashMap::from([(Element::Message, style(themecfg::PlainColor::Green))]).into();
+    cfg.levels.insert(
+        InfallibleLevel::Valid(Level::Error),
+        HashMap::from([(Element::Message, style(themecfg::PlainColor::Red))]).into(),
+    );
+    let theme = Theme::from(&cfg);
+
+    let format = |level| {
+        let mut buf = Vec::new();
+        theme.apply(&mut buf, &level, |s| {
+            s.element(Element::Message, |s| s.batch(|buf| buf.extend_from_slice(b"hello!")));
+        });
+        String::from_utf8(buf).unwrap()
+    };
+
+    let error = format(Some(Level::Error));
+    let info = format(Some(Level::Info));
+    let none = format(None);
+    assert_ne!(error, info);
+    assert_eq!(info, none);
+    assert!(error.contains("31m") && error.contains("hello!"));
+    assert!(info.contains("32m") && info.contains("hello!"));
+}
+
+#[test]
+fn test_tinted() {
+    let mut cfg = themecfg::Theme::default();
+    cfg.palette = vec![themecfg::Color::Palette(1), themecfg::Color::Palette(2)];
+    let theme = Theme::from(&cfg);
+
+    let format = |value: &[u8]| {
+        let mut buf = Vec::new();
+        theme.apply(&mut buf, &None, |s| s.tinted(value, |buf| buf.extend_from_slice(b"|")));
+        String::from_utf8(buf).unwrap()
+    };
+
+    assert_eq!(format(b"a"), format(b"a"));
+    assert_ne!(format(b"a"), format(b"b"));
+    assert_eq!(format(b"a"), "\x1b[0;38;5;1m|\x1b[0m");
+    assert_eq!(format(b"b"), "\x1b[0;38;5;2m|\x1b[0m");
+}
+
+#[test]
+fn test_tinted_no_palette() {
+    let theme = Theme::none();
+    let mut buf = Vec::new();
+    theme.apply(&mut buf, &None, |s| s.tinted(b"a", |buf| buf.extend_from_slice(b"|")));
+    assert_eq!(buf, b"|");
+}
+
+#[test]
+fn test_patched() {
+    let mut cfg = themecfg::Theme::default();
+    cfg.elements = HashMap::from([(
+        Element::Message,
+        themecfg::Style {
+            foreground: Some(themecfg::Color::Plain(themecfg::PlainColor::Green)),
+            ..Default::default()
+        },
+    )])
+    .into();
+    let theme = Theme::from(&cfg);
+    let patched = theme.patched(&themecfg::Style {
+        background: Some(themecfg::Color::Plain(themecfg::PlainColor::Red)),
+        ..Default::default()
+    });
+
+    let format = |theme: &Theme| {
+        let mut buf = Vec::new();
+        theme.apply(&mut buf, &None, |s| {
+            s.batch(|buf| buf.extend_from_slice(b"<"));
+            s.element(Element::Message, |s| s.batch(|buf| buf.extend_from_slice(b"hello!")));
+        });
+        String::from_utf8(buf).unwrap()
+    };
+
+    assert_eq!(format(&theme), "\x1b[0m<\x1b[0;32mhello!\x1b[0m");
+    assert_eq!(format(&patched), "\x1b[0;41m<\x1b[0;41;32mhello!\x1b[0;41m\x1b[0m");
+
+    let mut buf = Vec::new();
+    theme.apply(&mut buf, &None, |s| {
+        s.restyled(&patched, |s| s.batch(|buf| buf.extend_from_slice(b"a")));
+        s.batch(|buf| buf.extend_from_slice(b"b"));
+    });
+    assert_eq!(String::from_utf8(buf).unwrap(), "\x1b[0;41ma\x1b[0m\x1b[0mb\x1b[0m");
+}
+
+#[test]
+fn test_inherited_styles() {
+    use themecfg::{Mode, PlainColor};
+
+    let style = |color, modes: &[Mode]| themecfg::Style {
+        foreground: Some(themecfg::Color::Plain(color)),
+        modes: modes.to_vec(),
+        ..Default::default()
+    };
+
+    let format = |cfg: &themecfg::Theme, element| {
+        let mut buf = Vec::new();
+        Theme::from(cfg).apply(&mut buf, &None, |s| {
+            s.element(element, |s| s.batch(|buf| buf.extend_from_slice(b"x")));
+        });
+        String::from_utf8(buf).unwrap()
+    };
+
+    let mut base = themecfg::Theme::default();
+    base.elements = HashMap::from([
+        (Element::Value, style(PlainColor::Green, &[Mode::Underline])),
+        (Element::Number, style(PlainColor::Blue, &[])),
+        (Element::BooleanTrue, style(PlainColor::Cyan, &[Mode::Bold])),
+        (Element::Field, style(PlainColor::Red, &[])),
+    ])
+    .into();
+
+    let mut expected = themecfg::Theme::default();
+    expected.elements = HashMap::from([
+        (Element::String, style(PlainColor::Green, &[Mode::Underline])),
+        (Element::Number, style(PlainColor::Blue, &[Mode::Underline])),
+        (Element::BooleanTrue, style(PlainColor::Cyan, &[Mode::Bold])),
+        (Element::KeyValueSeparator, style(PlainColor::Red, &[])),
+    ])
+    .into();
+
+    for element in [
+        Element::String,
+        Element::Number,
+        Element::BooleanTrue,
+        Element::KeyValueSeparator,
+    ] {
+        assert_eq!(format(&base, element), format(&expected, element), "{:?}", element);
+    }
+}
@@ -47,6 +47,8 @@ pub enum Error {
     FailedToListCustomThemes(#[from] io::Error),
     #[error("invalid tag {value}", value=.value.hlq())]
     InvalidTag { value: Arc<str>, suggestions: Suggestions },
+    #[error("theme {name} extends itself: {chain}", name=.name.hlq())]
+    CyclicInheritance { name: Arc<str>, chain: String },
 }
 
 /// Error is an error which may occur in the application.
@@ -76,14 +78,20 @@ pub struct Theme {
     pub elements: StylePack,
     pub levels: HashMap<InfallibleLevel, StylePack>,
     pub indicators: IndicatorPack,
+    pub palette: Vec<Color>,
+    /// Name or path of the theme this one is based on, overriding only the keys it sets.
+    pub extends: Option<String>,
 }
 
 impl Theme {
     pub fn load(app_dirs: &AppDirs, name: &str) -> Result<Self> {
+        let source = || Source::locate(Some(app_dirs), None, name);
         match Self::load_from(&Self::themes_dir(app_dirs), name) {
-            Ok(v) => Ok(v),
+            Ok(v) => v.resolved(Some(app_dirs), source),
             Err(Error::ThemeNotFound { .. }) => match Self::load_embedded::<Assets>(name) {
-                Ok(v) => Ok(v),
+                Ok(v) => v.resolved(Some(app_dirs), source),
+                // a name that is neither a custom nor a stock theme is taken as a path of the theme file
+                Err(Error::ThemeNotFound { .. }) if Path::new(name).is_file() => Self::load_file(Path::new(name)),
                 Err(Error::ThemeNotFound { name, mut suggestions }) => {
                     if let Ok(variants) = Self::custom_names(app_dirs) {
                         let variants = variants.into_iter().filter_map(|v| v.ok());
@@ -98,7 +106,7 @@ impl Theme {
     }
 
     pub fn embedded(name: &str) -> Result<Self> {
-        Self::load_embedded::<Assets>(name)
+        Self::load_embedded::<Assets>(name)?.resolved(None, || Source::locate(None, None, name))
     }
 
     pub fn list(app_dirs: &AppDirs) -> Result<HashMap<Arc<str>, ThemeInfo>> {
@@ -152,15 +160,86 @@ impl Theme {
         }
     }
 
+    /// Returns the path of the custom theme file with the given name, if there is one,
+    /// looking it up the same way [`Theme::load`] does.
+    pub fn custom_path(app_dirs: &AppDirs, name: &str) -> Option<PathBuf> {
+        let dir = Self::themes_dir(app_dirs);
+        Format::iter()
+            .map(|format| Self::custom_file(&dir, name, format))
+            .find(|path| path.is_file())
+            .or_else(|| {
+                let embedded = Format::iter().any(|format| Assets::get(&Self::filename(name, format)).is_some());
+                Some(PathBuf::from(name)).filter(|path| !embedded && path.is_file())
+            })
+    }
+
+    /// Loads the theme from the given file, the format is selected by the file extension.
+    pub fn load_file(path: &Path) -> Result<Self> {
+        let format = Format::iter()
+            .find(|format| path.extension().is_some_and(|ext| ext == format.extension()))
+            .unwrap_or(Format::Yaml);
+        let name: Arc<str> = path.file_stem().unwrap_or_default().to_string_lossy().into();
+
+        std::fs::read(path)
+            .map_err(ExternalError::from)
+            .and_then(|data| Self::from_buf(&data, format))
+            .map_err(|e| Error::FailedToLoadCustomTheme {
+                name,
+                path: path.into(),
+                source: e,
+            })?
+            .resolved(crate::config::app_dirs().as_ref(), || {
+                Ok(Source::Custom(path.into(), format))
+            })
+    }
+
+    /// Loads the chain of themes this one extends and overlays each one with the keys set in the next one.
+    ///
+    /// The themes are merged before deserialization, so that only the keys present in a theme override the base.
+    fn resolved<F>(self, app_dirs: Option<&AppDirs>, source: F) -> Result<Self>
+    where
+        F: FnOnce() -> Result<Source>,
+    {
+        if self.extends.is_none() {
+            return Ok(self);
+        }
+
+        let source = source()?;
+        let raw = Self::load_chain(app_dirs, &source, &mut Vec::new())?;
+        json::from_value(raw).map_err(|e| source.error(e.into()))
+    }
+
+    fn load_chain(app_dirs: Option<&AppDirs>, source: &Source, chain: &mut Vec<String>) -> Result<json::Value> {
+        let id = source.to_string();
+        if chain.contains(&id) {
+            chain.push(id);
+            return Err(Error::CyclicInheritance {
+                name: chain[0].as_str().into(),
+                chain: chain.join(" -> "),
+            });
+        }
+        chain.push(id);
+
+        let mut raw = source.read_raw()?;
+        if let Some(base) = raw.as_object_mut().and_then(|object| object.remove("extends")) {
+            let base: String = json::from_value(base).map_err(|e| source.error(e.into()))?;
+            // the base is looked up next to the theme extending it first
+            let near = match source {
+                Source::Custom(path, _) => path.parent(),
+                Source::Embedded(..) => None,
+            };
+            let base = Source::locate(app_dirs, near, &base)?;
+            let mut result = Self::load_chain(app_dirs, &base, chain)?;
+            overlay(&mut result, raw);
+            raw = result;
+        }
+
+        Ok(raw)
+    }
+
     fn load_from(dir: &Path, name: &str) -> Result<Self> {
         for format in Format::iter() {
-            let filename = Self::filename(name, format);
-            let path = PathBuf::from(&filename);
-            let path = if matches!(path.components().next(), Some(Component::ParentDir | Component::CurDir)) {
-                path
-            } else {
-                dir.join(&filename)
-            };
+            let path = Self::custom_file(dir, name, format);
 
             let map_err = |e: ExternalError, path: PathBuf| Error::FailedToLoadCustomTheme {
                 name: name.into(),
@@ -185,6 +264,16 @@ impl Theme {
         })
     }
 
+    fn custom_file(dir: &Path, name: &str, format: Format) -> PathBuf {
+        let filename = Self::filename(name, format);
+        let path = PathBuf::from(&filename);
+        if matches!(path.components().next(), Some(Component::ParentDir | Component::CurDir)) {
+            path
+        } else {
+            dir.join(&filename)
+        }
+    }
+
     fn filename(name: &str, format: Format) -> String {
         if Self::strip_extension(name, format).is_some() {
             return name.to_string();
@@ -398,6 +487,8 @@ pub enum Element {
     MessageDelimiter,
     Field,
     Key,
+    KeyValueSeparator,
+    Value,
     Array,
     Object,
     String,
@@ -407,11 +498,12 @@ pub enum Element {
     BooleanFalse,
     Null,
     Ellipsis,
+    Match,
 }
 
 // ---
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 #[serde(default)]
 pub struct Style {
@@ -601,6 +693,101 @@ struct Assets;
 
 // ---
 
+/// Location of a theme file used to resolve theme inheritance.
+enum Source {
+    Custom(PathBuf, Format),
+    Embedded(String, Format),
+}
+
+impl Source {
+    /// Finds the theme with the given name or path the same way [`Theme::load`] does,
+    /// looking into the `near` directory first if it is given.
+    fn locate(app_dirs: Option<&AppDirs>, near: Option<&Path>, name: &str) -> Result<Self> {
+        let dirs = near
+            .map(PathBuf::from)
+            .into_iter()
+            .chain(app_dirs.map(Theme::themes_dir));
+        for dir in dirs {
+            for format in Format::iter() {
+                let path = Theme::custom_file(&dir, name, format);
+                if path.is_file() {
+                    return Ok(Self::Custom(path, format));
+                }
+            }
+        }
+        for format in Format::iter() {
+            let filename = Theme::filename(name, format);
+            if Assets::get(&filename).is_some() {
+                return Ok(Self::Embedded(filename, format));
+            }
+        }
+
+        Err(Error::ThemeNotFound {
+            name: name.into(),
+            suggestions: Suggestions::new(name, Theme::embedded_names()),
+        })
+    }
+
+    fn read_raw(&self) -> Result<json::Value> {
+        let (data, format) = match self {
+            Self::Custom(path, format) => (std::fs::read(path).map_err(|e| self.error(e.into()))?.into(), *format),
+            Self::Embedded(filename, format) => (Assets::get(filename).unwrap().data, *format),
+        };
+        Self::parse(&data, format).map_err(|e| self.error(e))
+    }
+
+    fn parse(data: &[u8], format: Format) -> Result<json::Value, ExternalError> {
+        let s = std::str::from_utf8(data)?;
+        match format {
+            Format::Yaml => Ok(yaml::from_str(s)?.remove(0)),
+            Format::Toml => Ok(toml::from_str(s)?),
+            Format::Json => Ok(json::from_str(s)?),
+        }
+    }
+
+    fn error(&self, source: ExternalError) -> Error {
+        match self {
+            Self::Custom(path, _) => Error::FailedToLoadCustomTheme {
+                name: path.file_stem().unwrap_or_default().to_string_lossy().into(),
+                path: path.as_path().into(),
+                source,
+            },
+            Self::Embedded(filename, _) => Error::FailedToLoadEmbeddedTheme {
+                name: filename.as_str().into(),
+                source,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(path, _) => write!(f, "{}", path.display()),
+            Self::Embedded(filename, _) => write!(f, "{}", filename),
+        }
+    }
+}
+
+/// Recursively merges the objects of the patch into the base, other values of the patch replace the base ones.
+fn overlay(base: &mut json::Value, patch: json::Value) {
+    match (base, patch) {
+        (json::Value::Object(base), json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                match base.get_mut(&key) {
+                    Some(item) => overlay(item, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+// ---
+
 fn unhex(high: u8, low: u8) -> Option<u8> {
     unhex_one(high).and_then(|high| unhex_one(low).map(|low| (high << 4) + low))
 }
@@ -34,10 +34,83 @@ fn test_load_from() {
     assert!(Theme::load_from(&path, "invalid-type").is_err());
 }
 
+#[test]
+fn test_custom_path() {
+    let app_dirs = AppDirs {
+        config_dir: PathBuf::from("src/testing/assets"),
+        cache_dir: Default::default(),
+        system_config_dirs: Default::default(),
+    };
+    assert_eq!(
+        Theme::custom_path(&app_dirs, "test"),
+        Some(PathBuf::from("src/testing/assets/themes/test.toml"))
+    );
+    assert_eq!(Theme::custom_path(&app_dirs, "universal"), None);
+    assert_eq!(Theme::custom_path(&app_dirs, "non-existent"), None);
+}
+
+#[test]
+fn test_load_file() {
+    let path = PathBuf::from("src/testing/assets/themes");
+    assert_ne!(Theme::load_file(&path.join("test.toml")).unwrap().elements.len(), 0);
+    assert!(Theme::load_file(&path.join("non-existent.yaml")).is_err());
+    assert!(Theme::load_file(&path.join("invalid.json")).is_err());
+    assert!(Theme::load_file(&path.join("invalid-type.yaml")).is_err());
+}
+
+#[test]
+fn test_extends() {
+    let app_dirs = AppDirs {
+        config_dir: PathBuf::from("src/testing/assets"),
+        cache_dir: Default::default(),
+        system_config_dirs: Default::default(),
+    };
+
+    let base = Theme::load(&app_dirs, "test").unwrap();
+    let theme = Theme::load(&app_dirs, "extended").unwrap();
+    assert_eq!(
+        theme.elements[&Element::Key].foreground,
+        Some(Color::Plain(PlainColor::Red))
+    );
+    assert_eq!(theme.elements[&Element::Message], base.elements[&Element::Message]);
+    assert_eq!(theme.elements.len(), base.elements.len());
+
+    let theme = Theme::load_file(Path::new("src/testing/assets/themes/extended.yaml")).unwrap();
+    assert_eq!(
+        theme.elements[&Element::Key].foreground,
+        Some(Color::Plain(PlainColor::Red))
+    );
+
+    assert!(matches!(
+        Theme::load(&app_dirs, "cycle-a"),
+        Err(Error::CyclicInheritance { .. })
+    ));
+}
+
 #[test]
 fn test_embedded() {
     assert_ne!(Theme::embedded("universal").unwrap().elements.len(), 0);
     assert!(Theme::embedded("non-existent").is_err());
+
+    for (name, tag) in [("dark", Tag::Dark), ("light", Tag::Light), ("solarized", Tag::Dark)] {
+        let theme = Theme::embedded(name).unwrap();
+        assert_ne!(theme.elements.len(), 0, "{}", name);
+        assert!(theme.tags.contains(tag), "{}", name);
+    }
+}
+
+#[test]
+fn test_load_path_fallback() {
+    let app_dirs = AppDirs {
+        config_dir: PathBuf::from("src/testing/assets/non-existent"),
+        cache_dir: Default::default(),
+        system_config_dirs: Default::default(),
+    };
+    let path = "src/testing/assets/themes/test.toml";
+    assert_ne!(Theme::load(&app_dirs, path).unwrap().elements.len(), 0);
+    assert_eq!(Theme::custom_path(&app_dirs, path), Some(PathBuf::from(path)));
+    assert!(Theme::load(&app_dirs, "src/testing/assets/themes/non-existent.toml").is_err());
+    assert_eq!(Theme::custom_path(&app_dirs, "dark"), None);
 }
 
 #[test]
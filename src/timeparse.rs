@@ -21,6 +21,19 @@ pub fn parse_time(s: &str, tz: &Tz, format: &DateTimeFormat) -> Result<DateTime<
         .ok_or(Error::UnrecognizedTime(s.into()))
 }
 
+/// Parses a time literal of a query, either relative to the current time, e.g. `-1h`,
+/// or in RFC 3339 format, e.g. `2024-01-01T00:00:00Z`, taking time without an offset as UTC.
+pub fn parse_time_literal(s: &str) -> Result<DateTime<Utc>> {
+    let s = s.trim();
+    let tz = Tz::from(Utc);
+    None.or_else(|| relative_past(s))
+        .or_else(|| relative_future(s))
+        .or_else(|| rfc3339(s, &tz))
+        .or_else(|| rfc3339_weak(s, &tz))
+        .map(|ts| ts.with_timezone(&Utc))
+        .ok_or(Error::UnrecognizedTime(s.into()))
+}
+
 fn relative_past(s: &str) -> Option<DateTime<Tz>> {
     if let Some(stripped) = s.strip_prefix('-') {
         let d = parse_duration(stripped).ok()?;
@@ -272,3 +272,44 @@ fn test_relative_time_boundary_cases() {
     let result = parse_time("+0invalidunit", &tz, &format);
     assert!(result.is_err(), "Should fail to parse '+0invalidunit'");
 }
+
+#[test]
+fn test_relative_time_window() {
+    let tz = Tz::FixedOffset(Utc.fix());
+    let format = DateTimeFormat::new();
+
+    let before = Utc::now();
+    let since = parse_time("-1h", &tz, &format).unwrap();
+    let until = parse_time("-30m", &tz, &format).unwrap();
+    let after = Utc::now();
+
+    let window = until.signed_duration_since(since);
+    assert!(window >= Duration::minutes(30), "window is too short: {window}");
+    assert!(
+        window <= Duration::minutes(30) + (after - before),
+        "window is too long: {window}"
+    );
+    assert!(until <= after - Duration::minutes(30));
+    assert!(since >= before - Duration::hours(1));
+}
+
+#[test]
+fn test_parse_time_literal() {
+    let utc = |s| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+
+    assert_eq!(
+        parse_time_literal("2024-01-01T02:00:00+02:00").unwrap(),
+        utc("2024-01-01T00:00:00Z")
+    );
+    assert_eq!(
+        parse_time_literal("2024-01-01 00:00:00").unwrap(),
+        utc("2024-01-01T00:00:00Z")
+    );
+
+    let before = Utc::now();
+    let ts = parse_time_literal("-1h").unwrap();
+    assert!(ts <= before - Duration::minutes(59) && ts >= before - Duration::minutes(61));
+
+    assert!(parse_time_literal("yesterday").is_err());
+    assert!(parse_time_literal("2024").is_err());
+}
@@ -0,0 +1,139 @@
+//! Mapping of Windows Event Log records exported with `Get-WinEvent | ConvertTo-Json` onto plain fields.
+
+// third-party imports
+use serde::Deserialize;
+use serde_json::{self as json};
+
+// local imports
+use crate::{
+    level::Level,
+    model::{RawRecordFields, RawValue, intern},
+    settings::{PredefinedFields, RawLevelFieldVariant},
+};
+
+// ---
+
+const TIME: &str = "TimeCreated";
+const LEVEL: &str = "LevelDisplayName";
+const LEVEL_NUMBER: &str = "Level";
+const MESSAGE: &str = "Message";
+const PROPERTIES: &str = "Properties";
+
+/// Returns the predefined fields extended with the Windows Event Log field names,
+/// which take precedence over the configured ones.
+///
+/// The level is taken from `LevelDisplayName`, or from the numeric `Level` if the record has no display name,
+/// e.g. because it was left out with `Select-Object`.
+pub fn predefined_fields(base: &PredefinedFields) -> PredefinedFields {
+    let mut result = base.clone();
+
+    result.time.0.names.insert(0, TIME.into());
+    result.message.0.names.insert(0, MESSAGE.into());
+
+    let variants = [
+        (
+            LEVEL,
+            [
+                (Level::Error, vec!["Critical", "Error"]),
+                (Level::Warning, vec!["Warning"]),
+                (Level::Info, vec!["Information"]),
+                (Level::Debug, vec!["Verbose"]),
+            ],
+        ),
+        (
+            LEVEL_NUMBER,
+            [
+                (Level::Error, vec!["1", "2"]),
+                (Level::Warning, vec!["3"]),
+                (Level::Info, vec!["0", "4"]),
+                (Level::Debug, vec!["5"]),
+            ],
+        ),
+    ];
+    for (i, (name, values)) in variants.into_iter().enumerate() {
+        let variant = RawLevelFieldVariant {
+            names: vec![name.into()],
+            values: values
+                .into_iter()
+                .map(|(level, names)| (level.into(), names.into_iter().map(Into::into).collect()))
+                .collect(),
+            level: None,
+        };
+        result.level.variants.insert(i, variant);
+    }
+
+    result
+}
+
+/// Replaces a `/Date(ms)/` wrapped creation time with the bare number of milliseconds since the epoch
+/// and the `Properties` list with a field per item, e.g. `Properties.0`.
+///
+/// Parts having an unexpected shape are left as is.
+pub fn flatten<'a>(fields: &mut RawRecordFields<'a>) {
+    if !fields.iter().any(|&(key, _)| key == TIME || key == PROPERTIES) {
+        return;
+    }
+
+    let mut result = RawRecordFields::default();
+    for &(key, value) in fields.iter() {
+        match (key, value) {
+            (TIME, RawValue::String(s)) => match unwrap_date(s.source()) {
+                Some(ms) => result.push((key, RawValue::Number(ms))),
+                None => result.push((key, value)),
+            },
+            (PROPERTIES, RawValue::Array(array)) => match json::from_str::<Vec<Property>>(array.get()) {
+                Ok(properties) => {
+                    for (i, property) in properties.into_iter().enumerate() {
+                        result.push((property_key(i), RawValue::from(property.value)));
+                    }
+                }
+                Err(_) => result.push((key, value)),
+            },
+            _ => result.push((key, value)),
+        }
+    }
+
+    *fields = result;
+}
+
+/// Returns the key of the field made of the `Properties` list item with the given index, e.g. `Properties.0`.
+fn property_key(i: usize) -> &'static str {
+    intern(&format!("{PROPERTIES}.{i}"))
+}
+
+/// Extracts the number of milliseconds from a JSON string like `"/Date(1700000000123)/"`,
+/// where the slashes may be escaped and the number may be followed by a time zone offset, e.g. `+0100`,
+/// which does not affect the moment in time.
+fn unwrap_date(source: &str) -> Option<&str> {
+    let inner = source.strip_prefix('"')?.strip_suffix('"')?;
+    let inner = inner
+        .strip_prefix("/Date(")
+        .or_else(|| inner.strip_prefix(r"\/Date("))?;
+    let inner = inner.strip_suffix(")/").or_else(|| inner.strip_suffix(r")\/"))?;
+
+    let digits = inner.strip_prefix('-').unwrap_or(inner);
+    let end = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    if end == 0 {
+        return None;
+    }
+    let offset = &digits[end..];
+    if !offset.is_empty() {
+        let offset = offset.strip_prefix(['+', '-'])?;
+        if offset.len() != 4 || !offset.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+    }
+
+    Some(&inner[..inner.len() - offset.len()])
+}
+
+// ---
+
+#[derive(Deserialize)]
+struct Property<'a> {
+    #[serde(borrow, rename = "Value")]
+    value: &'a json::value::RawValue,
+}
+
+#[cfg(test)]
+mod tests;
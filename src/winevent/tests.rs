@@ -0,0 +1,87 @@
+use super::*;
+
+use itertools::Itertools;
+
+use crate::{
+    app::InputFormat,
+    model::{Parser, ParserSettings, RawRecord, RawRecordParser},
+};
+
+const RECORD: &str = concat!(
+    r#"{"Message":"The Windows Update service entered the running state.","Id":7036,"Level":4,"#,
+    r#""ProviderName":"Service Control Manager","TimeCreated":"\/Date(1700000000123)\/","#,
+    r#""LevelDisplayName":"Information","Properties":[{"Value":"Windows Update"},{"Value":"running"}]}"#,
+);
+
+fn raw(line: &str) -> RawRecord<'_> {
+    RawRecordParser::new()
+        .format(Some(InputFormat::WinEvent))
+        .parse(line.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap()
+        .record
+}
+
+#[test]
+fn test_flatten() {
+    let record = raw(RECORD);
+    let fields: Vec<_> = record.fields().map(|(k, v)| (*k, v.raw_str())).collect();
+    assert_eq!(
+        fields,
+        vec![
+            ("Message", r#""The Windows Update service entered the running state.""#),
+            ("Id", "7036"),
+            ("Level", "4"),
+            ("ProviderName", r#""Service Control Manager""#),
+            ("TimeCreated", "1700000000123"),
+            ("LevelDisplayName", r#""Information""#),
+            ("Properties.0", r#""Windows Update""#),
+            ("Properties.1", r#""running""#),
+        ]
+    );
+}
+
+#[test]
+fn test_flatten_many_properties() {
+    let properties = (0..40).map(|i| format!(r#"{{"Value":{i}}}"#)).join(",");
+    let line = format!(r#"{{"Message":"x","Properties":[{properties}]}}"#);
+    let record = raw(&line);
+    let fields: Vec<_> = record.fields().map(|(k, v)| (*k, v.raw_str())).collect();
+    assert_eq!(fields.len(), 41);
+    assert_eq!(fields[1], ("Properties.0", "0"));
+    assert_eq!(fields[33], ("Properties.32", "32"));
+    assert_eq!(fields[40], ("Properties.39", "39"));
+}
+
+#[test]
+fn test_unwrap_date() {
+    assert_eq!(unwrap_date(r#""/Date(1700000000123)/""#), Some("1700000000123"));
+    assert_eq!(unwrap_date(r#""\/Date(1700000000123)\/""#), Some("1700000000123"));
+    assert_eq!(unwrap_date(r#""/Date(1700000000123+0100)/""#), Some("1700000000123"));
+    assert_eq!(unwrap_date(r#""/Date(-1000)/""#), Some("-1000"));
+    assert_eq!(unwrap_date(r#""2023-11-14T22:13:20.123Z""#), None);
+    assert_eq!(unwrap_date(r#""/Date()/""#), None);
+    assert_eq!(unwrap_date(r#""/Date(1700000000123+1)/""#), None);
+}
+
+#[test]
+fn test_predefined_fields() {
+    let predefined = predefined_fields(&PredefinedFields::default());
+    let parser = Parser::new(ParserSettings::new(&predefined, Vec::new(), None));
+    let raw = raw(RECORD);
+    let record = parser.parse(&raw);
+
+    assert_eq!(record.level, Some(Level::Info));
+    assert_eq!(
+        record.message.map(|v| v.raw_str()),
+        Some(r#""The Windows Update service entered the running state.""#)
+    );
+    assert_eq!(
+        record.ts.and_then(|ts| ts.parse()).map(|ts| ts.timestamp_millis()),
+        Some(1700000000123)
+    );
+
+    let raw = self::raw(r#"{"Message":"x","Level":2}"#);
+    assert_eq!(parser.parse(&raw).level, Some(Level::Error));
+}